@@ -0,0 +1,29 @@
+//! Biome codes shared by the climate classifier and run diagnostics.
+
+/// Number of biome codes; valid codes are `0..COUNT`.
+pub const COUNT: usize = 6;
+
+/// Label of each biome code, indexed by code.
+pub const LABELS: [&str; COUNT] = [
+    "polar",
+    "boreal",
+    "temperate",
+    "steppe",
+    "desert",
+    "rainforest",
+];
+
+/// Slot of `biome` in [`LABELS`]; codes past the table read as polar, as the classifier
+/// treats them.
+pub fn index(biome: u8) -> usize {
+    let index = usize::from(biome);
+    if index < COUNT {
+        index
+    } else {
+        0
+    }
+}
+
+pub fn label(biome: u8) -> &'static str {
+    LABELS[index(biome)]
+}
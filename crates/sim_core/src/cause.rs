@@ -10,9 +10,13 @@ pub enum Code {
     HadleyCell,
     HadleyDrift,
     MonsoonOnset,
+    MonsoonRetreat,
     RainShadow,
+    FrontalPrecip,
     HumidityTransport,
+    Evapotranspiration,
     EnergyBalanceAdjustment,
+    ColdAirPooling,
     OrogenyBelt,
     VolcanicAerosolPulse,
     SubsidenceDeltas,
@@ -24,11 +28,14 @@ pub enum Code {
     TideNeap,
     TideSpring,
     SoilFertilityLow,
+    Desertification,
     DroughtFlag,
     FloodFlag,
     StormTrackShift,
+    StormTrack,
     HeatExtreme,
     AlbedoFeedback,
+    SurfaceAlbedo,
     PermafrostThaw,
     GlacierMassBalance,
     FreshwaterPulse,
@@ -49,9 +56,13 @@ impl std::fmt::Display for Code {
             Code::HadleyCell => "hadley_cell",
             Code::HadleyDrift => "hadley_drift",
             Code::MonsoonOnset => "monsoon_onset",
+            Code::MonsoonRetreat => "monsoon_retreat",
             Code::RainShadow => "rain_shadow",
+            Code::FrontalPrecip => "frontal_precip",
             Code::HumidityTransport => "humidity_transport",
+            Code::Evapotranspiration => "evapotranspiration",
             Code::EnergyBalanceAdjustment => "energy_balance_adjustment",
+            Code::ColdAirPooling => "cold_air_pooling",
             Code::OrogenyBelt => "orogeny_belt",
             Code::VolcanicAerosolPulse => "volcanic_aerosol_pulse",
             Code::SubsidenceDeltas => "subsidence_deltas",
@@ -63,11 +74,14 @@ impl std::fmt::Display for Code {
             Code::TideNeap => "tide_neap",
             Code::TideSpring => "tide_spring",
             Code::SoilFertilityLow => "soil_fertility_low",
+            Code::Desertification => "desertification",
             Code::DroughtFlag => "drought_flag",
             Code::FloodFlag => "flood_flag",
             Code::StormTrackShift => "storm_track_shift",
+            Code::StormTrack => "storm_track",
             Code::HeatExtreme => "heat_extreme",
             Code::AlbedoFeedback => "albedo_feedback",
+            Code::SurfaceAlbedo => "surface_albedo",
             Code::PermafrostThaw => "permafrost_thaw",
             Code::GlacierMassBalance => "glacier_mass_balance",
             Code::FreshwaterPulse => "freshwater_pulse",
@@ -82,6 +96,125 @@ impl std::fmt::Display for Code {
     }
 }
 
+/// Simulation subsystem a [`Code`] reports on, in schedule order.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Astronomy,
+    Geodynamics,
+    Atmosphere,
+    Storms,
+    Cryosphere,
+    Ecology,
+    Diagnostics,
+}
+
+/// How much a [`Code`] matters to a reader of the run, lowest first.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Bookkeeping the model records every tick.
+    Routine,
+    /// A shift worth mentioning but not a disruption.
+    Notable,
+    /// An event or hazard that changes the world's trajectory.
+    Major,
+}
+
+impl Code {
+    pub fn subsystem(self) -> Subsystem {
+        match self {
+            Code::CmeEvent
+            | Code::InsolationGradient
+            | Code::ObliquityShift
+            | Code::PrecessionPhase
+            | Code::SolarCyclePeak
+            | Code::TideNeap
+            | Code::TideSpring => Subsystem::Astronomy,
+            Code::OrogenyBelt | Code::VolcanicAerosolPulse | Code::SubsidenceDeltas => {
+                Subsystem::Geodynamics
+            }
+            Code::LatitudeBelt
+            | Code::OrographicLift
+            | Code::SeasonalShift
+            | Code::HadleyCell
+            | Code::HadleyDrift
+            | Code::MonsoonOnset
+            | Code::MonsoonRetreat
+            | Code::RainShadow
+            | Code::FrontalPrecip
+            | Code::HumidityTransport
+            | Code::Evapotranspiration
+            | Code::EnergyBalanceAdjustment
+            | Code::ColdAirPooling
+            | Code::HeatExtreme
+            | Code::StormTrackShift => Subsystem::Atmosphere,
+            Code::StormTrack => Subsystem::Storms,
+            Code::AlbedoFeedback
+            | Code::SurfaceAlbedo
+            | Code::PermafrostThaw
+            | Code::GlacierMassBalance
+            | Code::FreshwaterPulse
+            | Code::SnowmeltSurge
+            | Code::IceMassVariation
+            | Code::SeaLevelContribution => Subsystem::Cryosphere,
+            Code::SoilFertilityLow
+            | Code::Desertification
+            | Code::DroughtFlag
+            | Code::FloodFlag => Subsystem::Ecology,
+            Code::EraEnd | Code::StagnationWarning | Code::CollapseWarning => {
+                Subsystem::Diagnostics
+            }
+        }
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            Code::CmeEvent
+            | Code::VolcanicAerosolPulse
+            | Code::HeatExtreme
+            | Code::Desertification
+            | Code::DroughtFlag
+            | Code::FloodFlag
+            | Code::EraEnd
+            | Code::CollapseWarning => Severity::Major,
+            Code::SolarCyclePeak
+            | Code::TideSpring
+            | Code::OrogenyBelt
+            | Code::SubsidenceDeltas
+            | Code::HadleyDrift
+            | Code::MonsoonOnset
+            | Code::MonsoonRetreat
+            | Code::StormTrackShift
+            | Code::StormTrack
+            | Code::PermafrostThaw
+            | Code::FreshwaterPulse
+            | Code::SnowmeltSurge
+            | Code::SoilFertilityLow
+            | Code::StagnationWarning => Severity::Notable,
+            Code::LatitudeBelt
+            | Code::OrographicLift
+            | Code::SeasonalShift
+            | Code::HadleyCell
+            | Code::RainShadow
+            | Code::FrontalPrecip
+            | Code::HumidityTransport
+            | Code::Evapotranspiration
+            | Code::EnergyBalanceAdjustment
+            | Code::ColdAirPooling
+            | Code::InsolationGradient
+            | Code::ObliquityShift
+            | Code::PrecessionPhase
+            | Code::TideNeap
+            | Code::AlbedoFeedback
+            | Code::SurfaceAlbedo
+            | Code::GlacierMassBalance
+            | Code::IceMassVariation
+            | Code::SeaLevelContribution => Severity::Routine,
+        }
+    }
+}
+
 /// Structured cause entry used for diagnostics and auditing.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Entry {
@@ -89,6 +222,13 @@ pub struct Entry {
     pub code: Code,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
+    /// Per-tick id, assigned by [`Diff::record_cause_with_id`](crate::diff::Diff::record_cause_with_id)
+    /// to causes that derived effects point back at.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub id: Option<u64>,
+    /// Id of the upstream cause this effect derives from, within the same tick.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caused_by: Option<u64>,
 }
 
 impl Entry {
@@ -97,6 +237,114 @@ impl Entry {
             target: target.into(),
             code,
             note,
+            id: None,
+            caused_by: None,
+        }
+    }
+
+    /// Link this cause to the upstream cause with id `source`.
+    pub fn caused_by(mut self, source: u64) -> Self {
+        self.caused_by = Some(source);
+        self
+    }
+}
+
+/// Structured `key=value;key=value` payload for [`Entry::note`].
+///
+/// Builds the same ordering-preserving string the kernels have always emitted and parses
+/// it back so consumers can read numeric payloads without substring matching.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CauseNote {
+    pairs: Vec<(String, String)>,
+}
+
+impl CauseNote {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a pair; values keep the caller's formatting (e.g. `format_args!("{:+.1}", v)`).
+    pub fn with<K: Into<String>, V: std::fmt::Display>(mut self, key: K, value: V) -> Self {
+        self.pairs.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Parse a serialized note, rejecting segments without a `key=` prefix.
+    pub fn parse(note: &str) -> anyhow::Result<Self> {
+        let mut pairs = Vec::new();
+        for segment in note.split(';').filter(|segment| !segment.is_empty()) {
+            let (key, value) = segment
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("cause note segment {:?} lacks '='", segment))?;
+            anyhow::ensure!(
+                !key.is_empty(),
+                "cause note segment {:?} has no key",
+                segment
+            );
+            pairs.push((key.to_string(), value.to_string()));
+        }
+        Ok(Self { pairs })
+    }
+
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(candidate, _)| candidate == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(|value| value.parse().ok())
+    }
+
+    /// Serialized form for [`Entry::new`].
+    pub fn into_note(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl std::fmt::Display for CauseNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (key, value)) in self.pairs.iter().enumerate() {
+            if index > 0 {
+                f.write_str(";")?;
+            }
+            write!(f, "{}={}", key, value)?;
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CauseNote, Code, Severity, Subsystem};
+
+    #[test]
+    fn cause_note_round_trips_and_matches_legacy_format() {
+        let note = CauseNote::new()
+            .with("gradient_km", format_args!("{:.2}", 1.2))
+            .with("multiplier", format_args!("{:.2}", 1.4));
+        let serialized = note.to_string();
+        assert_eq!(serialized, "gradient_km=1.20;multiplier=1.40");
+
+        let parsed = CauseNote::parse(&serialized).expect("note parses");
+        assert_eq!(parsed, note);
+        assert_eq!(parsed.get_f64("multiplier"), Some(1.4));
+
+        let delta = CauseNote::parse("delta_kt=+12.3").expect("signed note parses");
+        assert_eq!(delta.get_f64("delta_kt"), Some(12.3));
+        assert!(CauseNote::parse("no_separator").is_err());
+    }
+
+    #[test]
+    fn storm_track_shift_belongs_to_the_atmosphere_that_emits_it() {
+        assert_eq!(Code::StormTrackShift.subsystem(), Subsystem::Atmosphere);
+        assert_eq!(Code::StormTrack.subsystem(), Subsystem::Storms);
+        assert_eq!(Code::StormTrackShift.severity(), Severity::Notable);
+        assert_eq!(Code::TideNeap.severity(), Severity::Routine);
     }
 }
@@ -0,0 +1,149 @@
+//! Transcendental functions built only from IEEE 754 basic operations.
+//!
+//! `+`, `-`, `*`, `/`, `sqrt`, `%`, `abs`, and `round` are correctly rounded (or exact) on
+//! every conforming target, and Rust never fuses them into FMAs, so any expression built
+//! from them is bit-identical across platforms. `f64::sin`, `powf`, `exp`, `ln`, and friends
+//! defer to the platform libm, whose last-bit results differ between targets; a single
+//! differing bit can flip a tenths-level `round()` and fork a run. Kernel hot paths use the
+//! functions here instead.
+//!
+//! Still on libm: [`crate::world::latitude_equal_area`] (`asin`), evaluated once when an
+//! opt-in equal-area grid is built, so equal-area worlds are only reproducible on one
+//! platform family.
+
+use std::f64::consts::{LN_2, PI, TAU};
+
+/// Terms of the `atanh` series in [`ln`]; the mantissa argument stays below 1/3, so 16
+/// terms reach the limit of `f64` precision.
+const LN_SERIES_TERMS: i32 = 16;
+/// Terms of the Taylor series in [`exp`]; the reduced argument stays within ±ln(2)/2.
+const EXP_SERIES_TERMS: i32 = 18;
+/// 2^54, exact; lifts subnormals into the normal range before [`ln`] reads their exponent.
+const TWO_POW_54: f64 = 18_014_398_509_481_984.0;
+
+fn wrap_angle(mut angle: f64) -> f64 {
+    angle %= TAU;
+    if angle > PI {
+        angle -= TAU;
+    } else if angle < -PI {
+        angle += TAU;
+    }
+    angle
+}
+
+/// Sine of `angle` radians from a 13th-order Taylor polynomial after wrapping into
+/// `-π..=π`; within 3e-5 of the true value at the wrap edges and far closer near zero.
+pub fn sin(angle: f64) -> f64 {
+    let x = wrap_angle(angle);
+    let x2 = x * x;
+    let x3 = x * x2;
+    let x5 = x3 * x2;
+    let x7 = x5 * x2;
+    let x9 = x7 * x2;
+    let x11 = x9 * x2;
+    let x13 = x11 * x2;
+    x - x3 / 6.0 + x5 / 120.0 - x7 / 5_040.0 + x9 / 362_880.0 - x11 / 39_916_800.0
+        + x13 / 6_227_020_800.0
+}
+
+/// Natural logarithm of `value`; `NaN` for negative or `NaN` input, `-∞` at zero.
+pub fn ln(value: f64) -> f64 {
+    if value.is_nan() || value < 0.0 {
+        return f64::NAN;
+    }
+    if value == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if value.is_infinite() {
+        return f64::INFINITY;
+    }
+    // Split into mantissa in 1..2 and a power of two; scaling subnormals first keeps the
+    // exponent field meaningful.
+    let (value, bias) = if value < f64::MIN_POSITIVE {
+        (value * TWO_POW_54, -54)
+    } else {
+        (value, 0)
+    };
+    let bits = value.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32 - 1_023 + bias;
+    let mantissa = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+
+    // ln(m) = 2·atanh(s) with s = (m - 1) / (m + 1) in 0..1/3.
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0;
+    for n in 0..LN_SERIES_TERMS {
+        sum += term / f64::from(2 * n + 1);
+        term *= s2;
+    }
+    2.0 * sum + f64::from(exponent) * LN_2
+}
+
+/// `e` raised to `value`; saturates to `0` and `+∞` outside the normal `f64` range.
+pub fn exp(value: f64) -> f64 {
+    if value.is_nan() {
+        return f64::NAN;
+    }
+    if value > 709.0 {
+        return f64::INFINITY;
+    }
+    if value < -708.0 {
+        return 0.0;
+    }
+    // e^x = 2^k · e^r with |r| <= ln(2)/2; 2^k is assembled exactly from its bits.
+    let k = (value / LN_2).round();
+    let r = value - k * LN_2;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..=EXP_SERIES_TERMS {
+        term = term * r / f64::from(n);
+        sum += term;
+    }
+    let scale = f64::from_bits(((k as i64 + 1_023) as u64) << 52);
+    sum * scale
+}
+
+/// `base` raised to `exponent` for non-negative `base`, as `exp(exponent · ln(base))`.
+pub fn powf(base: f64, exponent: f64) -> f64 {
+    if exponent == 0.0 {
+        return 1.0;
+    }
+    if base == 0.0 {
+        return if exponent > 0.0 { 0.0 } else { f64::INFINITY };
+    }
+    exp(exponent * ln(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approximations_track_libm_over_the_simulated_ranges() {
+        for step in 0..=720 {
+            let angle = f64::from(step) / 720.0 * 4.0 * TAU - 2.0 * TAU;
+            assert!((sin(angle) - angle.sin()).abs() < 5e-5, "sin({})", angle);
+        }
+        for step in 1..=1_000 {
+            let value = f64::from(step) * 0.013;
+            assert!(
+                (ln(value) - value.ln()).abs() < 1e-13,
+                "ln({}) = {} vs {}",
+                value,
+                ln(value),
+                value.ln()
+            );
+            let x = f64::from(step) / 50.0 - 10.0;
+            assert!((exp(x) / x.exp() - 1.0).abs() < 1e-13, "exp({})", x);
+        }
+        for &(base, exponent) in &[(0.5, 0.8), (0.93, 5.255_88), (0.6, 0.190_26), (1.0, 3.0)] {
+            let expected = f64::powf(base, exponent);
+            assert!((powf(base, exponent) / expected - 1.0).abs() < 1e-13);
+        }
+        assert_eq!(powf(0.0, 0.8), 0.0);
+        assert_eq!(ln(1.0), 0.0);
+        assert_eq!(exp(0.0), 1.0);
+        assert!(ln(f64::MIN_POSITIVE / 4.0).is_finite());
+    }
+}
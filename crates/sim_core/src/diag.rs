@@ -0,0 +1,187 @@
+//! Run-level diagnostics computed from whole-world summaries.
+
+use std::collections::VecDeque;
+
+use crate::biome;
+use crate::kernels::atmosphere::SEASON_PERIOD_TICKS;
+use crate::world::World;
+
+/// Equal-weight global means of the headline climate fields for one tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlobalMeans {
+    pub temp_c: f64,
+    pub albedo: f64,
+    pub humidity_pct: f64,
+    pub precip_native: f64,
+}
+
+impl GlobalMeans {
+    fn fields(&self) -> [f64; 4] {
+        [
+            self.temp_c,
+            self.albedo,
+            self.humidity_pct,
+            self.precip_native,
+        ]
+    }
+
+    /// Largest change of any field from `previous`, relative to the field's previous
+    /// magnitude (floored at one unit so values near zero compare absolutely).
+    pub fn max_relative_change(&self, previous: &GlobalMeans) -> f64 {
+        self.fields()
+            .into_iter()
+            .zip(previous.fields())
+            .map(|(next, previous)| (next - previous).abs() / previous.abs().max(1.0))
+            .fold(0.0, f64::max)
+    }
+
+    fn mean_of<'a, I: Iterator<Item = &'a GlobalMeans>>(samples: I) -> GlobalMeans {
+        let (mut sum, mut count) = ([0.0; 4], 0usize);
+        for sample in samples {
+            for (total, value) in sum.iter_mut().zip(sample.fields()) {
+                *total += value;
+            }
+            count += 1;
+        }
+        let count = count.max(1) as f64;
+        GlobalMeans {
+            temp_c: sum[0] / count,
+            albedo: sum[1] / count,
+            humidity_pct: sum[2] / count,
+            precip_native: sum[3] / count,
+        }
+    }
+}
+
+/// Detects when global means stop changing.
+///
+/// Each tick's means are first averaged with the preceding seasonal cycle, since the
+/// seasonal swing alone moves global temperature by several degrees per tick. The run
+/// counts as converged once that smoothed state changes by less than `tolerance`
+/// (see [`GlobalMeans::max_relative_change`]) for `patience` consecutive ticks.
+#[derive(Clone, Debug)]
+pub struct SteadyStateDetector {
+    tolerance: f64,
+    patience: usize,
+    period: usize,
+    window: VecDeque<GlobalMeans>,
+}
+
+impl SteadyStateDetector {
+    pub fn new(tolerance: f64, patience: usize) -> Self {
+        Self::with_period(tolerance, patience, SEASON_PERIOD_TICKS as usize)
+    }
+
+    /// Detector that smooths over `period` ticks instead of the seasonal cycle; a period
+    /// of 1 compares raw tick-to-tick means.
+    pub fn with_period(tolerance: f64, patience: usize, period: usize) -> Self {
+        let patience = patience.max(1);
+        let period = period.max(1);
+        Self {
+            tolerance,
+            patience,
+            period,
+            window: VecDeque::with_capacity(period + patience),
+        }
+    }
+
+    /// Record the means for the tick that just completed.
+    pub fn push(&mut self, means: GlobalMeans) {
+        if self.window.len() == self.period + self.patience {
+            self.window.pop_front();
+        }
+        self.window.push_back(means);
+    }
+
+    /// Largest smoothed change over the last `patience` ticks, once enough ticks are in.
+    pub fn max_change(&self) -> Option<f64> {
+        if self.window.len() < self.period + self.patience {
+            return None;
+        }
+        let smoothed: Vec<GlobalMeans> = (0..=self.patience)
+            .map(|start| GlobalMeans::mean_of(self.window.iter().skip(start).take(self.period)))
+            .collect();
+        Some(
+            smoothed
+                .windows(2)
+                .map(|pair| pair[1].max_relative_change(&pair[0]))
+                .fold(0.0, f64::max),
+        )
+    }
+
+    pub fn converged(&self) -> bool {
+        self.max_change()
+            .is_some_and(|change| change < self.tolerance)
+    }
+}
+
+/// Number of regions holding each biome code, indexed as [`biome::LABELS`].
+pub fn biome_histogram(world: &World) -> [u32; biome::COUNT] {
+    let mut counts = [0; biome::COUNT];
+    for region in &world.regions {
+        counts[biome::index(region.biome)] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn means(temp_c: f64) -> GlobalMeans {
+        GlobalMeans {
+            temp_c,
+            ..GlobalMeans::default()
+        }
+    }
+
+    #[test]
+    fn converges_after_patience_quiet_ticks() {
+        let mut detector = SteadyStateDetector::with_period(0.05, 3, 1);
+        for temp in [10.0, 12.0, 13.0, 13.02, 13.04] {
+            detector.push(means(temp));
+            assert!(!detector.converged());
+        }
+        detector.push(means(13.05));
+        assert!(detector.converged());
+
+        detector.push(means(14.0));
+        assert!(!detector.converged());
+        let change = detector.max_change().expect("window holds several ticks");
+        assert!((change - 0.95 / 13.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn seasonal_swing_alone_is_steady() {
+        let mut detector = SteadyStateDetector::with_period(0.01, 4, 4);
+        let cycle = [8.0, 2.0, -4.0, 2.0];
+        for tick in 0..12 {
+            detector.push(means(cycle[tick % 4]));
+        }
+        assert!(detector.converged());
+
+        let mut raw = SteadyStateDetector::with_period(0.01, 4, 1);
+        for tick in 0..12 {
+            raw.push(means(cycle[tick % 4]));
+        }
+        assert!(!raw.converged());
+    }
+
+    #[test]
+    fn biome_histogram_counts_every_region_once() {
+        use crate::world::Region;
+
+        let biomes = [5, 5, 4, 0, 2, 5, 1];
+        let regions = biomes
+            .iter()
+            .enumerate()
+            .map(|(x, &code)| Region::sample(x as u32, x as u32, 0).with_biome(code))
+            .collect::<Vec<_>>();
+        let world = World::new(3, biomes.len() as u32, 1, regions);
+
+        let histogram = biome_histogram(&world);
+        assert_eq!(histogram, [1, 1, 1, 0, 1, 3]);
+        assert_eq!(histogram.iter().sum::<u32>() as usize, world.regions.len());
+        assert_eq!(biome::LABELS[5], "rainforest");
+    }
+}
@@ -1,11 +1,103 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
+use anyhow::{bail, Result};
 use serde::ser::{SerializeMap, SerializeStruct};
 use serde::{Deserialize, Serialize};
 
 use crate::cause::Entry;
-use crate::world::World;
+use crate::world::{StormCell, World};
+
+/// How [`Diff::merge_with_policy`] resolves a scalar field both diffs set for the same
+/// region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The later diff's value wins, matching [`Diff::merge`].
+    #[default]
+    Overwrite,
+    /// Add the two values, saturating at the `i32` bounds.
+    Sum,
+    /// Reject the merge, naming every conflicting field and region.
+    Error,
+}
+
+/// Region-keyed single-value fields of a [`Diff`], for code that loops over fields
+/// generically.
+///
+/// `wind`, `hazards`, `causes`, and `diagnostics` carry multi-value or non-region entries
+/// and stay named fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiffField {
+    Biome,
+    Water,
+    Soil,
+    Insolation,
+    TideEnvelope,
+    Elevation,
+    Temperature,
+    TemperatureBaseline,
+    Precipitation,
+    PrecipExtreme,
+    Humidity,
+    Albedo,
+    PermafrostActive,
+    FreshwaterFlux,
+    MeltPulse,
+    IceMass,
+    HeatwaveIdx,
+    PrecipType,
+    DiagClimate,
+}
+
+impl DiffField {
+    /// Every variant, in [`Diff`] field order.
+    pub const ALL: [DiffField; 19] = [
+        DiffField::Biome,
+        DiffField::Water,
+        DiffField::Soil,
+        DiffField::Insolation,
+        DiffField::TideEnvelope,
+        DiffField::Elevation,
+        DiffField::Temperature,
+        DiffField::TemperatureBaseline,
+        DiffField::Precipitation,
+        DiffField::PrecipExtreme,
+        DiffField::Humidity,
+        DiffField::Albedo,
+        DiffField::PermafrostActive,
+        DiffField::FreshwaterFlux,
+        DiffField::MeltPulse,
+        DiffField::IceMass,
+        DiffField::HeatwaveIdx,
+        DiffField::PrecipType,
+        DiffField::DiagClimate,
+    ];
+
+    /// Key used when the field is serialized (`temp`, `precip`, ...).
+    pub fn name(self) -> &'static str {
+        match self {
+            DiffField::Biome => "biome",
+            DiffField::Water => "water",
+            DiffField::Soil => "soil",
+            DiffField::Insolation => "insolation",
+            DiffField::TideEnvelope => "tide_envelope",
+            DiffField::Elevation => "elevation",
+            DiffField::Temperature => "temp",
+            DiffField::TemperatureBaseline => "temperature_baseline",
+            DiffField::Precipitation => "precip",
+            DiffField::PrecipExtreme => "precip_extreme",
+            DiffField::Humidity => "humidity",
+            DiffField::Albedo => "albedo",
+            DiffField::PermafrostActive => "permafrost_active",
+            DiffField::FreshwaterFlux => "freshwater_flux",
+            DiffField::MeltPulse => "melt_pulse",
+            DiffField::IceMass => "ice_mass",
+            DiffField::HeatwaveIdx => "heatwave_idx",
+            DiffField::PrecipType => "precip_type",
+            DiffField::DiagClimate => "diag_climate",
+        }
+    }
+}
 
 #[derive(Clone, Debug, Default)]
 pub struct Diff {
@@ -26,10 +118,23 @@ pub struct Diff {
     pub melt_pulse: Vec<ScalarValue>,
     pub ice_mass: Vec<ScalarValue>,
     pub heatwave_idx: Vec<ScalarValue>,
+    pub precip_type: Vec<ScalarValue>,
     pub diag_climate: Vec<ScalarValue>,
+    pub wind: Vec<WindVector>,
     pub hazards: Vec<HazardEvent>,
     pub causes: Vec<Entry>,
     pub diagnostics: BTreeMap<String, i32>,
+    /// Global forcing offset in tenths of °C that the reducer commits to
+    /// [`crate::world::ClimateState::applied_forcing_tenths`]; never serialized.
+    pub forcing_offset_tenths: Option<i32>,
+    /// Storm centres that replace [`crate::world::ClimateState::storm_tracks`] when
+    /// committed; never serialized.
+    pub storm_tracks: Option<Vec<StormCell>>,
+    /// Per-region volcanic episode counters that replace
+    /// [`crate::world::ClimateState::volcanic_episode`] when committed; never serialized.
+    pub volcanic_episode: Option<Vec<u16>>,
+    /// Next id handed out by [`Diff::record_cause_with_id`].
+    next_cause_id: u64,
 }
 
 impl Diff {
@@ -116,10 +221,28 @@ impl Diff {
         Self::set_scalar_value(&mut self.heatwave_idx, region_index as u32, value);
     }
 
+    pub fn record_precip_type(&mut self, region_index: usize, value: i32) {
+        Self::set_scalar_value(&mut self.precip_type, region_index as u32, value);
+    }
+
     pub fn record_diag_climate(&mut self, region_index: usize, value: i32) {
         Self::set_scalar_value(&mut self.diag_climate, region_index as u32, value);
     }
 
+    pub fn record_wind(&mut self, region_index: usize, dx: i32, dy: i32) {
+        let region = region_index as u32;
+        match self
+            .wind
+            .binary_search_by_key(&region, |entry| entry.region)
+        {
+            Ok(idx) => {
+                self.wind[idx].dx = dx;
+                self.wind[idx].dy = dy;
+            }
+            Err(idx) => self.wind.insert(idx, WindVector { region, dx, dy }),
+        }
+    }
+
     pub fn record_hazard(&mut self, region_index: usize, drought: u16, flood: u16) {
         let region = region_index as u32;
         match self.hazards.binary_search_by_key(&region, |h| h.region) {
@@ -156,6 +279,20 @@ impl Diff {
         }
     }
 
+    /// Record `cause` under a fresh id, returned so derived effects can reference it via
+    /// [`Entry::caused_by`].
+    ///
+    /// Ids count up from zero per diff; [`Diff::merge`] shifts the merged diff's ids (and
+    /// the `caused_by` links between them) past this diff's, so a tick's aggregate diff
+    /// carries unique ids with every link intact.
+    pub fn record_cause_with_id(&mut self, mut cause: Entry) -> u64 {
+        let id = self.next_cause_id;
+        self.next_cause_id += 1;
+        cause.id = Some(id);
+        self.record_cause(cause);
+        id
+    }
+
     pub fn extend_causes<I>(&mut self, causes: I)
     where
         I: IntoIterator<Item = Entry>,
@@ -169,7 +306,48 @@ impl Diff {
         self.diagnostics.insert(key.into(), value);
     }
 
+    /// Fold `other` into `self`; scalar fields take `other`'s value where both are set.
     pub fn merge(&mut self, other: &Diff) {
+        self.merge_unchecked(other, MergePolicy::Overwrite);
+    }
+
+    /// Like [`Diff::merge`], but resolves scalar fields both diffs set under `policy`.
+    ///
+    /// Biome changes still take `other`'s value and water/soil deltas still add. Under
+    /// [`MergePolicy::Error`] any conflict fails the merge and leaves `self` untouched.
+    pub fn merge_with_policy(&mut self, other: &Diff, policy: MergePolicy) -> Result<()> {
+        if policy == MergePolicy::Error {
+            let conflicts = self.scalar_conflicts(other);
+            if !conflicts.is_empty() {
+                let listed: Vec<String> = conflicts
+                    .iter()
+                    .map(|(field, region)| format!("{} r:{}", field.name(), region))
+                    .collect();
+                bail!("conflicting scalar writes: {}", listed.join(", "));
+            }
+        }
+        self.merge_unchecked(other, policy);
+        Ok(())
+    }
+
+    /// Scalar `(field, region)` pairs set in both `self` and `other`, in field order.
+    pub fn scalar_conflicts(&self, other: &Diff) -> Vec<(DiffField, u32)> {
+        let mut conflicts = Vec::new();
+        for field in DiffField::ALL {
+            let ours = self.scalars(field);
+            for scalar in other.scalars(field) {
+                if ours
+                    .binary_search_by_key(&scalar.region, |entry| entry.region)
+                    .is_ok()
+                {
+                    conflicts.push((field, scalar.region));
+                }
+            }
+        }
+        conflicts
+    }
+
+    fn merge_unchecked(&mut self, other: &Diff, policy: MergePolicy) {
         for change in &other.biome {
             self.set_biome_value(change.region, change.biome);
         }
@@ -179,59 +357,146 @@ impl Diff {
         for delta in &other.soil {
             Self::insert_delta(&mut self.soil, delta.region, delta.delta);
         }
-        for scalar in &other.insolation {
-            Self::set_scalar_value(&mut self.insolation, scalar.region, scalar.value);
-        }
-        for scalar in &other.tide_envelope {
-            Self::set_scalar_value(&mut self.tide_envelope, scalar.region, scalar.value);
+        self.merge_scalars(other, policy);
+        for vector in &other.wind {
+            self.record_wind(vector.region as usize, vector.dx, vector.dy);
         }
-        for scalar in &other.elevation {
-            Self::set_scalar_value(&mut self.elevation, scalar.region, scalar.value);
-        }
-        for scalar in &other.temperature {
-            Self::set_scalar_value(&mut self.temperature, scalar.region, scalar.value);
-        }
-        for scalar in &other.temperature_baseline {
-            Self::set_scalar_value(&mut self.temperature_baseline, scalar.region, scalar.value);
+        for hazard in &other.hazards {
+            self.record_hazard(hazard.region as usize, hazard.drought, hazard.flood);
         }
-        for scalar in &other.precipitation {
-            Self::set_scalar_value(&mut self.precipitation, scalar.region, scalar.value);
+        let id_offset = self.next_cause_id;
+        for mut cause in other.causes.iter().cloned() {
+            cause.id = cause.id.map(|id| id + id_offset);
+            cause.caused_by = cause.caused_by.map(|id| id + id_offset);
+            self.record_cause(cause);
         }
-        for scalar in &other.precip_extreme {
-            Self::set_scalar_value(&mut self.precip_extreme, scalar.region, scalar.value);
+        self.next_cause_id += other.next_cause_id;
+        for (key, value) in &other.diagnostics {
+            self.diagnostics.insert(key.clone(), *value);
         }
-        for scalar in &other.humidity {
-            Self::set_scalar_value(&mut self.humidity, scalar.region, scalar.value);
+        if other.forcing_offset_tenths.is_some() {
+            self.forcing_offset_tenths = other.forcing_offset_tenths;
         }
-        for scalar in &other.albedo {
-            Self::set_scalar_value(&mut self.albedo, scalar.region, scalar.value);
+        if other.storm_tracks.is_some() {
+            self.storm_tracks.clone_from(&other.storm_tracks);
         }
-        for scalar in &other.permafrost_active {
-            Self::set_scalar_value(&mut self.permafrost_active, scalar.region, scalar.value);
+        if other.volcanic_episode.is_some() {
+            self.volcanic_episode.clone_from(&other.volcanic_episode);
         }
-        for scalar in &other.freshwater_flux {
-            Self::set_scalar_value(&mut self.freshwater_flux, scalar.region, scalar.value);
+    }
+
+    fn merge_scalars(&mut self, other: &Diff, policy: MergePolicy) {
+        for field in DiffField::ALL {
+            let Some(target) = self.scalars_mut(field) else {
+                continue;
+            };
+            for scalar in other.scalars(field) {
+                match policy {
+                    MergePolicy::Sum => Self::add_scalar_value(target, scalar.region, scalar.value),
+                    MergePolicy::Overwrite | MergePolicy::Error => {
+                        Self::set_scalar_value(target, scalar.region, scalar.value)
+                    }
+                }
+            }
         }
-        for scalar in &other.melt_pulse {
-            Self::set_scalar_value(&mut self.melt_pulse, scalar.region, scalar.value);
+    }
+
+    /// Number of regions recorded in `field`.
+    pub fn len(&self, field: DiffField) -> usize {
+        match field {
+            DiffField::Biome => self.biome.len(),
+            DiffField::Water => self.water.len(),
+            DiffField::Soil => self.soil.len(),
+            _ => self.scalars(field).len(),
         }
-        for scalar in &other.ice_mass {
-            Self::set_scalar_value(&mut self.ice_mass, scalar.region, scalar.value);
+    }
+
+    pub fn is_field_empty(&self, field: DiffField) -> bool {
+        self.len(field) == 0
+    }
+
+    /// `(region, value)` pairs recorded in `field`, in region order; water and soil yield
+    /// their deltas.
+    pub fn iter_regions(&self, field: DiffField) -> impl Iterator<Item = (u32, i32)> + '_ {
+        let entries: Box<dyn Iterator<Item = (u32, i32)> + '_> = match field {
+            DiffField::Biome => Box::new(self.biome.iter().map(|c| (c.region, c.biome))),
+            DiffField::Water => Box::new(self.water.iter().map(|d| (d.region, d.delta))),
+            DiffField::Soil => Box::new(self.soil.iter().map(|d| (d.region, d.delta))),
+            _ => Box::new(self.scalars(field).iter().map(|v| (v.region, v.value))),
+        };
+        entries
+    }
+
+    /// Drop biome and scalar entries that already equal `world`'s stored value, so
+    /// applying the diff to `world` leaves the same state with fewer entries.
+    ///
+    /// `world` must be the state the diff will be applied to, i.e. the world *before*
+    /// the tick that produced it. Fields the reducer never stores on the world (humidity,
+    /// extremes, melt pulses, ...) have nothing to compare against and are kept. Kernels
+    /// never call this; runners opt in to shrink their frames.
+    pub fn compact(&mut self, world: &World) {
+        self.biome.retain(|change| {
+            stored_value(world, DiffField::Biome, change.region) != Some(change.biome)
+        });
+        for field in DiffField::ALL {
+            if let Some(values) = self.scalars_mut(field) {
+                values
+                    .retain(|value| stored_value(world, field, value.region) != Some(value.value));
+            }
         }
-        for scalar in &other.heatwave_idx {
-            Self::set_scalar_value(&mut self.heatwave_idx, scalar.region, scalar.value);
+        if self.forcing_offset_tenths == Some(world.climate.applied_forcing_tenths) {
+            self.forcing_offset_tenths = None;
         }
-        for scalar in &other.diag_climate {
-            Self::set_scalar_value(&mut self.diag_climate, scalar.region, scalar.value);
+        if self.storm_tracks.as_ref() == Some(&world.climate.storm_tracks) {
+            self.storm_tracks = None;
         }
-        for hazard in &other.hazards {
-            self.record_hazard(hazard.region as usize, hazard.drought, hazard.flood);
+        if self.volcanic_episode.as_ref() == Some(&world.climate.volcanic_episode) {
+            self.volcanic_episode = None;
         }
-        for cause in other.causes.iter().cloned() {
-            self.record_cause(cause);
+    }
+
+    fn scalars_mut(&mut self, field: DiffField) -> Option<&mut Vec<ScalarValue>> {
+        match field {
+            DiffField::Biome | DiffField::Water | DiffField::Soil => None,
+            DiffField::Insolation => Some(&mut self.insolation),
+            DiffField::TideEnvelope => Some(&mut self.tide_envelope),
+            DiffField::Elevation => Some(&mut self.elevation),
+            DiffField::Temperature => Some(&mut self.temperature),
+            DiffField::TemperatureBaseline => Some(&mut self.temperature_baseline),
+            DiffField::Precipitation => Some(&mut self.precipitation),
+            DiffField::PrecipExtreme => Some(&mut self.precip_extreme),
+            DiffField::Humidity => Some(&mut self.humidity),
+            DiffField::Albedo => Some(&mut self.albedo),
+            DiffField::PermafrostActive => Some(&mut self.permafrost_active),
+            DiffField::FreshwaterFlux => Some(&mut self.freshwater_flux),
+            DiffField::MeltPulse => Some(&mut self.melt_pulse),
+            DiffField::IceMass => Some(&mut self.ice_mass),
+            DiffField::HeatwaveIdx => Some(&mut self.heatwave_idx),
+            DiffField::PrecipType => Some(&mut self.precip_type),
+            DiffField::DiagClimate => Some(&mut self.diag_climate),
         }
-        for (key, value) in &other.diagnostics {
-            self.diagnostics.insert(key.clone(), *value);
+    }
+
+    /// Backing slice for the [`ScalarValue`] fields; empty for biome, water, and soil.
+    fn scalars(&self, field: DiffField) -> &[ScalarValue] {
+        match field {
+            DiffField::Biome | DiffField::Water | DiffField::Soil => &[],
+            DiffField::Insolation => &self.insolation,
+            DiffField::TideEnvelope => &self.tide_envelope,
+            DiffField::Elevation => &self.elevation,
+            DiffField::Temperature => &self.temperature,
+            DiffField::TemperatureBaseline => &self.temperature_baseline,
+            DiffField::Precipitation => &self.precipitation,
+            DiffField::PrecipExtreme => &self.precip_extreme,
+            DiffField::Humidity => &self.humidity,
+            DiffField::Albedo => &self.albedo,
+            DiffField::PermafrostActive => &self.permafrost_active,
+            DiffField::FreshwaterFlux => &self.freshwater_flux,
+            DiffField::MeltPulse => &self.melt_pulse,
+            DiffField::IceMass => &self.ice_mass,
+            DiffField::HeatwaveIdx => &self.heatwave_idx,
+            DiffField::PrecipType => &self.precip_type,
+            DiffField::DiagClimate => &self.diag_climate,
         }
     }
 
@@ -257,10 +522,15 @@ impl Diff {
             && self.melt_pulse.is_empty()
             && self.ice_mass.is_empty()
             && self.heatwave_idx.is_empty()
+            && self.precip_type.is_empty()
             && self.diag_climate.is_empty()
+            && self.wind.is_empty()
             && self.hazards.is_empty()
             && self.causes.is_empty()
             && self.diagnostics.is_empty()
+            && self.forcing_offset_tenths.is_none()
+            && self.storm_tracks.is_none()
+            && self.volcanic_episode.is_none()
     }
 
     fn set_biome_value(&mut self, region: u32, biome: i32) {
@@ -292,6 +562,45 @@ impl Diff {
             Err(idx) => target.insert(idx, ScalarValue { region, value }),
         }
     }
+
+    fn add_scalar_value(target: &mut Vec<ScalarValue>, region: u32, value: i32) {
+        match target.binary_search_by_key(&region, |entry| entry.region) {
+            Ok(idx) => target[idx].value = target[idx].value.saturating_add(value),
+            Err(idx) => target.insert(idx, ScalarValue { region, value }),
+        }
+    }
+}
+
+/// Value [`crate::reduce::apply`] last committed to `world` for `field` in `region`;
+/// `None` for fields it does not store and for regions outside the world.
+fn stored_value(world: &World, field: DiffField, region: u32) -> Option<i32> {
+    let index = region as usize;
+    let stored = world.regions.get(index)?;
+    let climate = &world.climate;
+    match field {
+        DiffField::Biome => Some(i32::from(stored.biome)),
+        DiffField::Insolation => climate.last_insolation_tenths.get(index).copied(),
+        DiffField::TideEnvelope => climate.last_tide_envelope_tenths.get(index).copied(),
+        DiffField::Elevation => Some(stored.elevation_m),
+        DiffField::Temperature => Some(i32::from(stored.temperature_tenths_c)),
+        DiffField::TemperatureBaseline => climate
+            .temperature_baseline_tenths
+            .get(index)
+            .map(|&value| i32::from(value)),
+        DiffField::Precipitation => Some(i32::from(stored.precipitation_mm)),
+        DiffField::Albedo => Some(i32::from(stored.albedo_milli)),
+        DiffField::FreshwaterFlux => Some(i32::from(stored.freshwater_flux_tenths_mm)),
+        DiffField::IceMass => i32::try_from(stored.ice_mass_kilotons).ok(),
+        DiffField::Water
+        | DiffField::Soil
+        | DiffField::PrecipExtreme
+        | DiffField::Humidity
+        | DiffField::PermafrostActive
+        | DiffField::MeltPulse
+        | DiffField::HeatwaveIdx
+        | DiffField::PrecipType
+        | DiffField::DiagClimate => None,
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -312,6 +621,14 @@ pub struct ScalarValue {
     pub value: i32,
 }
 
+/// Prevailing wind direction for a region as grid offsets `(dx, dy)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindVector {
+    pub region: u32,
+    pub dx: i32,
+    pub dy: i32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HazardEvent {
     pub region: u32,
@@ -373,9 +690,15 @@ impl Serialize for Diff {
         if !self.heatwave_idx.is_empty() {
             field_count += 1;
         }
+        if !self.precip_type.is_empty() {
+            field_count += 1;
+        }
         if !self.diag_climate.is_empty() {
             field_count += 1;
         }
+        if !self.wind.is_empty() {
+            field_count += 1;
+        }
         if !self.hazards.is_empty() {
             field_count += 1;
         }
@@ -431,9 +754,15 @@ impl Serialize for Diff {
         if !self.heatwave_idx.is_empty() {
             state.serialize_field("heatwave_idx", &ScalarValues(&self.heatwave_idx))?;
         }
+        if !self.precip_type.is_empty() {
+            state.serialize_field("precip_type", &ScalarValues(&self.precip_type))?;
+        }
         if !self.diag_climate.is_empty() {
             state.serialize_field("diag_climate", &ScalarValues(&self.diag_climate))?;
         }
+        if !self.wind.is_empty() {
+            state.serialize_field("wind", &WindVectors(&self.wind))?;
+        }
         if !self.hazards.is_empty() {
             state.serialize_field("hazards", &self.hazards)?;
         }
@@ -491,3 +820,219 @@ impl<'a> Serialize for ScalarValues<'a> {
         map.end()
     }
 }
+
+struct WindVectors<'a>(&'a [WindVector]);
+
+impl<'a> Serialize for WindVectors<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for vector in self.0 {
+            let key = World::region_key(vector.region as usize);
+            map.serialize_entry(&key, &[vector.dx, vector.dy])?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diff, DiffField, MergePolicy};
+    use crate::cause::{Code, Entry};
+    use crate::io::frame::make_frame;
+
+    #[test]
+    fn cryosphere_fields_serialize_and_survive_make_frame() {
+        let mut diff = Diff::default();
+        diff.record_melt_pulse(1, 40);
+        diff.record_melt_pulse(3, 15);
+        diff.record_permafrost_active(0, 120);
+        diff.record_freshwater_flux(3, 220);
+
+        let value = serde_json::to_value(&diff).expect("diff serializes");
+        assert_eq!(value["melt_pulse"]["r:1"], 40);
+        assert_eq!(value["melt_pulse"]["r:3"], 15);
+        assert_eq!(value["permafrost_active"]["r:0"], 120);
+        assert_eq!(value["freshwater_flux"]["r:3"], 220);
+
+        let frame = make_frame(1, diff, Vec::new(), Vec::new(), false, 2, 2);
+        assert_eq!(frame.diff.melt_pulse.len(), 2);
+        assert_eq!(frame.diff.permafrost_active.len(), 1);
+        assert_eq!(frame.diff.freshwater_flux.get("r:3"), Some(&220));
+    }
+
+    #[test]
+    fn iterating_every_field_visits_every_recorded_entry() {
+        let mut diff = Diff::default();
+        diff.record_biome(3, 4);
+        diff.record_water_delta(1, -20);
+        diff.record_water_delta(0, 15);
+        diff.record_soil_delta(2, 7);
+        diff.record_insolation(0, 1);
+        diff.record_tide_envelope(0, 2);
+        diff.record_elevation(0, 3);
+        diff.record_temperature(5, -40);
+        diff.record_temperature(2, 110);
+        diff.record_temperature_baseline(0, 5);
+        diff.record_precipitation(0, 6);
+        diff.record_precip_extreme(0, 7);
+        diff.record_humidity(0, 8);
+        diff.record_albedo(0, 9);
+        diff.record_permafrost_active(0, 10);
+        diff.record_freshwater_flux(0, 11);
+        diff.record_melt_pulse(0, 12);
+        diff.record_ice_mass(0, 13);
+        diff.record_heatwave_idx(0, 14);
+        diff.record_precip_type(0, 2);
+        diff.record_diag_climate(0, 16);
+
+        let mut visited = 0;
+        for field in DiffField::ALL {
+            let entries: Vec<(u32, i32)> = diff.iter_regions(field).collect();
+            assert_eq!(entries.len(), diff.len(field), "{}", field.name());
+            assert!(
+                !diff.is_field_empty(field),
+                "{} unexpectedly empty",
+                field.name()
+            );
+            assert!(entries.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            visited += entries.len();
+        }
+        assert_eq!(visited, 21);
+        assert_eq!(
+            diff.iter_regions(DiffField::Water).collect::<Vec<_>>(),
+            vec![(0, 15), (1, -20)]
+        );
+        assert_eq!(
+            diff.iter_regions(DiffField::Temperature)
+                .collect::<Vec<_>>(),
+            vec![(2, 110), (5, -40)]
+        );
+        assert!(Diff::default().is_field_empty(DiffField::Albedo));
+    }
+
+    #[test]
+    fn merged_causes_keep_provenance_links() {
+        // Stand-in for a melt stage whose flood effect points at its snowmelt source.
+        let mut stage = Diff::default();
+        let surge =
+            stage.record_cause_with_id(Entry::new("region:3/water", Code::SnowmeltSurge, None));
+        stage.record_cause(Entry::new("region:4/water", Code::FloodFlag, None).caused_by(surge));
+
+        let mut tick = Diff::default();
+        tick.record_cause_with_id(Entry::new("world:astronomy", Code::SolarCyclePeak, None));
+        tick.record_cause(Entry::new("region:0/soil", Code::SoilFertilityLow, None));
+        tick.merge(&stage);
+
+        let find = |code: Code| tick.causes.iter().find(|entry| entry.code == code).unwrap();
+        let surge_id = find(Code::SnowmeltSurge).id.expect("snowmelt keeps an id");
+        assert_eq!(find(Code::FloodFlag).caused_by, Some(surge_id));
+        assert_ne!(find(Code::SolarCyclePeak).id, Some(surge_id));
+
+        let plain = serde_json::to_string(find(Code::SoilFertilityLow)).unwrap();
+        assert_eq!(
+            plain,
+            r#"{"target":"region:0/soil","code":"soil_fertility_low"}"#
+        );
+        let linked = serde_json::to_value(find(Code::FloodFlag)).unwrap();
+        assert_eq!(linked["caused_by"], surge_id);
+        assert!(linked.get("id").is_none());
+        assert_eq!(
+            tick.record_cause_with_id(Entry::new("x", Code::EraEnd, None)),
+            2
+        );
+    }
+
+    #[test]
+    fn error_policy_reports_conflicting_scalars() {
+        let mut base = Diff::default();
+        base.record_temperature(0, 120);
+        base.record_temperature(1, 80);
+        let mut other = Diff::default();
+        other.record_temperature(0, 90);
+        other.record_albedo(0, 300);
+
+        assert_eq!(
+            base.scalar_conflicts(&other),
+            vec![(DiffField::Temperature, 0)]
+        );
+        let json = |diff: &Diff| serde_json::to_string(diff).expect("diff serializes");
+        let before = base.clone();
+        let err = base
+            .merge_with_policy(&other, MergePolicy::Error)
+            .expect_err("temperature conflict");
+        assert!(err.to_string().contains("temp r:0"), "{}", err);
+        assert_eq!(json(&base), json(&before));
+
+        let mut summed = before.clone();
+        summed
+            .merge_with_policy(&other, MergePolicy::Sum)
+            .expect("sum never conflicts");
+        assert_eq!(
+            summed
+                .iter_regions(DiffField::Temperature)
+                .collect::<Vec<_>>(),
+            vec![(0, 210), (1, 80)]
+        );
+
+        let mut overwritten = before.clone();
+        overwritten
+            .merge_with_policy(&other, MergePolicy::default())
+            .expect("overwrite never conflicts");
+        let mut merged = before;
+        merged.merge(&other);
+        assert_eq!(json(&overwritten), json(&merged));
+        assert_eq!(
+            merged
+                .iter_regions(DiffField::Temperature)
+                .collect::<Vec<_>>(),
+            vec![(0, 90), (1, 80)]
+        );
+    }
+
+    #[test]
+    fn compact_drops_entries_equal_to_the_stored_world_values() {
+        use crate::world::{Region, World};
+
+        let mut world = World::new(
+            1,
+            2,
+            1,
+            vec![
+                Region::sample(0, 0, 0).with_temperature(120),
+                Region::sample(1, 1, 0).with_temperature(80),
+            ],
+        );
+        world.climate.last_insolation_tenths = vec![3_400, 3_100];
+
+        let mut diff = Diff::default();
+        diff.record_insolation(0, 3_400);
+        diff.record_insolation(1, 3_150);
+        diff.record_temperature(0, 120);
+        diff.record_temperature(1, 80);
+        diff.record_biome(0, world.regions[0].biome);
+        diff.record_humidity(0, 600);
+        diff.record_humidity(1, 650);
+        let full = diff.clone();
+
+        diff.compact(&world);
+        assert_eq!(
+            diff.iter_regions(DiffField::Insolation).collect::<Vec<_>>(),
+            vec![(1, 3_150)]
+        );
+        assert!(diff.is_field_empty(DiffField::Temperature));
+        assert!(diff.is_field_empty(DiffField::Biome));
+        // The world keeps no humidity to compare against, so those entries stay.
+        assert_eq!(diff.len(DiffField::Humidity), 2);
+
+        let mut compacted_world = world.clone();
+        crate::reduce::apply(&mut compacted_world, diff);
+        crate::reduce::apply(&mut world, full);
+        assert_eq!(
+            serde_json::to_value(&compacted_world).unwrap(),
+            serde_json::to_value(&world).unwrap()
+        );
+    }
+}
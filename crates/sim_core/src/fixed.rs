@@ -10,6 +10,55 @@ pub const ALBEDO_MAX: u16 = 1_000;
 /// Upper bound for freshwater flux pulses represented in tenths of millimetres.
 pub const FRESHWATER_FLUX_MAX: u16 = 2_000;
 
+/// Default per-tick precipitation ceiling in millimetres; the reducer only clamps higher
+/// when configured regime caps allow it.
+pub const PRECIP_MAX_MM: u16 = 5_000;
+
+/// Hard ceiling for per-tick precipitation in millimetres, whatever the regime caps allow.
+pub const PRECIP_SAFETY_MAX_MM: u16 = 10_000;
+
+/// Lowest terrain height in metres; extends the seed clamp for bathymetry adjustments.
+pub const MIN_ELEVATION_M: i32 = -1_000;
+
+/// Highest terrain height in metres; allows moderate uplift beyond the seed cap.
+pub const MAX_ELEVATION_M: i32 = 4_000;
+
+/// Default lowest surface temperature in tenths of °C (-50 °C).
+pub const TEMP_MIN_TENTHS_C: i16 = -500;
+
+/// Default highest surface temperature in tenths of °C (+50 °C).
+pub const TEMP_MAX_TENTHS_C: i16 = 500;
+
+/// Inclusive surface temperature bounds in tenths of °C, shared by the atmosphere's
+/// committed temperatures and the reducer's clamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemperatureRange {
+    pub min_tenths: i16,
+    pub max_tenths: i16,
+}
+
+impl Default for TemperatureRange {
+    fn default() -> Self {
+        Self {
+            min_tenths: TEMP_MIN_TENTHS_C,
+            max_tenths: TEMP_MAX_TENTHS_C,
+        }
+    }
+}
+
+impl TemperatureRange {
+    /// Clamp `value` into the range; an inverted range collapses onto `max_tenths`.
+    pub fn clamp(&self, value: i32) -> i32 {
+        value
+            .max(i32::from(self.min_tenths))
+            .min(i32::from(self.max_tenths))
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        self.clamp(value) == value
+    }
+}
+
 /// Clamp an integer value to a bounded `u16` range.
 pub fn clamp_u16(value: i32, min: u16, max: u16) -> u16 {
     debug_assert!(min <= max);
@@ -27,12 +76,27 @@ pub fn clamp_biome_index(value: i32) -> u8 {
     value.clamp(u8::MIN as i32, u8::MAX as i32) as u8
 }
 
+/// Clamp a terrain height to `[MIN_ELEVATION_M, MAX_ELEVATION_M]`.
+pub fn clamp_elevation(value: i32) -> i32 {
+    value.clamp(MIN_ELEVATION_M, MAX_ELEVATION_M)
+}
+
 /// Apply a signed delta to a resource meter, returning the clamped value.
 pub fn commit_resource_delta(current: u16, delta: i32, max: u16) -> u16 {
     let next = i32::from(current) + delta;
     clamp_u16(next, 0, max)
 }
 
+/// Like [`commit_resource_delta`], but also reports whether the bounds clipped the delta.
+///
+/// Stages that emit deltas should derive them from the returned value so the recorded
+/// change matches what the reducer will actually apply.
+pub fn commit_resource_delta_saturating(current: u16, delta: i32, max: u16) -> (u16, bool) {
+    let next = i32::from(current) + delta;
+    let clamped = clamp_u16(next, 0, max);
+    (clamped, i32::from(clamped) != next)
+}
+
 /// Clamp hazard meters to the water range bounds.
 pub fn clamp_hazard_meter(value: u16) -> u16 {
     clamp_u16(i32::from(value), 0, WATER_MAX)
@@ -68,7 +132,7 @@ mod tests {
         #[test]
         fn clamp_biome_index_never_exits_bounds(value in -50_000i32..50_000i32) {
             let clamped = clamp_biome_index(value);
-            prop_assert!(clamped <= u8::MAX);
+            prop_assert_eq!(i32::from(clamped), value.clamp(0, i32::from(u8::MAX)));
         }
 
         #[test]
@@ -81,10 +145,37 @@ mod tests {
             prop_assert!(i32::from(clamped) >= 0);
         }
 
+        #[test]
+        fn saturating_commit_matches_plain_commit(
+            current in 0u16..=WATER_MAX,
+            delta in -50_000i32..50_000i32,
+        ) {
+            let (value, saturated) = commit_resource_delta_saturating(current, delta, WATER_MAX);
+            prop_assert_eq!(value, commit_resource_delta(current, delta, WATER_MAX));
+            prop_assert_eq!(saturated, i32::from(value) - i32::from(current) != delta);
+        }
+
         #[test]
         fn clamp_hazard_meter_never_exits_bounds(value in 0u16..=u16::MAX) {
             let clamped = clamp_hazard_meter(value);
             prop_assert!(clamped <= WATER_MAX);
         }
     }
+
+    #[test]
+    fn saturated_commit_reports_zero_effective_delta() {
+        let (value, saturated) = commit_resource_delta_saturating(WATER_MAX, 120, WATER_MAX);
+        assert_eq!(value, WATER_MAX);
+        assert!(saturated);
+        assert_eq!(i32::from(value) - i32::from(WATER_MAX), 0);
+
+        assert_eq!(
+            commit_resource_delta_saturating(SOIL_MAX - 50, 30, SOIL_MAX),
+            (SOIL_MAX - 20, false)
+        );
+        assert_eq!(
+            commit_resource_delta_saturating(40, -100, SOIL_MAX),
+            (0, true)
+        );
+    }
 }
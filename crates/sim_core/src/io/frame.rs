@@ -1,9 +1,12 @@
 use std::collections::BTreeMap;
 
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::cause::Entry;
 use crate::diff::Diff;
-use crate::world::World;
+use crate::io::narrative::{self, NarrativeEvent};
+use crate::world::{pressure_hpa, World};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Highlight {
@@ -17,6 +20,12 @@ pub struct Highlight {
 pub struct HighlightInfo {
     pub kind: String,
     pub level: f32,
+    /// Region whose state caused this event, for cross-region highlights.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub source_region: Option<u32>,
+    /// Extra named magnitudes describing the event.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub detail: BTreeMap<String, f32>,
 }
 
 impl Highlight {
@@ -27,12 +36,46 @@ impl Highlight {
             info: HighlightInfo {
                 kind: kind.to_string(),
                 level,
+                source_region: None,
+                detail: BTreeMap::new(),
             },
         }
     }
+
+    /// Attribute this highlight to an upstream `region`, e.g. the melt feeding a flood.
+    pub fn with_source_region(mut self, region: u32) -> Self {
+        self.info.source_region = Some(region);
+        self
+    }
+
+    /// Attach a named magnitude to `info.detail`.
+    pub fn with_detail<K: Into<String>>(mut self, key: K, value: f32) -> Self {
+        self.info.detail.insert(key.into(), value);
+        self
+    }
+
+    /// Ranking score for [`collect_highlights`]: the hazard severity or extreme magnitude
+    /// carried in `info.level`, higher first.
+    pub fn priority(&self) -> f32 {
+        self.info.level
+    }
 }
 
-#[derive(Clone, Debug, Default, Serialize)]
+/// Keep at most `max_highlights` candidates, choosing the highest [`Highlight::priority`].
+///
+/// Within budget the candidates are returned untouched, in producer order. Over budget
+/// they are stably sorted by descending priority, so equal scores keep producer order,
+/// and the top `max_highlights` are returned.
+pub fn collect_highlights(mut candidates: Vec<Highlight>, max_highlights: usize) -> Vec<Highlight> {
+    if candidates.len() <= max_highlights {
+        return candidates;
+    }
+    candidates.sort_by(|a, b| b.priority().total_cmp(&a.priority()));
+    candidates.truncate(max_highlights);
+    candidates
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct FrameDiff {
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub biome: BTreeMap<String, i32>,
@@ -63,8 +106,14 @@ pub struct FrameDiff {
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub heatwave_idx: BTreeMap<String, i32>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub precip_type: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub diag_climate: BTreeMap<String, i32>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub wind: BTreeMap<String, [i32; 2]>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub pressure: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub soil: BTreeMap<String, i32>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub water: BTreeMap<String, i32>,
@@ -86,19 +135,81 @@ impl FrameDiff {
             && self.melt_pulse.is_empty()
             && self.ice_mass.is_empty()
             && self.heatwave_idx.is_empty()
+            && self.precip_type.is_empty()
             && self.diag_climate.is_empty()
+            && self.wind.is_empty()
+            && self.pressure.is_empty()
             && self.soil.is_empty()
             && self.water.is_empty()
     }
+
+    /// Rebuild the [`Diff`] this frame diff was made from, so it can go back through
+    /// [`crate::reduce::apply`].
+    ///
+    /// Only what frames carry comes back: baseline adjustments, hazard meters, and causes
+    /// are never serialized, wind only when the frame was written with it, and the derived
+    /// pressure map is ignored.
+    pub fn to_diff(&self) -> Result<Diff> {
+        let mut diff = Diff::default();
+        for (index, &biome) in indexed(&self.biome)? {
+            let biome = u8::try_from(biome)
+                .with_context(|| format!("biome {} for r:{} is not a biome code", biome, index))?;
+            diff.record_biome(index, biome);
+        }
+        let values: [(&BTreeMap<String, i32>, RecordFn); 17] = [
+            (&self.insolation, Diff::record_insolation),
+            (&self.tide_envelope, Diff::record_tide_envelope),
+            (&self.elevation, Diff::record_elevation),
+            (&self.temp, Diff::record_temperature),
+            (&self.precip, Diff::record_precipitation),
+            (&self.precip_extreme, Diff::record_precip_extreme),
+            (&self.humidity, Diff::record_humidity),
+            (&self.albedo, Diff::record_albedo),
+            (&self.permafrost_active, Diff::record_permafrost_active),
+            (&self.freshwater_flux, Diff::record_freshwater_flux),
+            (&self.melt_pulse, Diff::record_melt_pulse),
+            (&self.ice_mass, Diff::record_ice_mass),
+            (&self.heatwave_idx, Diff::record_heatwave_idx),
+            (&self.precip_type, Diff::record_precip_type),
+            (&self.diag_climate, Diff::record_diag_climate),
+            (&self.soil, Diff::record_soil_delta),
+            (&self.water, Diff::record_water_delta),
+        ];
+        for (map, record) in values {
+            for (index, &value) in indexed(map)? {
+                record(&mut diff, index, value);
+            }
+        }
+        for (index, &[dx, dy]) in indexed(&self.wind)? {
+            diff.record_wind(index, dx, dy);
+        }
+        Ok(diff)
+    }
+}
+
+type RecordFn = fn(&mut Diff, usize, i32);
+
+/// `map`'s entries with their `"r:<index>"` keys parsed back to region indices.
+fn indexed<V>(map: &BTreeMap<String, V>) -> Result<Vec<(usize, &V)>> {
+    map.iter()
+        .map(|(key, value)| {
+            World::region_index_from_key(key)
+                .map(|index| (index, value))
+                .with_context(|| format!("invalid region key {:?}", key))
+        })
+        .collect()
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FrameWorldMeta {
     pub width: u32,
     pub height: u32,
+    /// Seed provenance hash; runners attach it to the first frame of a run.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub seed_hash: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Frame {
     pub t: u64,
     pub world: FrameWorldMeta,
@@ -106,11 +217,47 @@ pub struct Frame {
     pub diff: FrameDiff,
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub diagnostics: BTreeMap<String, i32>,
+    /// Cause code name → number of causes recorded this tick; opt-in via [`FrameOptions`].
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub cause_summary: BTreeMap<String, u32>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub highlights: Vec<Highlight>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub chronicle: Vec<String>,
+    /// The tick's causes, kept in memory for [`Frame::narrative_events`]; never serialized,
+    /// so a frame read back from NDJSON has none. Only `cause_summary` counts survive, and
+    /// each code's [`crate::cause::Code::subsystem`] and [`crate::cause::Code::severity`]
+    /// can be recovered from those names.
+    #[serde(skip)]
+    pub causes: Vec<Entry>,
     pub era_end: bool,
+    /// Set when [`Frame::truncate_to`] dropped low-priority fields to fit a byte cap.
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub truncated: bool,
+}
+
+/// Optional frame payloads that are skipped by default to keep steady output lean.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameOptions {
+    /// Emit per-region prevailing wind vectors as `[dx, dy]` pairs.
+    pub include_wind: bool,
+    /// Emit surface pressure in whole hPa for regions whose elevation changed.
+    pub include_pressure: bool,
+    /// Emit a `cause_summary` histogram of the tick's cause codes.
+    pub include_cause_summary: bool,
+    /// Highlight budget per frame, applied with [`collect_highlights`]; defaults to all.
+    pub max_highlights: usize,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            include_wind: false,
+            include_pressure: false,
+            include_cause_summary: false,
+            max_highlights: usize::MAX,
+        }
+    }
 }
 
 pub fn make_frame(
@@ -122,6 +269,35 @@ pub fn make_frame(
     width: u32,
     height: u32,
 ) -> Frame {
+    make_frame_with_options(
+        t,
+        diff,
+        highlights,
+        chronicle,
+        era_end,
+        width,
+        height,
+        FrameOptions::default(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_frame_with_options(
+    t: u64,
+    diff: Diff,
+    highlights: Vec<Highlight>,
+    chronicle: Vec<String>,
+    era_end: bool,
+    width: u32,
+    height: u32,
+    options: FrameOptions,
+) -> Frame {
+    let mut cause_summary = BTreeMap::new();
+    if options.include_cause_summary {
+        for cause in &diff.causes {
+            *cause_summary.entry(cause.code.to_string()).or_insert(0) += 1;
+        }
+    }
     let mut frame_diff = FrameDiff::default();
     for change in diff.biome {
         frame_diff
@@ -139,6 +315,12 @@ pub fn make_frame(
             .insert(World::region_key(value.region as usize), value.value);
     }
     for value in diff.elevation {
+        if options.include_pressure {
+            frame_diff.pressure.insert(
+                World::region_key(value.region as usize),
+                pressure_hpa(value.value).round() as i32,
+            );
+        }
         frame_diff
             .elevation
             .insert(World::region_key(value.region as usize), value.value);
@@ -193,11 +375,24 @@ pub fn make_frame(
             .heatwave_idx
             .insert(World::region_key(value.region as usize), value.value);
     }
+    for value in diff.precip_type {
+        frame_diff
+            .precip_type
+            .insert(World::region_key(value.region as usize), value.value);
+    }
     for value in diff.diag_climate {
         frame_diff
             .diag_climate
             .insert(World::region_key(value.region as usize), value.value);
     }
+    if options.include_wind {
+        for vector in diff.wind {
+            frame_diff.wind.insert(
+                World::region_key(vector.region as usize),
+                [vector.dx, vector.dy],
+            );
+        }
+    }
     for delta in diff.soil {
         frame_diff
             .soil
@@ -213,10 +408,44 @@ pub fn make_frame(
         t,
         diff: frame_diff,
         diagnostics: diff.diagnostics,
-        world: FrameWorldMeta { width, height },
-        highlights,
+        cause_summary,
+        world: FrameWorldMeta {
+            width,
+            height,
+            seed_hash: None,
+        },
+        highlights: collect_highlights(highlights, options.max_highlights),
         chronicle,
+        causes: diff.causes,
         era_end,
+        truncated: false,
+    }
+}
+
+/// Diff maps that carry no reducer state, in the order `truncate_to` drops them.
+const TRUNCATION_ORDER: &[fn(&mut FrameDiff)] = &[
+    |diff| diff.diag_climate.clear(),
+    |diff| diff.humidity.clear(),
+    |diff| diff.wind.clear(),
+    |diff| diff.pressure.clear(),
+    |diff| diff.precip_type.clear(),
+    |diff| diff.heatwave_idx.clear(),
+    |diff| diff.precip_extreme.clear(),
+    |diff| diff.melt_pulse.clear(),
+    |diff| diff.permafrost_active.clear(),
+];
+
+/// `io::Write` sink that only counts bytes, so sizing a frame allocates nothing.
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
@@ -226,6 +455,45 @@ impl Frame {
         json.push('\n');
         Ok(json)
     }
+
+    /// Parse one NDJSON frame line; a trailing newline is accepted.
+    pub fn from_ndjson(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line.trim_end_matches(['\n', '\r']))
+    }
+
+    /// The tick's causes grouped into phrasing-free events; see
+    /// [`narrative::narrative_events`]. Empty for frames read back from NDJSON.
+    pub fn narrative_events(&self) -> Vec<NarrativeEvent> {
+        narrative::narrative_events(&self.causes)
+    }
+
+    /// Byte length of [`Frame::to_ndjson`], including the trailing newline.
+    pub fn estimated_bytes(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        match serde_json::to_writer(&mut counter, self) {
+            Ok(()) => counter.0 + 1,
+            Err(_) => usize::MAX,
+        }
+    }
+
+    /// Drop low-priority diagnostic maps until the frame fits in `max_bytes`.
+    ///
+    /// State-bearing maps (resource deltas, temperatures, biomes, ...) are never dropped,
+    /// so viewers stay in sync. Marks the frame `truncated` when anything was removed and
+    /// returns whether it now fits.
+    pub fn truncate_to(&mut self, max_bytes: usize) -> bool {
+        if self.estimated_bytes() <= max_bytes {
+            return true;
+        }
+        for drop_field in TRUNCATION_ORDER {
+            drop_field(&mut self.diff);
+            self.truncated = true;
+            if self.estimated_bytes() <= max_bytes {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 #[cfg(test)]
@@ -233,6 +501,184 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    #[test]
+    fn highlight_extras_serialize_only_when_present() {
+        let hazard = serde_json::to_string(&Highlight::hazard(3, "flood", 0.5)).unwrap();
+        assert_eq!(
+            hazard,
+            r#"{"type":"hazard_flag","region":3,"info":{"kind":"flood","level":0.5}}"#
+        );
+
+        let upstream = Highlight::hazard(3, "flood", 0.5)
+            .with_source_region(1)
+            .with_detail("melt_mm", 12.5);
+        let json: serde_json::Value = serde_json::to_value(&upstream).unwrap();
+        assert_eq!(json["info"]["source_region"], 1);
+        assert_eq!(json["info"]["detail"]["melt_mm"], 12.5);
+        let parsed: Highlight = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, upstream);
+    }
+
+    #[test]
+    fn collect_highlights_keeps_top_priorities_in_stable_order() {
+        let candidates = vec![
+            Highlight::hazard(0, "drought", 0.4),
+            Highlight::hazard(1, "flood", 0.9),
+            Highlight::hazard(2, "drought", 0.6),
+            Highlight::hazard(3, "flood", 0.2),
+            Highlight::hazard(4, "drought", 0.6),
+        ];
+        assert_eq!(
+            collect_highlights(candidates.clone(), usize::MAX),
+            candidates
+        );
+
+        let top: Vec<u32> = collect_highlights(candidates.clone(), 3)
+            .iter()
+            .map(|highlight| highlight.region)
+            .collect();
+        assert_eq!(top, vec![1, 2, 4]);
+
+        let frame = make_frame_with_options(
+            1,
+            Diff::default(),
+            candidates,
+            Vec::new(),
+            false,
+            5,
+            1,
+            FrameOptions {
+                max_highlights: 3,
+                ..FrameOptions::default()
+            },
+        );
+        assert_eq!(frame.highlights.len(), 3);
+    }
+
+    #[test]
+    fn cause_summary_counts_codes_when_enabled() {
+        use crate::cause::{Code, Entry};
+
+        let mut diff = Diff::default();
+        diff.record_cause(Entry::new("region:0/temp", Code::HadleyCell, None));
+        diff.record_cause(Entry::new("region:1/temp", Code::HadleyCell, None));
+        diff.record_cause(Entry::new("region:1/precip", Code::RainShadow, None));
+
+        let options = FrameOptions {
+            include_cause_summary: true,
+            ..FrameOptions::default()
+        };
+        let frame = make_frame_with_options(
+            1,
+            diff.clone(),
+            Vec::new(),
+            Vec::new(),
+            false,
+            2,
+            1,
+            options,
+        );
+        let json = serde_json::to_value(frame).unwrap();
+        assert_eq!(
+            json["cause_summary"],
+            serde_json::json!({"hadley_cell": 2, "rain_shadow": 1})
+        );
+
+        let plain = make_frame(1, diff, Vec::new(), Vec::new(), false, 2, 1);
+        assert!(plain.cause_summary.is_empty());
+        assert!(!plain.to_ndjson().unwrap().contains("cause_summary"));
+    }
+
+    #[test]
+    fn narrative_events_type_monsoon_onset_and_volcanic_pulse() {
+        use crate::cause::{Code, Entry, Severity, Subsystem};
+
+        let mut diff = Diff::default();
+        diff.record_cause(Entry::new(
+            "region:2/precip",
+            Code::MonsoonOnset,
+            Some("humidity=0.82;strength=0.41".to_string()),
+        ));
+        diff.record_cause(Entry::new(
+            "world:atmosphere",
+            Code::VolcanicAerosolPulse,
+            Some("region=5 optical_depth=0.031".to_string()),
+        ));
+
+        let frame = make_frame(1, diff, Vec::new(), Vec::new(), false, 3, 2);
+        let events = frame.narrative_events();
+        assert_eq!(
+            events,
+            vec![
+                NarrativeEvent {
+                    subsystem: Subsystem::Geodynamics,
+                    severity: Severity::Major,
+                    code: Code::VolcanicAerosolPulse,
+                    count: 1,
+                    regions: vec![5],
+                },
+                NarrativeEvent {
+                    subsystem: Subsystem::Atmosphere,
+                    severity: Severity::Notable,
+                    code: Code::MonsoonOnset,
+                    count: 1,
+                    regions: vec![2],
+                },
+            ]
+        );
+        assert!(!frame.to_ndjson().unwrap().contains("causes"));
+    }
+
+    #[test]
+    fn from_ndjson_round_trips_serialized_frames() {
+        let mut diff = Diff::default();
+        diff.record_temperature(1, -35);
+        diff.record_water_delta(0, 12);
+        let frame = make_frame(
+            4,
+            diff,
+            vec![Highlight::hazard(1, "drought", 0.5)],
+            vec!["Rains failed.".to_string()],
+            false,
+            2,
+            1,
+        );
+        let line = frame.to_ndjson().expect("frame serializes");
+        let parsed = Frame::from_ndjson(&line).expect("frame parses");
+        assert_eq!(parsed.t, 4);
+        assert_eq!(parsed.diff.temp.get("r:1"), Some(&-35));
+        assert_eq!(parsed.to_ndjson().expect("frame serializes"), line);
+        assert!(Frame::from_ndjson("{\"t\":1}").is_err());
+    }
+
+    #[test]
+    fn truncate_to_drops_diagnostics_only_from_oversized_frames() {
+        let mut small_diff = Diff::default();
+        small_diff.record_water_delta(0, 12);
+        let mut small = make_frame(1, small_diff, Vec::new(), Vec::new(), false, 2, 1);
+        let small_line = small.to_ndjson().expect("frame serializes");
+        assert_eq!(small.estimated_bytes(), small_line.len());
+        assert!(small.truncate_to(1_024));
+        assert!(!small.truncated);
+        assert_eq!(small.to_ndjson().expect("frame serializes"), small_line);
+
+        let mut huge_diff = Diff::default();
+        for region in 0..2_000 {
+            huge_diff.record_humidity(region, 500);
+            huge_diff.record_diag_climate(region, -7);
+        }
+        huge_diff.record_water_delta(0, 12);
+        let mut huge = make_frame(1, huge_diff, Vec::new(), Vec::new(), false, 2_000, 1);
+        assert!(huge.estimated_bytes() > 1_024);
+        assert!(huge.truncate_to(1_024));
+        assert!(huge.estimated_bytes() <= 1_024);
+        assert!(huge.truncated);
+        assert_eq!(huge.diff.water.get("r:0"), Some(&12));
+
+        let json_line = huge.to_ndjson().expect("frame serializes");
+        assert!(json_line.contains("\"truncated\":true"));
+    }
+
     #[test]
     fn frame_diff_excludes_hazards_key() {
         let mut diff = Diff::default();
@@ -429,7 +875,7 @@ mod tests {
                 diff.record_freshwater_flux(*region, base.abs());
                 let melt = if base == 0 { 5 } else { base.abs() };
                 diff.record_melt_pulse(*region, melt);
-                diff.record_ice_mass(*region, (base.abs() + 100) as i32);
+                diff.record_ice_mass(*region, base.abs() + 100);
                 diff.record_heatwave_idx(*region, extreme);
                 diff.record_water_delta(*region, extreme);
                 diff.record_soil_delta(*region, -extreme);
@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+
+use crate::world::World;
+
+/// Export every region as a GeoJSON `Polygon` feature covering its grid cell.
+///
+/// Longitude spans `-180..180` across the grid width and latitude follows
+/// [`crate::world::latitude_from_row`], so row 0 is the northernmost band. Cells are drawn
+/// equirectangularly even for equal-area seeds, whose state does not record a projection.
+pub fn world_to_geojson(world: &World) -> Value {
+    let width = f64::from(world.width.max(1));
+    let height = f64::from(world.height.max(1));
+    let features: Vec<Value> = world
+        .regions
+        .iter()
+        .map(|region| {
+            let west = f64::from(region.x) / width * 360.0 - 180.0;
+            let east = f64::from(region.x + 1) / width * 360.0 - 180.0;
+            let north = 90.0 - f64::from(region.y) / height * 180.0;
+            let south = 90.0 - f64::from(region.y + 1) / height * 180.0;
+            // RFC 7946 exterior rings run counter-clockwise and close on the first point.
+            let ring = [
+                [west, south],
+                [east, south],
+                [east, north],
+                [west, north],
+                [west, south],
+            ];
+            json!({
+                "type": "Feature",
+                "id": region.id,
+                "geometry": {"type": "Polygon", "coordinates": [ring]},
+                "properties": {
+                    "region": World::region_key(region.index()),
+                    "x": region.x,
+                    "y": region.y,
+                    "biome": region.biome,
+                    "elevation_m": region.elevation_m,
+                    "temp_c": f64::from(region.temperature_tenths_c) / 10.0,
+                    "water": region.water,
+                },
+            })
+        })
+        .collect();
+    json!({
+        "type": "FeatureCollection",
+        "features": features,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_tile_the_globe_from_north_to_south() {
+        let world =
+            World::from_fields(3, 2, 2, &[0, 10, 20, 30], &[0; 4], |_, _| 0.0).expect("grid");
+        let collection = world_to_geojson(&world);
+        let features = collection["features"].as_array().expect("features");
+        assert_eq!(features.len(), 4);
+        let ring = &features[1]["geometry"]["coordinates"][0];
+        assert_eq!(ring[0], json!([0.0, 0.0]));
+        assert_eq!(ring[2], json!([180.0, 90.0]));
+        assert_eq!(ring[4], ring[0]);
+        assert_eq!(features[3]["properties"]["elevation_m"], 30);
+        assert_eq!(
+            features[3]["geometry"]["coordinates"][0][0],
+            json!([0.0, -90.0])
+        );
+    }
+}
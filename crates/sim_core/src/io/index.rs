@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// One sidecar line: the byte offset at which frame `t` starts in the NDJSON file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameIndexEntry {
+    pub t: u64,
+    pub offset: u64,
+}
+
+impl FrameIndexEntry {
+    /// Serialize as a single newline-terminated NDJSON line.
+    pub fn to_ndjson(&self) -> Result<String> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        Ok(line)
+    }
+}
+
+/// Tick → byte offset lookup loaded from a `simstep --index-out` sidecar.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameIndex {
+    offsets: BTreeMap<u64, u64>,
+}
+
+impl FrameIndex {
+    /// Load an index sidecar from disk.
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open frame index {:?}", path))?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Parse index entries from an arbitrary NDJSON reader; blank lines are skipped.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let mut offsets = BTreeMap::new();
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.context("failed to read frame index")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: FrameIndexEntry = serde_json::from_str(&line)
+                .with_context(|| format!("invalid frame index entry on line {}", number + 1))?;
+            ensure!(
+                offsets.insert(entry.t, entry.offset).is_none(),
+                "frame index lists tick {} more than once",
+                entry.t
+            );
+        }
+        Ok(Self { offsets })
+    }
+
+    /// Byte offset of the first byte of frame `t`, if indexed.
+    pub fn offset_for_tick(&self, t: u64) -> Option<u64> {
+        self.offsets.get(&t).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_entries_and_rejects_duplicate_ticks() {
+        let lines = [
+            FrameIndexEntry { t: 1, offset: 0 },
+            FrameIndexEntry { t: 2, offset: 120 },
+        ]
+        .iter()
+        .map(|entry| entry.to_ndjson().expect("entry serializes"))
+        .collect::<String>();
+        let index = FrameIndex::from_reader(lines.as_bytes()).expect("index parses");
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.offset_for_tick(2), Some(120));
+        assert_eq!(index.offset_for_tick(3), None);
+
+        let duplicate = format!(
+            "{}{}",
+            lines,
+            FrameIndexEntry { t: 2, offset: 9 }.to_ndjson().unwrap()
+        );
+        assert!(FrameIndex::from_reader(duplicate.as_bytes()).is_err());
+    }
+}
@@ -1,2 +1,9 @@
 pub mod frame;
+pub mod geojson;
+pub mod index;
+pub mod narrative;
 pub mod seed;
+pub mod snapshot;
+pub mod stream;
+
+pub use index::{FrameIndex, FrameIndexEntry};
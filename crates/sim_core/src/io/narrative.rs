@@ -0,0 +1,85 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cause::{Code, Entry, Severity, Subsystem};
+
+/// One tick's causes of a single [`Code`], stripped of kernel phrasing so a downstream
+/// narrative layer can word them itself.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NarrativeEvent {
+    pub subsystem: Subsystem,
+    pub severity: Severity,
+    pub code: Code,
+    /// Number of causes folded into this event.
+    pub count: u32,
+    /// Regions named by the causes, ascending and deduplicated; empty for world-wide codes.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub regions: Vec<u32>,
+}
+
+/// Group `causes` by code, ordered by subsystem, then most severe first, then code.
+pub fn narrative_events(causes: &[Entry]) -> Vec<NarrativeEvent> {
+    let mut grouped: BTreeMap<(Subsystem, Reverse<Severity>, Code), NarrativeEvent> =
+        BTreeMap::new();
+    for cause in causes {
+        let code = cause.code;
+        let event = grouped
+            .entry((code.subsystem(), Reverse(code.severity()), code))
+            .or_insert_with(|| NarrativeEvent {
+                subsystem: code.subsystem(),
+                severity: code.severity(),
+                code,
+                count: 0,
+                regions: Vec::new(),
+            });
+        event.count += 1;
+        event.regions.extend(cause_region(cause));
+    }
+    grouped
+        .into_values()
+        .map(|mut event| {
+            event.regions.sort_unstable();
+            event.regions.dedup();
+            event
+        })
+        .collect()
+}
+
+/// Region a cause is about: its `region:<id>/...` target, or a `region=<id>` note field
+/// for world-targeted causes such as volcanic pulses.
+fn cause_region(cause: &Entry) -> Option<u32> {
+    if let Some(rest) = cause.target.strip_prefix("region:") {
+        return rest.split('/').next().and_then(|id| id.parse().ok());
+    }
+    cause
+        .note
+        .as_deref()?
+        .split([' ', ';'])
+        .find_map(|field| field.strip_prefix("region="))
+        .and_then(|id| id.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn causes_of_one_code_fold_into_a_single_event() {
+        let causes = vec![
+            Entry::new("region:4/precip", Code::RainShadow, None),
+            Entry::new("region:1/precip", Code::RainShadow, None),
+            Entry::new("region:4/temperature", Code::RainShadow, None),
+            Entry::new("world:atmosphere", Code::HadleyDrift, None),
+        ];
+
+        let events = narrative_events(&causes);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].code, Code::HadleyDrift);
+        assert!(events[0].regions.is_empty());
+        assert_eq!(events[1].code, Code::RainShadow);
+        assert_eq!(events[1].count, 3);
+        assert_eq!(events[1].regions, vec![1, 4]);
+    }
+}
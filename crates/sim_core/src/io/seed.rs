@@ -2,15 +2,18 @@ use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
 
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
 
 use crate::fixed::{clamp_u16, ALBEDO_MAX, FRESHWATER_FLUX_MAX, SOIL_MAX, WATER_MAX};
-use crate::rng::Stream;
-use crate::world::{Hazards, Region, World};
+use crate::rng::{fnv1a64, RngKind, Stream};
+use crate::world::{
+    Hazards, Projection, Region, Tuning, World, WrapMode, MIN_EXTREME_WINDOW,
+    MIN_LUNAR_PERIOD_TICKS,
+};
 
 /// Parsed seed definition describing the deterministic initial world.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Seed {
     pub name: String,
     pub width: u32,
@@ -19,25 +22,142 @@ pub struct Seed {
     pub noise: Noise,
     #[serde(rename = "humidity_bias")]
     pub humidity: Humidity,
+    /// Optional; omitted from serialization at defaults so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "SoilFertility::is_default")]
+    pub soil_fertility: SoilFertility,
+    /// Named RNG algorithm; omitted at the default so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "RngKind::is_default")]
+    pub rng: RngKind,
+    /// Starting biome codes; regions start at biome 0 when omitted, which keeps existing
+    /// seed hashes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_biomes: Option<InitialBiomes>,
+    /// Row-to-latitude mapping; omitted at the default so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "Projection::is_default")]
+    pub projection: Projection,
+    /// Samples in the heat and precipitation extreme windows (at least
+    /// [`MIN_EXTREME_WINDOW`]); [`crate::world::DEFAULT_EXTREME_WINDOW`] when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extreme_window: Option<usize>,
+    /// Ticks per spring–neap tide cycle (at least [`MIN_LUNAR_PERIOD_TICKS`]);
+    /// [`crate::world::DEFAULT_LUNAR_PERIOD_TICKS`] when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lunar_period_ticks: Option<u32>,
+    /// Optional kernel couplings and thresholds carried into the world; omitted at the
+    /// defaults so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "Tuning::is_default")]
+    pub tuning: Tuning,
+    /// How the grid edges join; omitted at the default so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "WrapMode::is_default")]
+    pub wrap: WrapMode,
+}
+
+/// Biomes a world starts in before its first climate classification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InitialBiomes {
+    /// One code for every region.
+    Uniform(u8),
+    /// One code per region in row-major order; must hold exactly `width * height` entries.
+    PerRegion(Vec<u8>),
+}
+
+impl InitialBiomes {
+    fn biome_at(&self, index: usize) -> u8 {
+        match self {
+            Self::Uniform(biome) => *biome,
+            Self::PerRegion(biomes) => biomes.get(index).copied().unwrap_or(0),
+        }
+    }
 }
 
 /// Multi-octave pseudo-noise configuration for elevation sampling.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Noise {
     pub octaves: u8,
     pub freq: f64,
     pub amp: f64,
     pub seed: u64,
+    /// Elevation noise style; omitted at the default so existing seed hashes hold.
+    #[serde(default, skip_serializing_if = "NoiseMode::is_default")]
+    pub mode: NoiseMode,
+}
+
+/// How octaves of elevation noise combine.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseMode {
+    /// Independent per-cell samples with halving amplitude; no spatial coherence.
+    #[default]
+    White,
+    /// Ridged multifractal: smooth lattice noise folded by absolute value, with each
+    /// octave doubling `freq` and weighted by the previous ridge, so high ground forms
+    /// connected ranges.
+    Ridged,
+}
+
+impl NoiseMode {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Deterministic humidity bias per latitude band.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Humidity {
     pub equator: f64,
     pub poles: f64,
 }
 
+/// Floodplain soil enrichment for low, well-watered regions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoilFertility {
+    /// Extra soil fraction (of `SOIL_MAX`) granted to a sea-level region at full water.
+    pub floodplain_bonus: f64,
+    /// Elevation in metres above which regions receive no floodplain bonus.
+    pub lowland_max_m: f64,
+}
+
+// TODO(agents): rationale - modest bonus keeps default soil near the humidity-only model.
+impl Default for SoilFertility {
+    fn default() -> Self {
+        Self {
+            floodplain_bonus: 0.08,
+            lowland_max_m: 600.0,
+        }
+    }
+}
+
+impl SoilFertility {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Soil fraction added for a region at `elevation_m` holding `water` units.
+    fn floodplain_term(&self, elevation_m: i32, water: u16) -> f64 {
+        if self.lowland_max_m <= 0.0 {
+            return 0.0;
+        }
+        let lowland = 1.0 - (f64::from(elevation_m) / self.lowland_max_m).clamp(0.0, 1.0);
+        let wetness = f64::from(water) / f64::from(WATER_MAX);
+        self.floodplain_bonus * lowland * wetness
+    }
+}
+
+/// Seed path that reads the document from standard input instead of a file.
+pub const STDIN_PATH: &str = "-";
+
 impl Seed {
+    /// Load a seed from `path`, or from standard input when `path` is [`STDIN_PATH`].
+    pub fn load(path: &Path) -> Result<Self> {
+        if path == Path::new(STDIN_PATH) {
+            return Self::from_reader(std::io::stdin().lock())
+                .context("failed to read seed from stdin");
+        }
+        Self::load_from_path(path)
+    }
+
     /// Load a seed JSON document from disk.
     pub fn load_from_path(path: &Path) -> Result<Self> {
         let file =
@@ -47,7 +167,46 @@ impl Seed {
 
     /// Deserialize a seed document from an arbitrary reader.
     pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
-        Ok(serde_json::from_reader(reader).context("invalid seed json")?)
+        let seed: Self = serde_json::from_reader(reader).context("invalid seed json")?;
+        seed.validate()?;
+        Ok(seed)
+    }
+
+    /// Check cross-field constraints serde cannot express.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(InitialBiomes::PerRegion(biomes)) = &self.initial_biomes {
+            let regions = self.width as usize * self.height as usize;
+            ensure!(
+                biomes.len() == regions,
+                "initial_biomes has {} entries but the {}x{} grid has {} regions",
+                biomes.len(),
+                self.width,
+                self.height,
+                regions
+            );
+        }
+        if let Some(len) = self.extreme_window {
+            ensure!(
+                len >= MIN_EXTREME_WINDOW,
+                "extreme_window {} is shorter than {} samples",
+                len,
+                MIN_EXTREME_WINDOW
+            );
+        }
+        if let Some(period) = self.lunar_period_ticks {
+            ensure!(
+                period >= MIN_LUNAR_PERIOD_TICKS,
+                "lunar_period_ticks {} is shorter than {} ticks",
+                period,
+                MIN_LUNAR_PERIOD_TICKS
+            );
+        }
+        ensure!(
+            self.wrap != WrapMode::Cylinder || self.width > 2,
+            "wrap \"cylinder\" needs a grid at least 3 regions wide, got {}",
+            self.width
+        );
+        self.tuning.validate()
     }
 }
 
@@ -58,12 +217,12 @@ pub fn build_world(seed: &Seed, world_seed_override: Option<u64>) -> World {
     let mut id: u32 = 0;
     for y in 0..seed.height {
         for x in 0..seed.width {
-            let latitude = latitude_from_grid(y, seed.height);
-            let elevation = sample_elevation(world_seed, &seed.noise, x, y);
-            let (water, soil) =
-                initial_resources(world_seed, &seed.humidity, latitude, elevation, x, y);
+            let latitude = seed.projection.latitude(y, seed.height);
+            let elevation = sample_elevation(world_seed, seed, x, y);
+            let (water, soil) = initial_resources(world_seed, seed, latitude, elevation, x, y);
             let polar_factor = (latitude.abs() / 90.0).clamp(0.0, 1.0);
-            let mut cryosphere_rng = Stream::from(world_seed, "seed:cryosphere", u64::from(id));
+            let mut cryosphere_rng =
+                Stream::with_kind(seed.rng, world_seed, "seed:cryosphere", u64::from(id));
             let albedo_noise = cryosphere_rng.next_signed_unit() * 25.0;
             let albedo = clamp_u16(
                 (300.0 + 500.0 * polar_factor + albedo_noise).round() as i32,
@@ -79,7 +238,10 @@ pub fn build_world(seed: &Seed, world_seed_override: Option<u64>) -> World {
                 y,
                 elevation_m: elevation,
                 latitude_deg: latitude,
-                biome: 0,
+                biome: seed
+                    .initial_biomes
+                    .as_ref()
+                    .map_or(0, |biomes| biomes.biome_at(id as usize)),
                 water,
                 soil,
                 temperature_tenths_c: 0,
@@ -93,21 +255,50 @@ pub fn build_world(seed: &Seed, world_seed_override: Option<u64>) -> World {
         }
     }
 
-    World::new(world_seed, seed.width, seed.height, regions)
+    let mut world = World::new(world_seed, seed.width, seed.height, regions);
+    world.seed_hash = Some(seed_hash(seed, world_seed));
+    world.rng_kind = seed.rng;
+    if let Some(len) = seed.extreme_window {
+        world
+            .climate
+            .set_extreme_window(len.max(MIN_EXTREME_WINDOW))
+            .expect("window length is at least the minimum");
+    }
+    if let Some(period) = seed.lunar_period_ticks {
+        world.climate.lunar_period_ticks = period.max(MIN_LUNAR_PERIOD_TICKS);
+    }
+    world.tuning = seed.tuning.clone();
+    world.wrap = seed.wrap;
+    world
+}
+
+/// Stable provenance hash over the canonical seed JSON and the effective world seed.
+///
+/// Field order follows the struct definition, so the hash is independent of the key order
+/// or whitespace in the source document.
+pub fn seed_hash(seed: &Seed, world_seed: u64) -> u64 {
+    let mut bytes = serde_json::to_vec(seed).expect("seed serializes to json");
+    bytes.extend_from_slice(&world_seed.to_le_bytes());
+    fnv1a64(&bytes)
 }
 
-fn latitude_from_grid(y: u32, height: u32) -> f64 {
-    let ratio = (f64::from(y) + 0.5) / f64::from(height);
-    90.0 - ratio * 180.0
+/// Render a seed hash the way frames and daemon messages carry it.
+pub fn format_seed_hash(hash: u64) -> String {
+    format!("{:016x}", hash)
 }
 
-fn sample_elevation(seed: u64, noise: &Noise, x: u32, y: u32) -> i32 {
+fn sample_elevation(world_seed: u64, seed: &Seed, x: u32, y: u32) -> i32 {
+    let noise = &seed.noise;
+    if noise.mode == NoiseMode::Ridged {
+        return sample_ridged_elevation(world_seed, seed, x, y);
+    }
     let mut octave = 0;
     let mut amplitude = noise.amp;
     let mut total = 0.0;
     while octave < noise.octaves {
         let context = ((x as u64) << 32) ^ ((y as u64) << 16) ^ u64::from(octave);
-        let mut rng = Stream::from(seed ^ noise.seed, "seed:elevation", context);
+        let mut rng =
+            Stream::with_kind(seed.rng, world_seed ^ noise.seed, "seed:elevation", context);
         let sample = rng.next_signed_unit();
         total += sample * amplitude * 500.0;
         amplitude *= 0.5;
@@ -116,17 +307,66 @@ fn sample_elevation(seed: u64, noise: &Noise, x: u32, y: u32) -> i32 {
     (total + 500.0).clamp(0.0, 3_000.0).round() as i32
 }
 
+/// [`NoiseMode::Ridged`] elevation in `0..=3_000` m, scaled by `amp`.
+fn sample_ridged_elevation(world_seed: u64, seed: &Seed, x: u32, y: u32) -> i32 {
+    let noise = &seed.noise;
+    let mut frequency = noise.freq;
+    let mut amplitude = 1.0;
+    let mut weight = 1.0;
+    let mut total = 0.0;
+    let mut norm = 0.0;
+    for octave in 0..noise.octaves {
+        let sample = lattice_noise(
+            world_seed,
+            seed,
+            octave,
+            f64::from(x) * frequency,
+            f64::from(y) * frequency,
+        );
+        let crest = 1.0 - sample.abs();
+        let ridge = crest * crest * weight;
+        weight = (ridge * 2.0).clamp(0.0, 1.0);
+        total += ridge * amplitude;
+        norm += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    let ridged = if norm > 0.0 { total / norm } else { 0.0 };
+    (ridged * noise.amp * 3_000.0).clamp(0.0, 3_000.0).round() as i32
+}
+
+/// Smoothly interpolated value noise in `[-1, 1)` at lattice coordinates `(u, v)`.
+fn lattice_noise(world_seed: u64, seed: &Seed, octave: u8, u: f64, v: f64) -> f64 {
+    let corner = |cx: f64, cy: f64| {
+        let context = ((cx as u64) << 32) ^ ((cy as u64) << 8) ^ u64::from(octave);
+        Stream::with_kind(
+            seed.rng,
+            world_seed ^ seed.noise.seed,
+            "seed:elevation:ridged",
+            context,
+        )
+        .next_signed_unit()
+    };
+    let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+    let (x0, y0) = (u.floor(), v.floor());
+    let (tx, ty) = (smooth(u - x0), smooth(v - y0));
+    let top = corner(x0, y0) + (corner(x0 + 1.0, y0) - corner(x0, y0)) * tx;
+    let bottom = corner(x0, y0 + 1.0) + (corner(x0 + 1.0, y0 + 1.0) - corner(x0, y0 + 1.0)) * tx;
+    top + (bottom - top) * ty
+}
+
 fn initial_resources(
-    seed: u64,
-    humidity: &Humidity,
+    world_seed: u64,
+    seed: &Seed,
     latitude_deg: f64,
     elevation_m: i32,
     x: u32,
     y: u32,
 ) -> (u16, u16) {
     let context = ((x as u64) << 32) ^ ((y as u64) << 16);
-    let mut water_rng = Stream::from(seed, "seed:resources:water", context);
-    let mut soil_rng = Stream::from(seed, "seed:resources:soil", context);
+    let mut water_rng = Stream::with_kind(seed.rng, world_seed, "seed:resources:water", context);
+    let mut soil_rng = Stream::with_kind(seed.rng, world_seed, "seed:resources:soil", context);
+    let humidity = &seed.humidity;
     let latitude_ratio = (latitude_deg.abs() / 90.0).clamp(0.0, 1.0);
     let bias = humidity.equator + (humidity.poles - humidity.equator) * latitude_ratio;
     let base = (0.55 + bias).clamp(0.05, 0.95);
@@ -139,8 +379,9 @@ fn initial_resources(
     );
     let soil_base = (base - 0.1).clamp(0.05, 0.9);
     let soil_noise = soil_rng.next_signed_unit() * 0.04;
+    let fertility = seed.soil_fertility.floodplain_term(elevation_m, water);
     let soil = clamp_u16(
-        ((soil_base - elevation_penalty * 0.5 + soil_noise) * 10_000.0).round() as i32,
+        ((soil_base - elevation_penalty * 0.5 + fertility + soil_noise) * 10_000.0).round() as i32,
         0,
         SOIL_MAX,
     );
@@ -150,6 +391,7 @@ fn initial_resources(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::test_seed;
     use std::path::PathBuf;
 
     #[test]
@@ -164,4 +406,224 @@ mod tests {
             assert!(seed.height > 0, "seed {:?} must define height", path);
         }
     }
+
+    #[test]
+    fn piped_seed_builds_the_same_world_as_its_file() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../testdata/seeds/seed_golden_harness.json");
+        let bytes = std::fs::read(&path).expect("seed file reads");
+        let piped = Seed::from_reader(std::io::Cursor::new(bytes)).expect("piped seed parses");
+        let from_file = Seed::load(&path).expect("seed file loads");
+
+        let world_json = |seed: &Seed| serde_json::to_string(&build_world(seed, None)).unwrap();
+        assert_eq!(world_json(&piped), world_json(&from_file));
+    }
+
+    #[test]
+    fn initial_biomes_shrink_the_first_reclassification() {
+        let bare = test_seed("primed", 8, 6);
+        let json = serde_json::to_string(&bare).unwrap();
+        let first_tick_biomes = |seed: &Seed| {
+            let mut world = build_world(seed, None);
+            let world_seed = world.seed;
+            let (diff, _, _) = crate::tick_once(&mut world, world_seed, 1).expect("tick");
+            (
+                diff.biome.len(),
+                world.field_values(crate::world::RegionField::Biome),
+            )
+        };
+        let (bare_changes, settled) = first_tick_biomes(&bare);
+
+        let mut primed = bare.clone();
+        primed.initial_biomes = Some(InitialBiomes::PerRegion(
+            settled.iter().map(|&biome| biome as u8).collect(),
+        ));
+        primed.validate().expect("array matches grid");
+        let (primed_changes, _) = first_tick_biomes(&primed);
+        assert!(
+            primed_changes * 4 < bare_changes,
+            "primed {} vs bare {} biome changes",
+            primed_changes,
+            bare_changes
+        );
+
+        let uniform = json.replacen("\"name\"", "\"initial_biomes\": 3, \"name\"", 1);
+        let world = build_world(&Seed::from_reader(uniform.as_bytes()).unwrap(), None);
+        assert!(world.regions.iter().all(|region| region.biome == 3));
+        let short = json.replacen("\"name\"", "\"initial_biomes\": [1, 2], \"name\"", 1);
+        let err = Seed::from_reader(short.as_bytes()).expect_err("length mismatch");
+        assert!(err.to_string().contains("48 regions"), "{}", err);
+        assert!(!serde_json::to_string(&bare)
+            .unwrap()
+            .contains("initial_biomes"));
+    }
+
+    #[test]
+    fn ridged_noise_forms_longer_high_elevation_runs() {
+        let mut plain = test_seed("ranges", 48, 24);
+        plain.noise.octaves = 4;
+        plain.noise.freq = 0.08;
+        plain.noise.seed = 31;
+        let mut ridged = plain.clone();
+        ridged.noise.mode = NoiseMode::Ridged;
+        assert!(serde_json::to_string(&ridged)
+            .unwrap()
+            .contains(r#""mode":"ridged""#));
+        assert!(!serde_json::to_string(&plain).unwrap().contains("mode"));
+
+        let elevations = |seed: &Seed| {
+            build_world(seed, None).field_values(crate::world::RegionField::Elevation)
+        };
+        let white = elevations(&plain);
+        let ranges = elevations(&ridged);
+        assert_eq!(ranges, elevations(&ridged));
+        assert_ne!(ranges, white);
+        assert!(ranges
+            .iter()
+            .all(|elevation| (0..=3_000).contains(elevation)));
+        let spread = ranges.iter().max().unwrap() - ranges.iter().min().unwrap();
+        assert!(spread > 1_000, "ridged spread {}", spread);
+
+        // Mean length of horizontal runs of cells in the field's top quartile.
+        let mean_high_run = |field: &[i32]| {
+            let mut sorted = field.to_vec();
+            sorted.sort_unstable();
+            let threshold = sorted[sorted.len() * 3 / 4];
+            let (mut runs, mut cells) = (0usize, 0usize);
+            for row in field.chunks(48) {
+                let mut previous_high = false;
+                for &elevation in row {
+                    let high = elevation >= threshold;
+                    if high {
+                        cells += 1;
+                        runs += usize::from(!previous_high);
+                    }
+                    previous_high = high;
+                }
+            }
+            cells as f64 / runs as f64
+        };
+        assert!(
+            mean_high_run(&ranges) > 1.5 * mean_high_run(&white),
+            "ridged {} vs white {}",
+            mean_high_run(&ranges),
+            mean_high_run(&white)
+        );
+    }
+
+    #[test]
+    fn seed_hash_is_stable_and_tracks_humidity_bias() {
+        let seed = test_seed("provenance", 2, 2);
+        let first = build_world(&seed, None);
+        let second = build_world(&seed.clone(), None);
+        assert!(first.seed_hash.is_some());
+        assert_eq!(first.seed_hash, second.seed_hash);
+
+        let mut wetter = seed;
+        wetter.humidity.equator = 0.35;
+        assert_ne!(build_world(&wetter, None).seed_hash, first.seed_hash);
+    }
+
+    #[test]
+    fn tuning_section_reaches_the_kernels() {
+        let bare = test_seed("tuned", 4, 3);
+        let json = serde_json::to_string(&bare).unwrap();
+        assert!(!json.contains("tuning"));
+        let with_tuning = |tuning: &str| {
+            json.replacen("\"name\"", &format!("\"tuning\": {}, \"name\"", tuning), 1)
+        };
+
+        let tuned = Seed::from_reader(
+            with_tuning(r#"{"evapotranspiration": 400.0, "drought_alert": 9000}"#).as_bytes(),
+        )
+        .expect("tuned seed parses");
+        assert_eq!(tuned.tuning.drought_alert, 9_000);
+        assert_eq!(tuned.tuning.flood_alert, Tuning::default().flood_alert);
+        assert_ne!(
+            build_world(&tuned, None).seed_hash,
+            build_world(&bare, None).seed_hash
+        );
+
+        // Worlds start at 0 °C, where nothing evaporates, so look past the first tick.
+        let evaporation_causes = |seed: &Seed| {
+            let mut world = build_world(seed, None);
+            let world_seed = world.seed;
+            let mut count = 0;
+            for tick in 1..=3 {
+                let (diff, _, _) = crate::tick_once(&mut world, world_seed, tick).expect("tick");
+                count += diff
+                    .causes
+                    .iter()
+                    .filter(|cause| cause.code == crate::cause::Code::Evapotranspiration)
+                    .count();
+            }
+            count
+        };
+        assert_eq!(build_world(&tuned, None).tuning, tuned.tuning);
+        assert!(evaporation_causes(&tuned) > 0);
+        assert_eq!(evaporation_causes(&bare), 0);
+
+        for (tuning, message) in [
+            (r#"{"ocean_inertia": 1.5}"#, "ocean_inertia"),
+            (r#"{"volcanic_episode_rate_multiplier": 0.5}"#, "volcanic"),
+            (
+                r#"{"diag_weights": {"temperature": 1.0, "precipitation": 1.0, "water": 0.0, "albedo": 0.0, "sea_level": 0.0}}"#,
+                "sum",
+            ),
+        ] {
+            let err = Seed::from_reader(with_tuning(tuning).as_bytes()).expect_err(tuning);
+            assert!(format!("{:#}", err).contains(message), "{:#}", err);
+        }
+    }
+
+    #[test]
+    fn wrap_field_joins_the_world_edges() {
+        let bare = test_seed("seam", 4, 2);
+        let json = serde_json::to_string(&bare).unwrap();
+        assert!(!json.contains("wrap"));
+        let with_wrap =
+            |wrap: &str| json.replacen("\"name\"", &format!("\"wrap\": {}, \"name\"", wrap), 1);
+
+        let wrapped =
+            Seed::from_reader(with_wrap(r#""cylinder""#).as_bytes()).expect("cylinder seed parses");
+        let world = build_world(&wrapped, None);
+        assert_eq!(world.wrap, WrapMode::Cylinder);
+        assert!(world.neighbors4(0).any(|neighbor| neighbor == 3));
+        assert_ne!(world.seed_hash, build_world(&bare, None).seed_hash);
+        assert_eq!(build_world(&bare, None).wrap, WrapMode::Plane);
+
+        assert!(Seed::from_reader(with_wrap(r#""torus""#).as_bytes()).is_err());
+        let narrow = serde_json::to_string(&test_seed("narrow", 2, 2))
+            .unwrap()
+            .replacen("\"name\"", "\"wrap\": \"cylinder\", \"name\"", 1);
+        let err = Seed::from_reader(narrow.as_bytes()).expect_err("2-wide cylinder");
+        assert!(format!("{:#}", err).contains("3 regions wide"), "{:#}", err);
+    }
+
+    #[test]
+    fn floodplains_start_with_richer_soil_than_dry_highlands() {
+        let mut seed = test_seed("floodplain", 2, 1);
+        seed.humidity.poles = -0.4;
+        seed.soil_fertility = serde_json::from_str(r#"{"floodplain_bonus": 0.2}"#).unwrap();
+        assert_eq!(seed.soil_fertility.floodplain_bonus, 0.2);
+        assert_eq!(
+            seed.soil_fertility.lowland_max_m,
+            SoilFertility::default().lowland_max_m
+        );
+
+        let (low_water, low_soil) = initial_resources(11, &seed, 0.0, 0, 0, 0);
+        let (high_water, high_soil) = initial_resources(11, &seed, 80.0, 2_500, 1, 0);
+        assert!(low_water > high_water, "lowland should be wetter");
+        assert!(low_soil > high_soil, "{} <= {}", low_soil, high_soil);
+
+        let mut barren = seed.clone();
+        barren.soil_fertility.floodplain_bonus = 0.0;
+        let (_, plain_soil) = initial_resources(11, &barren, 0.0, 0, 0, 0);
+        let (_, plain_high_soil) = initial_resources(11, &barren, 80.0, 2_500, 1, 0);
+        assert!(
+            low_soil > plain_soil,
+            "floodplain bonus enriches low wet soil"
+        );
+        assert_eq!(high_soil, plain_high_soil, "highlands get no bonus");
+    }
 }
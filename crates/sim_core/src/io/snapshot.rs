@@ -0,0 +1,140 @@
+//! Full-fidelity world snapshots for checkpointing long runs.
+//!
+//! Plain `World` JSON omits the rolling climate windows and caches (`#[serde(skip)]`),
+//! so a world restored from it would diverge on the next tick. A snapshot stores those
+//! alongside the world so resuming reproduces the uninterrupted run exactly. Attached
+//! forcing series are not stored; callers reattach them after loading.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::world::World;
+
+/// Bumped whenever the snapshot layout changes incompatibly.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    format: u32,
+    world: World,
+    transient: TransientClimate,
+}
+
+/// `ClimateState` fields that plain world serialization skips.
+#[derive(Serialize, Deserialize)]
+struct TransientClimate {
+    temperature_maxima: Vec<VecDeque<i16>>,
+    precipitation_peaks: Vec<VecDeque<u16>>,
+    snowpack_mm: Vec<i32>,
+    permafrost_active_cm: Vec<i32>,
+    monsoon_active: Vec<bool>,
+    #[serde(default)]
+    volcanic_episode: Vec<u16>,
+}
+
+pub fn write_snapshot<W: Write>(world: &World, writer: W) -> Result<()> {
+    let climate = &world.climate;
+    let snapshot = Snapshot {
+        format: SNAPSHOT_FORMAT,
+        world: world.clone(),
+        transient: TransientClimate {
+            temperature_maxima: climate.temperature_maxima.clone(),
+            precipitation_peaks: climate.precipitation_peaks.clone(),
+            snowpack_mm: climate.snowpack_mm.clone(),
+            permafrost_active_cm: climate.permafrost_active_cm.clone(),
+            monsoon_active: climate.monsoon_active.clone(),
+            volcanic_episode: climate.volcanic_episode.clone(),
+        },
+    };
+    serde_json::to_writer(writer, &snapshot).context("failed to serialize snapshot")
+}
+
+pub fn read_snapshot<R: Read>(reader: R) -> Result<World> {
+    let snapshot: Snapshot = serde_json::from_reader(reader).context("invalid snapshot")?;
+    ensure!(
+        snapshot.format == SNAPSHOT_FORMAT,
+        "unsupported snapshot format {} (expected {})",
+        snapshot.format,
+        SNAPSHOT_FORMAT
+    );
+    let mut world = snapshot.world;
+    let transient = snapshot.transient;
+    let climate = &mut world.climate;
+    climate.temperature_maxima = transient.temperature_maxima;
+    climate.precipitation_peaks = transient.precipitation_peaks;
+    climate.snowpack_mm = transient.snowpack_mm;
+    climate.permafrost_active_cm = transient.permafrost_active_cm;
+    climate.monsoon_active = transient.monsoon_active;
+    climate.volcanic_episode = transient.volcanic_episode;
+    climate.ensure_region_capacity(world.regions.len());
+    Ok(world)
+}
+
+/// Write `world` to `path`, replacing any existing file only once the write completes.
+pub fn save_snapshot(world: &World, path: &Path) -> Result<()> {
+    let partial = path.with_extension("partial");
+    let file = File::create(&partial)
+        .with_context(|| format!("failed to create snapshot {:?}", partial))?;
+    let mut writer = BufWriter::new(file);
+    write_snapshot(world, &mut writer)?;
+    writer.flush()?;
+    std::fs::rename(&partial, path)
+        .with_context(|| format!("failed to move snapshot into place at {:?}", path))
+}
+
+pub fn load_snapshot(path: &Path) -> Result<World> {
+    let file = File::open(path).with_context(|| format!("failed to open snapshot {:?}", path))?;
+    read_snapshot(BufReader::new(file)).with_context(|| format!("failed to load {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::frame::make_frame;
+    use crate::io::seed::build_world;
+    use crate::testing::test_seed;
+    use crate::tick_once;
+
+    fn frames(world: &mut World, ticks: u64) -> Vec<String> {
+        (0..ticks)
+            .map(|_| {
+                let next_tick = world.tick + 1;
+                let (diff, chronicle, highlights) =
+                    tick_once(world, world.seed, next_tick).expect("tick succeeds");
+                make_frame(next_tick, diff, highlights, chronicle, false, 4, 3)
+                    .to_ndjson()
+                    .expect("frame serializes")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn restored_snapshot_continues_like_the_original() {
+        let seed = test_seed("snapshot", 4, 3);
+        let mut world = build_world(&seed, None);
+        frames(&mut world, 9);
+
+        let mut bytes = Vec::new();
+        write_snapshot(&world, &mut bytes).expect("snapshot writes");
+        let mut restored = read_snapshot(bytes.as_slice()).expect("snapshot reads");
+        assert_eq!(restored.tick, 9);
+        assert_eq!(frames(&mut restored, 8), frames(&mut world, 8));
+    }
+
+    #[test]
+    fn rejects_unknown_snapshot_format() {
+        let world = World::new(1, 0, 0, Vec::new());
+        let mut bytes = Vec::new();
+        write_snapshot(&world, &mut bytes).expect("snapshot writes");
+        let text =
+            String::from_utf8(bytes)
+                .expect("utf8")
+                .replacen("\"format\":1", "\"format\":99", 1);
+        assert!(read_snapshot(text.as_bytes()).is_err());
+    }
+}
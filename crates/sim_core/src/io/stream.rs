@@ -0,0 +1,172 @@
+//! Client-side decoding of the `simd` WebSocket stream.
+//!
+//! Each WebSocket text message carries one or more newline-terminated JSON lines: frames
+//! (see [`Frame`]) or control messages tagged with a `"type"` field. [`FrameDecoder`]
+//! buffers partial lines across messages so callers can feed raw payloads as they arrive.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use super::frame::Frame;
+
+/// Control messages a stream may interleave with frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlKind {
+    /// Provenance line sent once before any frames.
+    Hello,
+    /// The client fell behind and frames were skipped.
+    Lag,
+    /// The server restarted the world; earlier frames no longer apply.
+    Reset,
+}
+
+impl ControlKind {
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "hello" => Some(Self::Hello),
+            "lag" => Some(Self::Lag),
+            "reset" => Some(Self::Reset),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hello => "hello",
+            Self::Lag => "lag",
+            Self::Reset => "reset",
+        }
+    }
+}
+
+/// One decoded stream line.
+#[derive(Clone, Debug)]
+pub enum StreamMessage {
+    Frame(Box<Frame>),
+    /// A control line with its full JSON body, `type` field included.
+    Control {
+        kind: ControlKind,
+        payload: Value,
+    },
+}
+
+/// Incremental NDJSON decoder for stream payloads.
+#[derive(Clone, Debug, Default)]
+pub struct FrameDecoder {
+    pending: String,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a raw payload and decode every line it completes, in order.
+    ///
+    /// Text after the last newline is held until a later push or [`FrameDecoder::finish`].
+    /// A malformed line fails the whole push; lines before it in the same payload are
+    /// discarded with it.
+    pub fn push(&mut self, text: &str) -> Result<Vec<StreamMessage>> {
+        self.pending.push_str(text);
+        let Some(end) = self.pending.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let complete: String = self.pending.drain(..=end).collect();
+        complete
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(decode_line)
+            .collect()
+    }
+
+    /// Decode any buffered line that never received its trailing newline.
+    pub fn finish(&mut self) -> Result<Option<StreamMessage>> {
+        let rest = std::mem::take(&mut self.pending);
+        if rest.trim().is_empty() {
+            return Ok(None);
+        }
+        decode_line(&rest).map(Some)
+    }
+
+    /// Whether a partial line is waiting for more input.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.trim().is_empty()
+    }
+}
+
+fn decode_line(line: &str) -> Result<StreamMessage> {
+    let value: Value = serde_json::from_str(line.trim_end_matches('\r'))
+        .with_context(|| format!("invalid stream line {:?}", line))?;
+    if let Some(tag) = value.get("type") {
+        let Some(kind) = tag.as_str().and_then(ControlKind::from_tag) else {
+            bail!("unknown stream control message type {}", tag);
+        };
+        return Ok(StreamMessage::Control {
+            kind,
+            payload: value,
+        });
+    }
+    let frame: Frame = serde_json::from_value(value).context("invalid stream frame")?;
+    Ok(StreamMessage::Frame(Box::new(frame)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::Diff;
+    use crate::io::frame::make_frame;
+
+    fn frame_line(t: u64) -> String {
+        let mut diff = Diff::default();
+        diff.record_temperature(0, 100 + t as i32);
+        make_frame(t, diff, Vec::new(), Vec::new(), false, 1, 1)
+            .to_ndjson()
+            .expect("frame serializes")
+    }
+
+    #[test]
+    fn decoder_separates_frames_and_control_messages() {
+        let mut decoder = FrameDecoder::new();
+        let hello = "{\"type\":\"hello\",\"world\":{\"width\":1,\"height\":1}}\n";
+        let batch = format!("{}{}{}", hello, frame_line(1), frame_line(2));
+        let split = batch.len() - 10;
+
+        let first = decoder.push(&batch[..split]).expect("first push decodes");
+        assert!(decoder.has_pending());
+        let mut messages = first;
+        messages.extend(decoder.push(&batch[split..]).expect("second push decodes"));
+        messages.extend(
+            decoder
+                .push("{\"type\":\"lag\",\"skipped\":3}")
+                .expect("partial"),
+        );
+        messages.extend(decoder.finish().expect("trailing line decodes"));
+
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(
+            &messages[0],
+            StreamMessage::Control { kind: ControlKind::Hello, payload } if payload["world"]["width"] == 1
+        ));
+        for (message, t) in messages[1..3].iter().zip([1, 2]) {
+            match message {
+                StreamMessage::Frame(frame) => {
+                    assert_eq!(frame.t, t);
+                    assert_eq!(frame.diff.temp.get("r:0"), Some(&(100 + t as i32)));
+                }
+                other => panic!("expected frame, got {:?}", other),
+            }
+        }
+        assert!(matches!(
+            &messages[3],
+            StreamMessage::Control { kind: ControlKind::Lag, payload } if payload["skipped"] == 3
+        ));
+        assert!(!decoder.has_pending());
+    }
+
+    #[test]
+    fn decoder_rejects_unknown_control_types() {
+        let mut decoder = FrameDecoder::new();
+        assert!(decoder.push("{\"type\":\"bogus\"}\n").is_err());
+        assert!(decoder.push("not json\n").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{ensure, Result};
 
 use crate::cause::{Code, Entry};
+use crate::detmath;
 use crate::diff::Diff;
 use crate::kernels::atmosphere::{seasonality, SEASONAL_INSOLATION_AMPLITUDE};
 use crate::rng::Stream;
@@ -11,13 +12,45 @@ pub const STAGE: &str = "kernel:astronomy";
 
 const SOLAR_CONSTANT_WM2: f64 = 1_361.0;
 const OBLIQUITY_BASE_DEG: f64 = 23.44;
-const LAT_POWER: f64 = 0.8;
 const TIDE_EQUATOR_METERS: f64 = 3.2;
 const TIDE_POLE_METERS: f64 = 1.2;
 
-fn lat_factor(latitude_deg: f64) -> f64 {
-    let closeness = (90.0 - latitude_deg.abs()).max(0.0) / 90.0;
-    closeness.powf(LAT_POWER)
+/// Tunable astronomy cycles.
+///
+/// The solar cycle advances one step per tick through `solar_cycle_steps` steps and
+/// scales irradiance sinusoidally by up to ±`solar_cycle_amplitude_frac`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AstronomyParams {
+    pub solar_cycle_steps: u32,
+    pub solar_cycle_amplitude_frac: f64,
+    /// Record insolation and tide envelope only where they differ from the values last
+    /// committed to `ClimateState`, like temperature and precipitation. Off by default,
+    /// so every region is reported every tick.
+    pub only_changed: bool,
+}
+
+impl Default for AstronomyParams {
+    fn default() -> Self {
+        Self {
+            solar_cycle_steps: 25,
+            solar_cycle_amplitude_frac: 0.05,
+            only_changed: false,
+        }
+    }
+}
+
+impl AstronomyParams {
+    /// Position of `tick` within the solar cycle, in `0..solar_cycle_steps`.
+    pub fn solar_cycle_index(&self, tick: u64) -> u32 {
+        (tick % u64::from(self.solar_cycle_steps.max(1))) as u32
+    }
+
+    /// Irradiance multiplier for `tick`'s solar cycle step.
+    pub fn solar_cycle_amplitude(&self, tick: u64) -> f64 {
+        let steps = f64::from(self.solar_cycle_steps.max(1));
+        let phase = f64::from(self.solar_cycle_index(tick)) / steps;
+        1.0 + self.solar_cycle_amplitude_frac * detmath::sin(phase * std::f64::consts::TAU)
+    }
 }
 
 fn to_tenths(value: f64) -> i32 {
@@ -25,17 +58,23 @@ fn to_tenths(value: f64) -> i32 {
 }
 
 pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &AstronomyParams::default())
+}
+
+pub fn update_with_params(
+    world: &World,
+    rng: &mut Stream,
+    params: &AstronomyParams,
+) -> Result<KernelRun> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
 
     let obliquity_delta = rng.next_signed_unit() * 0.35;
     let obliquity_deg = OBLIQUITY_BASE_DEG + obliquity_delta;
     let precession_phase = rng.next_f64() * 360.0;
-    let solar_cycle_position = rng.next_f64();
-    let solar_cycle_index = (solar_cycle_position * 25.0).floor() as u32;
-    let solar_cycle_amplitude = 1.0 + (solar_cycle_position - 0.5) * 0.1;
-    let lunar_phase = rng.next_f64();
-    let lunar_wave = lunar_phase * 2.0 - 1.0;
+    let solar_cycle_index = params.solar_cycle_index(world.tick + 1);
+    let solar_cycle_amplitude = params.solar_cycle_amplitude(world.tick + 1);
+    let lunar_wave = world.climate.lunar_wave(world.tick + 1);
     let seasonal_scalar = seasonality::scalar_for_tick(world.tick + 1);
     let seasonal_bias = (1.0 + SEASONAL_INSOLATION_AMPLITUDE * seasonal_scalar).clamp(
         1.0 - SEASONAL_INSOLATION_AMPLITUDE,
@@ -69,12 +108,17 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
             index
         );
 
-        let lat_effect = lat_factor(region.latitude_deg);
+        let lat_effect = seasonality::insolation_factor(region.latitude_deg);
         let insolation_wm2 = SOLAR_CONSTANT_WM2
             * solar_cycle_amplitude
             * seasonal_bias
             * (0.35 + 0.65 * lat_effect * (obliquity_deg / OBLIQUITY_BASE_DEG));
-        diff.record_insolation(index, to_tenths(insolation_wm2));
+        let insolation_tenths = to_tenths(insolation_wm2);
+        if !params.only_changed
+            || world.climate.last_insolation_tenths.get(index) != Some(&insolation_tenths)
+        {
+            diff.record_insolation(index, insolation_tenths);
+        }
 
         let delta_wm2 = (equatorial_insolation - insolation_wm2).abs();
         diff.record_cause(Entry::new(
@@ -86,7 +130,12 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
         let tide_lat_component =
             TIDE_POLE_METERS + (TIDE_EQUATOR_METERS - TIDE_POLE_METERS) * lat_effect;
         let tide_envelope_m = tide_lat_component * (1.0 + 0.25 * lunar_wave);
-        diff.record_tide_envelope(index, to_tenths(tide_envelope_m));
+        let tide_tenths = to_tenths(tide_envelope_m);
+        if !params.only_changed
+            || world.climate.last_tide_envelope_tenths.get(index) != Some(&tide_tenths)
+        {
+            diff.record_tide_envelope(index, tide_tenths);
+        }
 
         let tide_code = if lunar_wave >= 0.0 {
             Code::TideSpring
@@ -121,43 +170,13 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
 mod tests {
     use super::*;
     use crate::rng::Stream;
-    use crate::world::{Hazards, Region, World};
+    use crate::world::{Region, World, DEFAULT_LUNAR_PERIOD_TICKS};
 
     #[test]
     fn astronomy_update_populates_diff_and_chronicle() {
         let regions = vec![
-            Region {
-                id: 0,
-                x: 0,
-                y: 0,
-                elevation_m: 0,
-                latitude_deg: 0.0,
-                biome: 0,
-                water: 5_000,
-                soil: 5_000,
-                temperature_tenths_c: 0,
-                precipitation_mm: 0,
-                albedo_milli: 400,
-                freshwater_flux_tenths_mm: 0,
-                ice_mass_kilotons: 0,
-                hazards: Hazards::default(),
-            },
-            Region {
-                id: 1,
-                x: 1,
-                y: 0,
-                elevation_m: 0,
-                latitude_deg: 45.0,
-                biome: 0,
-                water: 5_000,
-                soil: 5_000,
-                temperature_tenths_c: 0,
-                precipitation_mm: 0,
-                albedo_milli: 400,
-                freshwater_flux_tenths_mm: 0,
-                ice_mass_kilotons: 0,
-                hazards: Hazards::default(),
-            },
+            Region::sample(0, 0, 0),
+            Region::sample(1, 1, 0).with_latitude(45.0),
         ];
         let world = World::new(0, 2, 1, regions);
         let mut rng = Stream::from(world.seed, STAGE, 1);
@@ -171,4 +190,149 @@ mod tests {
         assert!(!diff.causes.is_empty());
         assert_eq!(chronicle.len(), 1);
     }
+
+    #[test]
+    fn solar_cycle_advances_each_tick_and_wraps() {
+        let params = AstronomyParams {
+            solar_cycle_steps: 7,
+            ..AstronomyParams::default()
+        };
+        let mut world = World::new(4, 0, 0, Vec::new());
+        let mut indices = Vec::new();
+        for tick in 0..15 {
+            world.tick = tick;
+            let mut rng = Stream::from(world.seed, STAGE, tick + 1);
+            let diff = update_with_params(&world, &mut rng, &params)
+                .expect("astronomy update")
+                .diff;
+            let note = diff
+                .causes
+                .iter()
+                .find(|entry| entry.code == Code::SolarCyclePeak)
+                .and_then(|entry| entry.note.clone())
+                .expect("solar cycle cause");
+            let index: u32 = note
+                .strip_prefix("cycle_index=")
+                .and_then(|value| value.parse().ok())
+                .expect("cycle index note");
+            indices.push(index);
+        }
+
+        for pair in indices.windows(2) {
+            let expected = if pair[0] + 1 == 7 { 0 } else { pair[0] + 1 };
+            assert_eq!(pair[1], expected, "indices {:?}", indices);
+        }
+        assert!(indices.iter().all(|&index| index < 7));
+        assert_eq!(params.solar_cycle_index(7), 0);
+        assert!((params.solar_cycle_amplitude(0) - 1.0).abs() < 1e-12);
+        let peak = (0..7)
+            .map(|tick| params.solar_cycle_amplitude(tick))
+            .fold(f64::MIN, f64::max);
+        assert!(peak <= 1.05 && peak > 1.04);
+    }
+
+    #[test]
+    fn insolation_falloff_matches_atmosphere() {
+        let region =
+            |id: u32, latitude_deg: f64| Region::sample(id, id, 0).with_latitude(latitude_deg);
+        let world = World::new(
+            3,
+            3,
+            1,
+            vec![region(0, 90.0), region(1, 0.0), region(2, 45.0)],
+        );
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let diff = update(&world, &mut rng).expect("astronomy update").diff;
+        let insolation: Vec<f64> = diff.insolation.iter().map(|v| f64::from(v.value)).collect();
+
+        // insolation = scale * (0.35 + 0.65 * falloff * tilt); the pole has zero falloff
+        // and the equator full falloff, which pins down scale and tilt.
+        let scale = insolation[0] / 0.35;
+        let tilt = (insolation[1] / scale - 0.35) / 0.65;
+        let falloff = (insolation[2] / scale - 0.35) / (0.65 * tilt);
+        let expected = seasonality::insolation_factor(45.0);
+        assert!(
+            (falloff - expected).abs() < 1e-3,
+            "astronomy falloff {} differs from atmosphere {}",
+            falloff,
+            expected
+        );
+    }
+
+    #[test]
+    fn only_changed_skips_values_already_committed() {
+        let region =
+            |id: u32, latitude_deg: f64| Region::sample(id, id, 0).with_latitude(latitude_deg);
+        let mut world = World::new(9, 2, 1, vec![region(0, 10.0), region(1, -50.0)]);
+        let params = AstronomyParams {
+            only_changed: true,
+            ..AstronomyParams::default()
+        };
+        let run = |world: &World| {
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update_with_params(world, &mut rng, &params)
+                .expect("astronomy update")
+                .diff
+        };
+
+        let first = run(&world);
+        assert_eq!(first.insolation.len(), 2);
+        assert_eq!(first.tide_envelope.len(), 2);
+        crate::reduce::apply(&mut world, first);
+        world.tick = 0;
+
+        let repeat = run(&world);
+        assert!(repeat.insolation.is_empty());
+        assert!(repeat.tide_envelope.is_empty());
+        assert!(!repeat.causes.is_empty());
+
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let dense = update(&world, &mut rng).expect("astronomy update").diff;
+        assert_eq!(dense.insolation.len(), 2);
+    }
+
+    #[test]
+    fn tides_alternate_spring_and_neap_at_the_lunar_period() {
+        let tide_run = |seed: u64, period: u32| {
+            let mut world = World::new(seed, 1, 1, vec![Region::sample(0, 0, 0)]);
+            world.climate.lunar_period_ticks = period;
+            let mut codes = Vec::new();
+            let mut waves = Vec::new();
+            for tick in 0..(2 * u64::from(period)) {
+                world.tick = tick;
+                let mut rng = Stream::from(world.seed, STAGE, tick + 1);
+                let diff = update(&world, &mut rng).expect("astronomy update").diff;
+                let tide = diff
+                    .causes
+                    .iter()
+                    .find(|cause| cause.target == "region:0/tide")
+                    .expect("tide cause");
+                codes.push(tide.code);
+                waves.push(world.climate.lunar_wave(tick + 1));
+            }
+            (codes, waves)
+        };
+
+        let (codes, waves) = tide_run(3, DEFAULT_LUNAR_PERIOD_TICKS);
+        use Code::{TideNeap as Neap, TideSpring as Spring};
+        assert_eq!(
+            codes,
+            vec![Neap, Neap, Spring, Spring, Neap, Neap, Spring, Spring]
+        );
+        // The same cadence whatever the seed: the phase follows the tick, not the stream.
+        assert_eq!(tide_run(77, DEFAULT_LUNAR_PERIOD_TICKS).0, codes);
+        for pair in waves.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= 1.5, "{:?}", waves);
+        }
+
+        let (slow, slow_waves) = tide_run(3, 8);
+        let springs: Vec<bool> = slow.iter().map(|&code| code == Spring).collect();
+        // Four-tick spring and neap halves; tick 1 sits at the end of a spring half.
+        let expected = [true, false, false, false, false, true, true, true];
+        assert_eq!(springs[..8], expected);
+        assert_eq!(springs[8..], expected);
+        for pair in slow_waves.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= 0.8, "{:?}", slow_waves);
+        }
+    }
 }
@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use crate::cause::{CauseNote, Code, Entry};
+use crate::diff::Diff;
+use crate::fixed::WATER_MAX;
+use crate::world::World;
+
+use super::{bounded_humidity, HUMIDITY_TENTHS_MAX};
+
+/// Stored insolation at which evaporation runs at its full rate.
+const PET_INSOLATION_REFERENCE_TENTHS: f64 = 16_000.0;
+/// Temperature (tenths °C) at which evaporation runs at its full rate; at or below 0 °C
+/// nothing evaporates.
+const PET_TEMPERATURE_REFERENCE_TENTHS: f64 = 300.0;
+
+/// Potential evapotranspiration for each region, in water units, capped by its storage.
+///
+/// The demand scales with last committed insolation and last tick's temperature, both
+/// clamped to their reference values, so `rate` is the loss of a fully lit 30 °C region.
+pub(super) fn potential(world: &World, rate: f64) -> Vec<i32> {
+    if rate <= 0.0 {
+        return vec![0; world.regions.len()];
+    }
+    world
+        .regions
+        .iter()
+        .enumerate()
+        .map(|(index, region)| {
+            let insolation_tenths = world
+                .climate
+                .last_insolation_tenths
+                .get(index)
+                .copied()
+                .unwrap_or(0);
+            let insolation_ratio =
+                (f64::from(insolation_tenths) / PET_INSOLATION_REFERENCE_TENTHS).clamp(0.0, 1.0);
+            let thermal_ratio = (f64::from(region.temperature_tenths_c)
+                / PET_TEMPERATURE_REFERENCE_TENTHS)
+                .clamp(0.0, 1.0);
+            let demand = (rate * insolation_ratio * thermal_ratio).round() as i32;
+            demand.clamp(0, i32::from(region.water))
+        })
+        .collect()
+}
+
+/// Return evaporated water to the air column so it can fall again as precipitation.
+pub(super) fn moisten(humidity: &mut [i32], evaporated: &[i32], strict: bool) -> Result<()> {
+    for (index, (value, &water)) in humidity.iter_mut().zip(evaporated).enumerate() {
+        let gain = water * HUMIDITY_TENTHS_MAX / i32::from(WATER_MAX);
+        *value = bounded_humidity(strict, index, *value + gain)?;
+    }
+    Ok(())
+}
+
+/// Draw the evaporated water from regional storage and record why.
+pub(super) fn commit(world: &World, diff: &mut Diff, evaporated: &[i32]) {
+    for (region, &water) in world.regions.iter().zip(evaporated) {
+        if water <= 0 {
+            continue;
+        }
+        diff.record_water_delta(region.index(), -water);
+        diff.record_cause(Entry::new(
+            format!("region:{}/water", region.id),
+            Code::Evapotranspiration,
+            CauseNote::new().with("loss", water).into_note(),
+        ));
+    }
+}
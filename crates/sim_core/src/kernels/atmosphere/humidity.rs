@@ -1,12 +1,14 @@
+use anyhow::Result;
+
 use crate::fixed::{resource_ratio, WATER_MAX};
 use crate::rng::Stream;
 use crate::world::World;
 
-use super::{HUMIDITY_NOISE_FRACTION, HUMIDITY_TENTHS_MAX, PRECIP_MAX_MM};
+use super::{bounded_humidity, HUMIDITY_NOISE_FRACTION, HUMIDITY_TENTHS_MAX, PRECIP_MAX_MM};
 
 const INSOLATION_REFERENCE_TENTHS: f64 = 16_000.0;
 
-pub(super) fn sample(world: &World, stream: &Stream) -> Vec<i32> {
+pub(super) fn sample(world: &World, stream: &Stream, strict: bool) -> Result<Vec<i32>> {
     let mut humidity = Vec::with_capacity(world.regions.len());
     for (index, region) in world.regions.iter().enumerate() {
         debug_assert_eq!(
@@ -32,9 +34,9 @@ pub(super) fn sample(world: &World, stream: &Stream) -> Vec<i32> {
         let transport_driver =
             0.45 * water_ratio + 0.4 * precip_ratio + 0.15 * (1.0 - insolation_ratio);
         let jitter = region_rng.next_signed_unit() * HUMIDITY_NOISE_FRACTION;
-        let ratio = (transport_driver + jitter).clamp(0.0, 1.0);
+        let ratio = transport_driver + jitter;
         let humidity_tenths = (ratio * f64::from(HUMIDITY_TENTHS_MAX)).round() as i32;
-        humidity.push(humidity_tenths.clamp(0, HUMIDITY_TENTHS_MAX));
+        humidity.push(bounded_humidity(strict, index, humidity_tenths)?);
     }
-    humidity
+    Ok(humidity)
 }
@@ -1,25 +1,33 @@
+mod evaporation;
 mod humidity;
 mod orography;
+mod polar;
 mod precipitation;
 pub(crate) mod seasonality;
+mod thermal;
 
-use anyhow::Result;
+pub use orography::prevailing_wind;
+pub use precipitation::{
+    classify_precip_type, PRECIP_TYPE_MIXED, PRECIP_TYPE_RAIN, PRECIP_TYPE_SNOW,
+};
 
-use crate::diff::Diff;
+use anyhow::{bail, Result};
+
+use crate::diff::{Diff, ScalarValue};
+use crate::fixed::{self, TemperatureRange, PRECIP_SAFETY_MAX_MM};
 use crate::rng::{stream_label, Stream};
 use crate::schedule::KernelRun;
 use crate::world::World;
 
 pub const STAGE: &str = "kernel:atmosphere";
 
-const TEMP_MIN_TENTHS_C: i32 = -500;
-const TEMP_MAX_TENTHS_C: i32 = 500;
 const PRECIP_MIN_MM: i32 = 0;
-const PRECIP_MAX_MM: i32 = 5_000;
-const HADLEY_LATITUDE_MAX: f64 = 30.0;
+/// Reference precipitation for humidity/ratio scaling and the default regime cap.
+const PRECIP_MAX_MM: i32 = fixed::PRECIP_MAX_MM as i32;
+pub(crate) const HADLEY_LATITUDE_MAX: f64 = 30.0;
+pub(crate) const POLAR_REGIME_LATITUDE_MIN: f64 = 60.0;
 const MONSOON_HUMIDITY_THRESHOLD: f64 = 0.6;
 const MONSOON_STRENGTH_THRESHOLD: f64 = 0.25;
-const LAPSE_RATE_C_PER_KM: f64 = 6.5;
 const HUMIDITY_TEMP_BONUS: f64 = 10.0;
 const OROGRAPHIC_LIFT_THRESHOLD_KM: f64 = 0.25;
 const HUMIDITY_TENTHS_MAX: i32 = 1_000;
@@ -27,14 +35,131 @@ const HUMIDITY_NOISE_FRACTION: f64 = 0.03;
 const PRECIP_MULTIPLIER_MIN: f64 = 0.2;
 const PRECIP_MULTIPLIER_MAX: f64 = 3.0;
 const RAIN_SHADOW_MAX: f64 = 0.75;
-const PI: f64 = 3.14159265358979323846264338327950288;
-const TAU: f64 = 6.28318530717958647692528676655900577;
-pub(crate) const SEASON_PERIOD_TICKS: u64 = 4;
+const TAU: f64 = std::f64::consts::TAU;
+/// Ticks per full seasonal cycle.
+pub const SEASON_PERIOD_TICKS: u64 = 4;
 pub(crate) const SEASONAL_INSOLATION_AMPLITUDE: f64 = 0.18;
+/// Exponent on equator closeness (`1 - |lat| / 90`) shared by astronomy's reported
+/// insolation and the atmosphere's temperature/precipitation forcing. Values below 1
+/// flatten the falloff so mid-latitudes keep more sunlight than a linear ramp.
+pub(crate) const LAT_INSOLATION_POWER: f64 = 0.8;
 const HADLEY_DRIFT_MAX_DEGREES: f64 = 5.0;
 const SEASONAL_SCALAR_EPSILON: f64 = 1e-9;
 
+/// Tunable atmosphere thresholds and optional couplings; defaults reproduce the
+/// uncoupled kernel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtmosphereParams {
+    /// Fraction (0..=1) by which the top and bottom grid rows' humidity and temperature
+    /// are pulled toward their shared cap mean each tick. `0.0` disables polar mixing.
+    pub polar_mixing: f64,
+    /// Minimum humidity ratio (0..=1) for a region to count as monsoonal.
+    pub monsoon_humidity_threshold: f64,
+    /// Minimum Hadley strength a region must exceed to count as monsoonal.
+    pub monsoon_strength_threshold: f64,
+    /// Per-regime ceilings on committed precipitation.
+    pub precip_caps: PrecipCaps,
+    /// Baseline precipitation in mm per tick that even the driest region receives, so
+    /// deserts fade out instead of ending at exactly zero. Never exceeds a region's cap;
+    /// `0` keeps hard desert edges.
+    pub arid_floor_mm: i32,
+    /// Fraction (0..=1) of last tick's temperature that sea-level regions and their
+    /// neighbours retain, damping their swings like an ocean's heat capacity. `0.0`
+    /// disables the buffering, as does a non-finite value.
+    pub ocean_inertia: f64,
+    /// Cooling in °C per kilometre of relief applied to land regions lower than all their
+    /// neighbours, where cold air drains and pools. `0.0` disables cold-air pooling.
+    pub cold_air_pooling: f64,
+    /// Potential evapotranspiration in water units per tick for a region under full
+    /// insolation at 30 °C; the evaporated water is added to humidity before
+    /// precipitation is committed. `0.0` disables evaporation.
+    pub evapotranspiration: f64,
+    /// Bounds for committed temperatures; widen them for seeds hotter or colder than
+    /// ±50 °C. The reducer must clamp to the same range (see [`crate::SimParams`]).
+    pub temperature_range: TemperatureRange,
+    /// Record each region's prevailing wind vector in the diff. Off by default so diffs
+    /// only carry wind when a frame will emit it.
+    pub record_wind: bool,
+    /// Fail instead of clamping when a humidity or temperature value leaves its bounds,
+    /// so strict runs see overflows the reducer would never receive (see
+    /// [`crate::SimParams::strict`]).
+    pub strict: bool,
+}
+
+/// Precipitation ceilings in mm per tick by latitude regime; every cap is further bounded
+/// by [`PRECIP_SAFETY_MAX_MM`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrecipCaps {
+    /// `|lat|` below the Hadley cell edge (30°).
+    pub tropical_mm: i32,
+    /// `|lat|` from 30° to 60°.
+    pub midlatitude_mm: i32,
+    /// `|lat|` of 60° and above.
+    pub polar_mm: i32,
+}
+
+impl Default for PrecipCaps {
+    fn default() -> Self {
+        Self {
+            tropical_mm: PRECIP_MAX_MM,
+            midlatitude_mm: PRECIP_MAX_MM,
+            polar_mm: PRECIP_MAX_MM,
+        }
+    }
+}
+
+impl PrecipCaps {
+    /// Effective ceiling for a region at `latitude_deg`.
+    pub fn cap_for(&self, latitude_deg: f64) -> i32 {
+        let lat = latitude_deg.abs();
+        let cap = if lat < HADLEY_LATITUDE_MAX {
+            self.tropical_mm
+        } else if lat < POLAR_REGIME_LATITUDE_MIN {
+            self.midlatitude_mm
+        } else {
+            self.polar_mm
+        };
+        cap.clamp(PRECIP_MIN_MM, i32::from(PRECIP_SAFETY_MAX_MM))
+    }
+
+    /// Largest effective cap across the regimes, used as the reducer's precipitation
+    /// ceiling so no regime's cap is clipped after the atmosphere commits it.
+    pub fn max_mm(&self) -> u16 {
+        [self.tropical_mm, self.midlatitude_mm, self.polar_mm]
+            .into_iter()
+            .map(|cap| cap.clamp(PRECIP_MIN_MM, i32::from(PRECIP_SAFETY_MAX_MM)) as u16)
+            .max()
+            .unwrap_or(fixed::PRECIP_MAX_MM)
+    }
+}
+
+impl Default for AtmosphereParams {
+    fn default() -> Self {
+        Self {
+            polar_mixing: 0.0,
+            monsoon_humidity_threshold: MONSOON_HUMIDITY_THRESHOLD,
+            monsoon_strength_threshold: MONSOON_STRENGTH_THRESHOLD,
+            precip_caps: PrecipCaps::default(),
+            arid_floor_mm: 0,
+            ocean_inertia: 0.0,
+            cold_air_pooling: 0.0,
+            evapotranspiration: 0.0,
+            temperature_range: TemperatureRange::default(),
+            record_wind: false,
+            strict: false,
+        }
+    }
+}
+
 pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &AtmosphereParams::default())
+}
+
+pub fn update_with_params(
+    world: &mut World,
+    rng: &mut Stream,
+    params: &AtmosphereParams,
+) -> Result<KernelRun> {
     if world.regions.is_empty() {
         return Ok(KernelRun::new(Diff::default()));
     }
@@ -45,34 +170,167 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
     let orography_stream = rng.derive(stream_label("CLIMATE.atmo_orography"));
     let commit_stream = rng.derive(stream_label("CLIMATE.atmo_precip_commit"));
 
-    let mut humidity = humidity::sample(world, &moisture_stream);
-    let orography = orography::apply(world, &orography_stream, &mut humidity);
+    let polar_mixing = params.polar_mixing.clamp(0.0, 1.0);
+    let strict = params.strict;
+    let mut humidity = humidity::sample(world, &moisture_stream, strict)?;
+    polar::mix_humidity(world, &mut humidity, polar_mixing, strict)?;
+    let evaporated = evaporation::potential(world, params.evapotranspiration);
+    evaporation::moisten(&mut humidity, &evaporated, strict)?;
+    let orography = orography::apply(world, &orography_stream, &mut humidity, strict)?;
     let precipitation = precipitation::commit(
         world,
         humidity.as_slice(),
         &seasonal,
         &orography,
         &commit_stream,
-    );
+        params,
+    )?;
+
+    let mut diff = precipitation.diff;
+    evaporation::commit(world, &mut diff, &evaporated);
+    polar::mix_temperature(
+        world,
+        &mut diff,
+        polar_mixing,
+        params.temperature_range,
+        strict,
+    )?;
+    thermal::buffer_temperature(
+        world,
+        &mut diff,
+        if params.ocean_inertia.is_finite() {
+            params.ocean_inertia.clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+        params.temperature_range,
+        strict,
+    )?;
+    thermal::pool_cold_air(
+        world,
+        &mut diff,
+        params.cold_air_pooling,
+        params.temperature_range,
+        strict,
+    )?;
+    for (index, region) in world.regions.iter().enumerate() {
+        if params.record_wind {
+            let (dx, dy) = prevailing_wind(region.latitude_deg);
+            diff.record_wind(index, dx, dy);
+        }
+        let precip_mm = committed_or(
+            &diff.precipitation,
+            index,
+            i32::from(region.precipitation_mm),
+        );
+        if precip_mm > 0 {
+            let temperature_tenths = committed_or(
+                &diff.temperature,
+                index,
+                i32::from(region.temperature_tenths_c),
+            );
+            diff.record_precip_type(index, classify_precip_type(temperature_tenths));
+        }
+    }
 
     Ok(KernelRun {
-        diff: precipitation.diff,
+        diff,
         chronicle: precipitation.chronicle,
         highlights: Vec::new(),
     })
 }
 
+/// Clamp `value` for `field` of region `index` into `low..=high`.
+///
+/// Under `strict` a value that needs clamping is an error naming the region and field,
+/// worded like [`crate::reduce::apply_strict`]'s, since the reducer only ever sees the
+/// clamped value.
+fn bounded(
+    strict: bool,
+    field: &str,
+    index: usize,
+    value: i32,
+    low: i32,
+    high: i32,
+) -> Result<i32> {
+    let clamped = value.max(low).min(high);
+    if strict && clamped != value {
+        bail!(
+            "strict mode: region {} {} value {} is out of range",
+            index,
+            field,
+            value
+        );
+    }
+    Ok(clamped)
+}
+
+/// [`bounded`] for humidity in tenths of a percent.
+fn bounded_humidity(strict: bool, index: usize, value: i32) -> Result<i32> {
+    bounded(strict, "humidity", index, value, 0, HUMIDITY_TENTHS_MAX)
+}
+
+/// [`bounded`] for a temperature in tenths of °C against `range`.
+fn bounded_temperature(
+    strict: bool,
+    index: usize,
+    value: i32,
+    range: TemperatureRange,
+) -> Result<i32> {
+    bounded(
+        strict,
+        "temperature",
+        index,
+        value,
+        i32::from(range.min_tenths),
+        i32::from(range.max_tenths),
+    )
+}
+
+/// This tick's recorded value for `index`, or the region's current state when unchanged.
+fn committed_or(values: &[ScalarValue], index: usize, current: i32) -> i32 {
+    values
+        .binary_search_by_key(&(index as u32), |value| value.region)
+        .map(|position| values[position].value)
+        .unwrap_or(current)
+}
+
 #[cfg(test)]
 mod tests {
     use super::seasonality;
     use super::*;
     use crate::cause::Code;
     use crate::fixed::WATER_MAX;
-    use crate::io::frame::make_frame;
+    use crate::io::frame::{make_frame, make_frame_with_options, FrameOptions};
     use crate::rng::Stream;
-    use crate::world::{Hazards, Region, World};
+    use crate::world::{Hazards, Region, World, WrapMode};
     use proptest::prelude::*;
 
+    #[test]
+    fn extreme_window_length_changes_when_precip_extremes_fire() {
+        use precipitation::{rolling_spread, PRECIP_EXTREME_THRESHOLD_MM};
+        use std::collections::VecDeque;
+
+        // A single wet spike at tick 5 in an otherwise steady series.
+        let series: Vec<u16> = (1..=20)
+            .map(|tick| if tick == 5 { 900 } else { 300 })
+            .collect();
+        let firing_ticks = |len: usize| -> Vec<u64> {
+            let mut window = VecDeque::from(vec![0u16; len]);
+            let mut ticks = Vec::new();
+            for (tick, &precip) in (1u64..).zip(&series) {
+                let spread = rolling_spread(&mut window, precip, len, tick);
+                if spread.is_some_and(|spread| spread >= PRECIP_EXTREME_THRESHOLD_MM) {
+                    ticks.push(tick);
+                }
+            }
+            ticks
+        };
+
+        assert_eq!(firing_ticks(3), vec![5, 6, 7]);
+        assert_eq!(firing_ticks(12), vec![12, 13, 14, 15, 16]);
+    }
+
     #[test]
     fn atmosphere_records_energy_balance_and_causes() {
         let regions = vec![
@@ -419,6 +677,182 @@ mod tests {
         }
     }
 
+    #[test]
+    fn precip_type_reports_snow_when_frozen_and_rain_when_warm() {
+        let regions = [(80.0, 3_500, 1_000), (5.0, 0, 9_500)]
+            .into_iter()
+            .enumerate()
+            .map(|(index, (latitude_deg, elevation_m, water))| Region {
+                id: index as u32,
+                x: index as u32,
+                y: 0,
+                elevation_m,
+                latitude_deg,
+                biome: 0,
+                water,
+                soil: 6_000,
+                temperature_tenths_c: 0,
+                precipitation_mm: 0,
+                albedo_milli: 360,
+                freshwater_flux_tenths_mm: 0,
+                ice_mass_kilotons: 0,
+                hazards: Hazards::default(),
+            })
+            .collect();
+        let mut world = World::new(29, 2, 1, regions);
+        let mut rng = Stream::from(world.seed, "CLIMATE::atmosphere_substep", world.tick);
+        let diff = update(&mut world, &mut rng)
+            .expect("atmosphere update succeeds")
+            .diff;
+        let frame = make_frame(
+            world.tick,
+            diff.clone(),
+            Vec::new(),
+            Vec::new(),
+            false,
+            world.width,
+            world.height,
+        );
+
+        let temperature = |index: u32| {
+            diff.temperature
+                .iter()
+                .find(|value| value.region == index)
+                .map(|value| value.value)
+                .expect("temperature committed")
+        };
+        assert!(temperature(0) < 0, "polar highland is frozen");
+        assert!(temperature(1) > 20, "equatorial lowland is warm");
+        assert_eq!(frame.diff.precip_type.get("r:0"), Some(&PRECIP_TYPE_SNOW));
+        assert_eq!(frame.diff.precip_type.get("r:1"), Some(&PRECIP_TYPE_RAIN));
+        assert_eq!(classify_precip_type(15), PRECIP_TYPE_MIXED);
+    }
+
+    #[test]
+    fn cold_poleward_neighbor_adds_frontal_precipitation() {
+        let column = |poleward_temp: i16| {
+            let regions = [(60.0, poleward_temp), (45.0, 150)]
+                .into_iter()
+                .enumerate()
+                .map(|(index, (latitude_deg, temperature_tenths_c))| Region {
+                    id: index as u32,
+                    x: 0,
+                    y: index as u32,
+                    elevation_m: 0,
+                    latitude_deg,
+                    biome: 0,
+                    water: 7_000,
+                    soil: 6_000,
+                    temperature_tenths_c,
+                    precipitation_mm: 0,
+                    albedo_milli: 360,
+                    freshwater_flux_tenths_mm: 0,
+                    ice_mass_kilotons: 0,
+                    hazards: Hazards::default(),
+                })
+                .collect();
+            World::new(61, 1, 2, regions)
+        };
+        let midlatitude_precip = |mut world: World| {
+            let mut rng = Stream::from(world.seed, "CLIMATE::atmosphere_substep", world.tick);
+            let diff = update(&mut world, &mut rng)
+                .expect("atmosphere update succeeds")
+                .diff;
+            let precip = diff
+                .precipitation
+                .iter()
+                .find(|value| value.region == 1)
+                .map(|value| value.value)
+                .expect("precipitation committed");
+            let frontal = diff.causes.iter().any(|entry| {
+                entry.code == Code::FrontalPrecip && entry.target == "region:1/precip"
+            });
+            (precip, frontal)
+        };
+
+        let (fronted, fronted_cause) = midlatitude_precip(column(-200));
+        let (isolated, isolated_cause) = midlatitude_precip(column(150));
+        assert!(
+            fronted_cause,
+            "cold neighbour should trigger frontal_precip"
+        );
+        assert!(!isolated_cause, "no contrast means no front");
+        assert!(
+            fronted > isolated,
+            "frontal precip {} should exceed {}",
+            fronted,
+            isolated
+        );
+    }
+
+    #[test]
+    fn wind_vectors_follow_prevailing_belts_when_requested() {
+        let regions = [12.0, 45.0]
+            .into_iter()
+            .enumerate()
+            .map(|(index, latitude_deg)| Region {
+                id: index as u32,
+                x: index as u32,
+                y: 0,
+                elevation_m: 100,
+                latitude_deg,
+                biome: 0,
+                water: 8_000,
+                soil: 6_000,
+                temperature_tenths_c: 0,
+                precipitation_mm: 0,
+                albedo_milli: 360,
+                freshwater_flux_tenths_mm: 0,
+                ice_mass_kilotons: 0,
+                hazards: Hazards::default(),
+            })
+            .collect();
+        let mut world = World::new(53, 2, 1, regions);
+        let mut rng = Stream::from(world.seed, "CLIMATE::atmosphere_substep", world.tick);
+        let unrequested = update(&mut world.clone(), &mut rng.clone())
+            .expect("atmosphere update succeeds")
+            .diff;
+        assert!(unrequested.wind.is_empty(), "wind not recorded by default");
+
+        let params = AtmosphereParams {
+            record_wind: true,
+            ..AtmosphereParams::default()
+        };
+        let diff = update_with_params(&mut world, &mut rng, &params)
+            .expect("atmosphere update succeeds")
+            .diff;
+
+        let default_frame = make_frame(
+            world.tick,
+            diff.clone(),
+            Vec::new(),
+            Vec::new(),
+            false,
+            world.width,
+            world.height,
+        );
+        assert!(
+            default_frame.diff.wind.is_empty(),
+            "wind omitted by default"
+        );
+
+        let frame = make_frame_with_options(
+            world.tick,
+            diff,
+            Vec::new(),
+            Vec::new(),
+            false,
+            world.width,
+            world.height,
+            FrameOptions {
+                include_wind: true,
+                ..FrameOptions::default()
+            },
+        );
+        assert_eq!(frame.diff.wind.get("r:0"), Some(&[-1, 0]));
+        assert_eq!(frame.diff.wind.get("r:1"), Some(&[1, 0]));
+    }
+
     #[test]
     fn temperature_and_precip_within_bounds() {
         let regions = vec![
@@ -480,7 +914,7 @@ mod tests {
 
         for value in &diff.temperature {
             assert!(
-                (TEMP_MIN_TENTHS_C..=TEMP_MAX_TENTHS_C).contains(&value.value),
+                TemperatureRange::default().contains(value.value),
                 "temperature {} out of bounds",
                 value.value
             );
@@ -502,4 +936,441 @@ mod tests {
             assert!((0..=super::precipitation::HEAT_EXTREME_CLAMP).contains(&value.value));
         }
     }
+
+    fn polar_world() -> World {
+        let elevation = [0, 0, 400, 400, 2_400, 2_400];
+        World::from_fields(
+            61,
+            2,
+            3,
+            &elevation,
+            &[0; 6],
+            crate::world::latitude_from_row,
+        )
+        .expect("fields match grid")
+    }
+
+    fn polar_gaps(params: &AtmosphereParams, ticks: u64) -> Vec<i32> {
+        let mut world = polar_world();
+        let mut gaps = Vec::new();
+        for tick in 1..=ticks {
+            let mut rng = Stream::from(world.seed, STAGE, tick);
+            let run = update_with_params(&mut world, &mut rng, params)
+                .expect("atmosphere update succeeds");
+            crate::reduce::apply(&mut world, run.diff);
+            world.tick = tick;
+            let north = i32::from(world.regions[0].temperature_tenths_c);
+            let south = i32::from(world.regions[4].temperature_tenths_c);
+            gaps.push((north - south).abs());
+        }
+        gaps
+    }
+
+    #[test]
+    fn precipitation_terms_draw_from_independent_streams() {
+        use super::precipitation::jitter_sample;
+
+        let commit = Stream::from(8, STAGE, 3).derive(stream_label("CLIMATE.atmo_precip_commit"));
+        let before = jitter_sample(&commit, 2);
+        // The jitter is still the region stream's first draw, as before terms were labelled.
+        assert_eq!(before, commit.derive(2).next_f64());
+
+        // A new labelled term drawing first, even heavily, leaves the jitter untouched.
+        let mut future = commit.derive(2).derive(stream_label("precip.future_term"));
+        for _ in 0..16 {
+            future.next_f64();
+        }
+        assert_eq!(jitter_sample(&commit, 2), before);
+        assert_ne!(
+            commit
+                .derive(2)
+                .derive(stream_label("precip.future_term"))
+                .next_f64(),
+            before
+        );
+        assert_ne!(jitter_sample(&commit, 3), before);
+    }
+
+    #[test]
+    fn ocean_inertia_damps_maritime_seasonal_swing() {
+        // One row at 45°: open water at x=0, its coast at x=1, inland from x=2.
+        let elevation = [0, 100, 100, 100, 100];
+        let mut world = World::from_fields(17, 5, 1, &elevation, &[0; 5], |_, _| 45.0)
+            .expect("fields match grid");
+        let params = AtmosphereParams {
+            ocean_inertia: 0.6,
+            ..AtmosphereParams::default()
+        };
+        let mut history: Vec<Vec<i32>> = vec![Vec::new(); 5];
+        for tick in 1..=4 * SEASON_PERIOD_TICKS {
+            let mut rng = Stream::from(world.seed, STAGE, tick);
+            let run = update_with_params(&mut world, &mut rng, &params)
+                .expect("atmosphere update succeeds");
+            crate::reduce::apply(&mut world, run.diff);
+            world.tick = tick;
+            // Skip the first two seasons while the buffered cells warm up from 0 °C.
+            if tick > 2 * SEASON_PERIOD_TICKS {
+                for (index, region) in world.regions.iter().enumerate() {
+                    history[index].push(i32::from(region.temperature_tenths_c));
+                }
+            }
+        }
+        let amplitude = |index: usize| {
+            let values = &history[index];
+            values.iter().max().unwrap() - values.iter().min().unwrap()
+        };
+
+        assert!(amplitude(4) > 0, "inland history {:?}", history[4]);
+        assert!(
+            amplitude(0) < amplitude(4),
+            "ocean {:?} vs inland {:?}",
+            history[0],
+            history[4]
+        );
+        assert!(amplitude(1) < amplitude(4));
+
+        // A NaN inertia would otherwise pin every buffered temperature forever.
+        let run_with = |ocean_inertia: f64| {
+            let mut world = World::from_fields(17, 5, 1, &elevation, &[0; 5], |_, _| 45.0)
+                .expect("fields match grid");
+            let params = AtmosphereParams {
+                ocean_inertia,
+                ..AtmosphereParams::default()
+            };
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update_with_params(&mut world, &mut rng, &params)
+                .expect("atmosphere update succeeds")
+                .diff
+                .temperature
+        };
+        assert_eq!(run_with(f64::NAN), run_with(0.0));
+    }
+
+    #[test]
+    fn cold_air_pools_in_a_basin_below_all_four_neighbours() {
+        // 3x3 grids at 45°; the basin's centre sits 1 km below its rim.
+        let basin = [1_500, 1_500, 1_500, 1_500, 500, 1_500, 1_500, 1_500, 1_500];
+        let flat = [500; 9];
+        let params = AtmosphereParams {
+            cold_air_pooling: 6.0,
+            ..AtmosphereParams::default()
+        };
+        let run = |elevation: &[i32], params: &AtmosphereParams| {
+            let mut world = World::from_fields(23, 3, 3, elevation, &[0; 9], |_, _| 45.0)
+                .expect("fields match grid");
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update_with_params(&mut world, &mut rng, params)
+                .expect("atmosphere update succeeds")
+                .diff
+        };
+        let centre = |diff: &Diff| committed_or(&diff.temperature, 4, 0);
+
+        let pooled = run(&basin, &params);
+        let unpooled = run(&basin, &AtmosphereParams::default());
+        let open = run(&flat, &params);
+        assert!(
+            centre(&pooled) < centre(&open),
+            "basin {} vs flat {}",
+            centre(&pooled),
+            centre(&open)
+        );
+        assert_eq!(centre(&pooled), centre(&unpooled) - 60);
+        let pooling: Vec<&str> = pooled
+            .causes
+            .iter()
+            .filter(|cause| cause.code == Code::ColdAirPooling)
+            .map(|cause| cause.target.as_str())
+            .collect();
+        assert_eq!(pooling, vec!["region:4/temperature"]);
+        assert!(open
+            .causes
+            .iter()
+            .all(|cause| cause.code != Code::ColdAirPooling));
+    }
+
+    #[test]
+    fn orographic_lift_reads_across_the_seam_on_cylinder_grids() {
+        // A 3x1 row at 45°, where the westerlies put region 2 upwind of region 0 once the
+        // edges join.
+        let lifted = |wrap: WrapMode| {
+            let mut world = World::from_fields(29, 3, 1, &[2_000, 0, 0], &[0; 3], |_, _| 45.0)
+                .expect("fields match grid");
+            world.wrap = wrap;
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update(&mut world, &mut rng)
+                .expect("atmosphere update succeeds")
+                .diff
+                .causes
+                .iter()
+                .filter(|cause| cause.code == Code::OrographicLift)
+                .map(|cause| cause.target.clone())
+                .collect::<Vec<_>>()
+        };
+        assert!(lifted(WrapMode::Plane).is_empty());
+        assert_eq!(
+            lifted(WrapMode::Cylinder),
+            vec!["region:0/precip".to_string()]
+        );
+    }
+
+    #[test]
+    fn hot_sunlit_regions_evaporate_more_than_cold_ones() {
+        let regions = vec![
+            Region::sample(0, 0, 0)
+                .with_latitude(5.0)
+                .with_temperature(300),
+            Region::sample(1, 1, 0)
+                .with_latitude(5.0)
+                .with_temperature(20),
+        ];
+        let mut world = World::new(23, 2, 1, regions);
+        world.climate.last_insolation_tenths = vec![16_000, 4_000];
+        let params = AtmosphereParams {
+            evapotranspiration: 200.0,
+            ..AtmosphereParams::default()
+        };
+
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let run =
+            update_with_params(&mut world, &mut rng, &params).expect("atmosphere update succeeds");
+        let loss = |index: u32| {
+            run.diff
+                .water
+                .iter()
+                .find(|delta| delta.region == index)
+                .map_or(0, |delta| -delta.delta)
+        };
+        assert_eq!(loss(0), 200);
+        assert!(loss(0) > loss(1), "hot {} vs cold {}", loss(0), loss(1));
+        assert!(run.diff.causes.iter().any(
+            |entry| entry.code == Code::Evapotranspiration && entry.target == "region:0/water"
+        ));
+
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let off = update(&mut world, &mut rng).expect("atmosphere update succeeds");
+        assert!(off.diff.water.is_empty());
+        assert!(off
+            .diff
+            .causes
+            .iter()
+            .all(|entry| entry.code != Code::Evapotranspiration));
+    }
+
+    #[test]
+    fn strict_mode_rejects_humidity_the_kernel_would_clamp() {
+        let regions = vec![Region::sample(0, 0, 0)
+            .with_latitude(5.0)
+            .with_temperature(300)
+            .with_water(WATER_MAX)];
+        let mut world = World::new(29, 1, 1, regions);
+        world.climate.last_insolation_tenths = vec![16_000];
+        let params = AtmosphereParams {
+            evapotranspiration: 8_000.0,
+            ..AtmosphereParams::default()
+        };
+
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let lenient =
+            update_with_params(&mut world, &mut rng, &params).expect("lenient update clamps");
+        assert_eq!(lenient.diff.humidity[0].value, HUMIDITY_TENTHS_MAX);
+
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let strict = AtmosphereParams {
+            strict: true,
+            ..params
+        };
+        let err = update_with_params(&mut world, &mut rng, &strict).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("region 0 humidity"), "{}", message);
+    }
+
+    #[test]
+    fn polar_mixing_converges_caps_only_when_enabled() {
+        let independent = polar_gaps(&AtmosphereParams::default(), 4);
+        let mixed = polar_gaps(
+            &AtmosphereParams {
+                polar_mixing: 0.5,
+                ..AtmosphereParams::default()
+            },
+            4,
+        );
+
+        assert!(
+            independent.iter().all(|gap| *gap > 20),
+            "gaps {:?}",
+            independent
+        );
+        for (mixed_gap, independent_gap) in mixed.iter().zip(&independent) {
+            assert!(
+                *mixed_gap * 2 <= *independent_gap + 2,
+                "mixed gap {} vs independent {}",
+                mixed_gap,
+                independent_gap
+            );
+        }
+
+        let mut world = polar_world();
+        let mut default_world = polar_world();
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let mut default_rng = Stream::from(world.seed, STAGE, 1);
+        let off = update_with_params(&mut world, &mut rng, &AtmosphereParams::default())
+            .expect("atmosphere update succeeds");
+        let plain =
+            update(&mut default_world, &mut default_rng).expect("atmosphere update succeeds");
+        assert_eq!(off.diff.temperature, plain.diff.temperature);
+        assert_eq!(off.diff.humidity, plain.diff.humidity);
+    }
+
+    #[test]
+    fn arid_floor_lifts_the_driest_regions_only_when_set() {
+        const FLOOR_MM: i32 = 800;
+        let latitudes = [0.0, 25.0, 50.0, 75.0, -85.0];
+        let dry_world = || {
+            let regions = latitudes
+                .iter()
+                .enumerate()
+                .map(|(index, &latitude)| {
+                    Region::sample(index as u32, index as u32, 0)
+                        .with_latitude(latitude)
+                        .with_elevation(1_500)
+                        .with_water(0)
+                })
+                .collect();
+            World::new(41, latitudes.len() as u32, 1, regions)
+        };
+        let run = |params: &AtmosphereParams| {
+            let mut world = dry_world();
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update_with_params(&mut world, &mut rng, params)
+                .expect("atmosphere update succeeds")
+                .diff
+        };
+
+        let plain = run(&AtmosphereParams::default());
+        let floored = run(&AtmosphereParams {
+            arid_floor_mm: FLOOR_MM,
+            ..AtmosphereParams::default()
+        });
+        let precip = |diff: &Diff, index: usize| committed_or(&diff.precipitation, index, 0);
+        assert!(
+            (0..latitudes.len()).any(|index| precip(&plain, index) < FLOOR_MM),
+            "dry world already wetter than the floor: {:?}",
+            plain.precipitation
+        );
+        for index in 0..latitudes.len() {
+            assert!(
+                precip(&floored, index) >= FLOOR_MM,
+                "{:?}",
+                floored.precipitation
+            );
+            assert_eq!(
+                precip(&floored, index),
+                precip(&plain, index).max(FLOOR_MM),
+                "region {}",
+                index
+            );
+        }
+
+        let zero_floor = run(&AtmosphereParams {
+            arid_floor_mm: 0,
+            ..AtmosphereParams::default()
+        });
+        assert_eq!(zero_floor.precipitation, plain.precipitation);
+        assert_eq!(zero_floor.temperature, plain.temperature);
+        assert_eq!(zero_floor.causes, plain.causes);
+    }
+
+    #[test]
+    fn regime_caps_let_tropics_exceed_temperate_cap_within_safety_cap() {
+        let params = AtmosphereParams {
+            precip_caps: PrecipCaps {
+                tropical_mm: 50_000,
+                midlatitude_mm: 300,
+                polar_mm: 300,
+            },
+            ..AtmosphereParams::default()
+        };
+        assert_eq!(
+            params.precip_caps.cap_for(-12.0),
+            i32::from(PRECIP_SAFETY_MAX_MM)
+        );
+        assert_eq!(params.precip_caps.cap_for(45.0), 300);
+        assert_eq!(PrecipCaps::default().cap_for(5.0), PRECIP_MAX_MM);
+
+        let mut world = World::from_fields(29, 2, 1, &[50, 50], &[0, 0], |_, _| 0.0)
+            .expect("fields match grid");
+        world.regions[1].latitude_deg = 45.0;
+        for region in &mut world.regions {
+            region.water = 9_000;
+        }
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let run =
+            update_with_params(&mut world, &mut rng, &params).expect("atmosphere update succeeds");
+        let precip = |index: usize| committed_or(&run.diff.precipitation, index, 0);
+        assert!(precip(0) > 300, "tropical precip {}", precip(0));
+        assert!(precip(1) <= 300, "midlatitude precip {}", precip(1));
+        for value in &run.diff.precipitation {
+            assert!(value.value <= i32::from(PRECIP_SAFETY_MAX_MM));
+        }
+
+        let mut runaway = Diff::default();
+        runaway.record_precipitation(0, 60_000);
+        let counts = crate::reduce::apply_counting(&mut world, runaway.clone());
+        assert_eq!(counts.precipitation, 1);
+        assert_eq!(world.regions[0].precipitation_mm, fixed::PRECIP_MAX_MM);
+
+        assert_eq!(params.precip_caps.max_mm(), PRECIP_SAFETY_MAX_MM);
+        let limits = crate::reduce::ReduceLimits {
+            precip_max_mm: params.precip_caps.max_mm(),
+            ..crate::reduce::ReduceLimits::default()
+        };
+        crate::reduce::apply_in_range(&mut world, runaway, limits);
+        assert_eq!(world.regions[0].precipitation_mm, PRECIP_SAFETY_MAX_MM);
+    }
+
+    #[test]
+    fn monsoon_onset_and_retreat_fire_once_per_season() {
+        let params = AtmosphereParams {
+            monsoon_humidity_threshold: 0.0,
+            ..AtmosphereParams::default()
+        };
+        let mut world =
+            World::from_fields(73, 1, 1, &[50], &[0], |_, _| 20.0).expect("fields match grid");
+        world.regions[0].water = 8_000;
+
+        let seasons = 3;
+        let mut onsets = Vec::new();
+        let mut retreats = Vec::new();
+        for tick in 1..=SEASON_PERIOD_TICKS * (seasons + 1) {
+            let mut rng = Stream::from(world.seed, STAGE, tick);
+            let run = update_with_params(&mut world, &mut rng, &params)
+                .expect("atmosphere update succeeds");
+            for entry in &run.diff.causes {
+                match entry.code {
+                    Code::MonsoonOnset => onsets.push(tick),
+                    Code::MonsoonRetreat => retreats.push(tick),
+                    _ => {}
+                }
+            }
+            crate::reduce::apply(&mut world, run.diff);
+            world.tick = tick;
+        }
+
+        // Seasons are counted from the first retreat so the opening partial season,
+        // which starts from an inactive state, is excluded.
+        let first_retreat = *retreats.first().expect("monsoon retreats at least once");
+        for season in 0..seasons {
+            let start = first_retreat + 1 + season * SEASON_PERIOD_TICKS;
+            let window = start..start + SEASON_PERIOD_TICKS;
+            let count = |ticks: &[u64]| ticks.iter().filter(|t| window.contains(t)).count();
+            assert_eq!(count(&onsets), 1, "season {} onsets {:?}", season, onsets);
+            assert_eq!(
+                count(&retreats),
+                1,
+                "season {} retreats {:?}",
+                season,
+                retreats
+            );
+        }
+    }
 }
@@ -1,8 +1,10 @@
+use anyhow::Result;
+
 use crate::rng::Stream;
 use crate::world::World;
 
 use super::{
-    HUMIDITY_TENTHS_MAX, OROGRAPHIC_LIFT_THRESHOLD_KM, PRECIP_MULTIPLIER_MAX,
+    bounded_humidity, HUMIDITY_TENTHS_MAX, OROGRAPHIC_LIFT_THRESHOLD_KM, PRECIP_MULTIPLIER_MAX,
     PRECIP_MULTIPLIER_MIN, RAIN_SHADOW_MAX,
 };
 
@@ -18,7 +20,8 @@ pub(super) fn apply(
     world: &World,
     stream: &Stream,
     humidity_tenths: &mut [i32],
-) -> OrographyEffects {
+    strict: bool,
+) -> Result<OrographyEffects> {
     let total_regions = world.regions.len();
     let mut precip_multipliers = vec![1.0f64; total_regions];
     let mut lift_gradients = vec![0.0f64; total_regions];
@@ -36,34 +39,31 @@ pub(super) fn apply(
         let lift_jitter = effect_rng.next_f64();
         let shadow_jitter = effect_rng.next_f64();
 
-        let upwind_x = region.x as i32 - wind_dx;
-        let upwind_y = region.y as i32 - wind_dy;
-        if let Some(upwind_index) = region_index_at(world, upwind_x, upwind_y) {
+        // Wind lookups share the grid's edge rules, so cylinder worlds wrap east-west.
+        if let Some(upwind_index) = world.neighbor_at(index, -wind_dx, -wind_dy) {
             let upwind = &world.regions[upwind_index];
             let gradient_km = f64::from(region.elevation_m - upwind.elevation_m) / 1_000.0;
             if gradient_km >= OROGRAPHIC_LIFT_THRESHOLD_KM {
                 let random_factor = 0.85 + lift_jitter * 0.3;
                 let lift = gradient_km * 0.25 * random_factor;
-                let mut humidity_ratio = f64::from(humidity_tenths[index]) / tenths_max;
-                humidity_ratio = (humidity_ratio + lift).clamp(0.0, 1.0);
+                let humidity_ratio = f64::from(humidity_tenths[index]) / tenths_max + lift;
                 humidity_tenths[index] =
-                    ((humidity_ratio * tenths_max).round() as i32).clamp(0, HUMIDITY_TENTHS_MAX);
+                    bounded_humidity(strict, index, (humidity_ratio * tenths_max).round() as i32)?;
                 let multiplier = (1.0 + lift * 0.8).clamp(1.0, PRECIP_MULTIPLIER_MAX);
                 precip_multipliers[index] *= multiplier;
                 lift_gradients[index] = gradient_km;
                 lift_multipliers[index] = precip_multipliers[index];
 
-                let downwind_x = region.x as i32 + wind_dx;
-                let downwind_y = region.y as i32 + wind_dy;
-                if let Some(downwind_index) = region_index_at(world, downwind_x, downwind_y) {
+                if let Some(downwind_index) = world.neighbor_at(index, wind_dx, wind_dy) {
                     let dryness_base = gradient_km * (0.18 + shadow_jitter * 0.12);
                     let dryness = dryness_base.clamp(0.0, RAIN_SHADOW_MAX);
-                    let mut downwind_ratio =
-                        f64::from(humidity_tenths[downwind_index]) / tenths_max;
-                    downwind_ratio = (downwind_ratio * (1.0 - dryness)).clamp(0.0, 1.0);
-                    humidity_tenths[downwind_index] = ((downwind_ratio * tenths_max).round()
-                        as i32)
-                        .clamp(0, HUMIDITY_TENTHS_MAX);
+                    let downwind_ratio =
+                        f64::from(humidity_tenths[downwind_index]) / tenths_max * (1.0 - dryness);
+                    humidity_tenths[downwind_index] = bounded_humidity(
+                        strict,
+                        downwind_index,
+                        (downwind_ratio * tenths_max).round() as i32,
+                    )?;
                     let rain_multiplier = (1.0 - dryness * 0.65).clamp(PRECIP_MULTIPLIER_MIN, 1.0);
                     precip_multipliers[downwind_index] *= rain_multiplier;
                     rain_shadow_factors[downwind_index] =
@@ -73,15 +73,15 @@ pub(super) fn apply(
         }
     }
 
-    OrographyEffects {
+    Ok(OrographyEffects {
         precip_multipliers,
         lift_gradients,
         lift_multipliers,
         rain_shadow_factors,
-    }
+    })
 }
 
-fn prevailing_wind(latitude_deg: f64) -> (i32, i32) {
+pub fn prevailing_wind(latitude_deg: f64) -> (i32, i32) {
     let abs_lat = latitude_deg.abs();
     if abs_lat < 30.0 {
         (-1, 0)
@@ -92,7 +92,7 @@ fn prevailing_wind(latitude_deg: f64) -> (i32, i32) {
     }
 }
 
-fn region_index_at(world: &World, x: i32, y: i32) -> Option<usize> {
+pub(super) fn region_index_at(world: &World, x: i32, y: i32) -> Option<usize> {
     if x < 0 || y < 0 {
         return None;
     }
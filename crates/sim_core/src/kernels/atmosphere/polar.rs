@@ -0,0 +1,85 @@
+use anyhow::Result;
+
+use crate::diff::Diff;
+use crate::fixed::TemperatureRange;
+use crate::world::World;
+
+use super::{bounded_humidity, bounded_temperature};
+
+/// Indices of the topmost and bottommost grid rows, in region order.
+fn polar_cap_indices(world: &World) -> Vec<usize> {
+    if world.height < 2 {
+        return Vec::new();
+    }
+    let bottom = world.height - 1;
+    world
+        .regions
+        .iter()
+        .enumerate()
+        .filter(|(_, region)| region.y == 0 || region.y == bottom)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Move each value a `strength` fraction toward the shared cap mean.
+///
+/// Sums run in index order over integers so the mean is deterministic.
+fn mix_toward_cap_mean(values: &mut [(usize, i32)], strength: f64) {
+    if values.is_empty() || strength <= 0.0 {
+        return;
+    }
+    let sum: i64 = values.iter().map(|(_, value)| i64::from(*value)).sum();
+    let mean = sum as f64 / values.len() as f64;
+    for (_, value) in values.iter_mut() {
+        let pull = (strength * (mean - f64::from(*value))).round() as i32;
+        *value += pull;
+    }
+}
+
+pub(super) fn mix_humidity(
+    world: &World,
+    humidity_tenths: &mut [i32],
+    strength: f64,
+    strict: bool,
+) -> Result<()> {
+    let mut values: Vec<(usize, i32)> = polar_cap_indices(world)
+        .into_iter()
+        .map(|index| (index, humidity_tenths[index]))
+        .collect();
+    mix_toward_cap_mean(&mut values, strength);
+    for (index, value) in values {
+        humidity_tenths[index] = bounded_humidity(strict, index, value)?;
+    }
+    Ok(())
+}
+
+/// Mix this tick's committed polar temperatures, falling back to current state where
+/// the commit left a region unchanged.
+pub(super) fn mix_temperature(
+    world: &World,
+    diff: &mut Diff,
+    strength: f64,
+    range: TemperatureRange,
+    strict: bool,
+) -> Result<()> {
+    let mut values: Vec<(usize, i32)> = Vec::new();
+    let mut committed = Vec::new();
+    for index in polar_cap_indices(world) {
+        let recorded = diff
+            .temperature
+            .binary_search_by_key(&(index as u32), |value| value.region)
+            .ok()
+            .map(|position| diff.temperature[position].value);
+        committed.push(recorded.is_some());
+        let current = i32::from(world.regions[index].temperature_tenths_c);
+        values.push((index, recorded.unwrap_or(current)));
+    }
+    mix_toward_cap_mean(&mut values, strength);
+    for ((index, value), recorded) in values.into_iter().zip(committed) {
+        let mixed = bounded_temperature(strict, index, value, range)?;
+        if recorded || mixed != i32::from(world.regions[index].temperature_tenths_c) {
+            diff.record_temperature(index, mixed);
+        }
+    }
+    Ok(())
+}
@@ -1,19 +1,78 @@
-use crate::cause::{Code, Entry};
+use anyhow::Result;
+
+use crate::cause::{CauseNote, Code, Entry};
 use crate::diff::Diff;
 use crate::rng::Stream;
-use crate::world::{World, EXTREME_WINDOW};
+use std::collections::VecDeque;
+
+use crate::world::{pressure_altitude_km, pressure_cooling_c, World};
 
 use super::{
-    orography::OrographyEffects,
+    bounded_humidity, bounded_temperature,
+    orography::{self, OrographyEffects},
     seasonality::{self, SeasonalityContext},
-    HUMIDITY_TEMP_BONUS, HUMIDITY_TENTHS_MAX, LAPSE_RATE_C_PER_KM, MONSOON_HUMIDITY_THRESHOLD,
-    MONSOON_STRENGTH_THRESHOLD, PRECIP_MAX_MM, PRECIP_MIN_MM, TEMP_MAX_TENTHS_C, TEMP_MIN_TENTHS_C,
+    AtmosphereParams, HADLEY_LATITUDE_MAX, HUMIDITY_TEMP_BONUS, HUMIDITY_TENTHS_MAX, PRECIP_MAX_MM,
+    PRECIP_MIN_MM,
 };
 
 pub(super) const PRECIP_EXTREME_THRESHOLD_MM: i32 = 400; // TODO(agents): rationale
 pub(super) const HEAT_EXTREME_THRESHOLD_TENTHS: i32 = 120; // TODO(agents): rationale
 pub(super) const PRECIP_EXTREME_CLAMP: i32 = 2_000;
 pub(super) const HEAT_EXTREME_CLAMP: i32 = 500;
+const FRONTAL_PRECIP_MM_PER_C: f64 = 40.0; // TODO(agents): rationale
+const FRONTAL_PRECIP_MAX_MM: f64 = 900.0;
+
+/// Uniform sample behind region `index`'s ±2% precipitation jitter.
+///
+/// The jitter keeps the first draw of the region's commit stream. Any further stochastic
+/// term must draw from its own `derive(stream_label(..))` child of that stream instead, so
+/// adding, removing, or reordering terms never shifts the jitter.
+pub(super) fn jitter_sample(stream: &Stream, index: usize) -> f64 {
+    stream.derive(index as u64).next_f64()
+}
+
+/// Push `value` into a rolling extreme window of `len` samples and return the spread
+/// between the window's extremes, once `tick` is late enough for the window to be full.
+pub(super) fn rolling_spread<T: Copy + Into<i32>>(
+    window: &mut VecDeque<T>,
+    value: T,
+    len: usize,
+    tick: u64,
+) -> Option<i32> {
+    while window.len() >= len {
+        window.pop_front();
+    }
+    window.push_back(value);
+    if tick < len as u64 {
+        return None;
+    }
+    let (min, max) = window
+        .iter()
+        .map(|&value| value.into())
+        .fold((i32::MAX, i32::MIN), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    Some(max - min)
+}
+
+/// Phase of falling precipitation reported in the `precip_type` map.
+pub const PRECIP_TYPE_RAIN: i32 = 0;
+pub const PRECIP_TYPE_MIXED: i32 = 1;
+pub const PRECIP_TYPE_SNOW: i32 = 2;
+// TODO(agents): rationale - sleet band above freezing before rain dominates.
+const PRECIP_TYPE_MIXED_MAX_TENTHS: i32 = 20;
+
+/// Classify precipitation phase from surface temperature; snow matches the cryosphere's
+/// `temp <= 0 °C` accumulation rule.
+pub fn classify_precip_type(temperature_tenths: i32) -> i32 {
+    if temperature_tenths <= 0 {
+        PRECIP_TYPE_SNOW
+    } else if temperature_tenths <= PRECIP_TYPE_MIXED_MAX_TENTHS {
+        PRECIP_TYPE_MIXED
+    } else {
+        PRECIP_TYPE_RAIN
+    }
+}
 
 pub(super) struct PrecipitationOutcome {
     pub diff: Diff,
@@ -26,16 +85,17 @@ pub(super) fn commit(
     seasonal: &SeasonalityContext,
     orography: &OrographyEffects,
     stream: &Stream,
-) -> PrecipitationOutcome {
+    params: &AtmosphereParams,
+) -> Result<PrecipitationOutcome> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
     let mut monsoon_regions = 0usize;
 
     world.climate.ensure_region_capacity(world.regions.len());
+    let extreme_window = world.climate.extreme_window;
 
     for (index, region) in world.regions.iter().enumerate() {
-        let mut commit_rng = stream.derive(index as u64);
-        let humidity_tenths_value = humidity_tenths[index].clamp(0, HUMIDITY_TENTHS_MAX);
+        let humidity_tenths_value = bounded_humidity(params.strict, index, humidity_tenths[index])?;
         let humidity_ratio = f64::from(humidity_tenths_value) / f64::from(HUMIDITY_TENTHS_MAX);
         diff.record_humidity(index, humidity_tenths_value);
         let capped_precip = i32::from(region.precipitation_mm).clamp(0, PRECIP_MAX_MM);
@@ -56,86 +116,85 @@ pub(super) fn commit(
             .get(index)
             .copied()
             .unwrap_or(0);
-        let mut temperature_tenths = compute_temperature_tenths(
-            effective_latitude,
-            region.elevation_m,
-            humidity_ratio,
-            seasonal.insolation_bias,
-        )
-        .clamp(TEMP_MIN_TENTHS_C, TEMP_MAX_TENTHS_C);
-        temperature_tenths = (temperature_tenths + i32::from(baseline_offset))
-            .clamp(TEMP_MIN_TENTHS_C, TEMP_MAX_TENTHS_C);
+        let pressure_hpa = region.pressure_hpa();
+        let range = params.temperature_range;
+        let mut temperature_tenths = bounded_temperature(
+            params.strict,
+            index,
+            compute_temperature_tenths(
+                effective_latitude,
+                pressure_hpa,
+                humidity_ratio,
+                seasonal.insolation_bias,
+            ),
+            range,
+        )?;
+        temperature_tenths = bounded_temperature(
+            params.strict,
+            index,
+            temperature_tenths + i32::from(baseline_offset),
+            range,
+        )?;
         if i32::from(region.temperature_tenths_c) != temperature_tenths {
             diff.record_temperature(index, temperature_tenths);
         }
 
+        let frontal = frontal_precip(world, index, humidity_ratio);
         let base_precip = compute_precip_mm(
             effective_latitude,
             region.elevation_m,
+            pressure_hpa,
             humidity_ratio,
             hadley,
             seasonal.insolation_bias,
-        );
-        let jitter = (commit_rng.next_f64() - 0.5) * 0.04;
+        ) + frontal.map_or(0, |(_, bonus_mm)| bonus_mm);
+        let jitter = (jitter_sample(stream, index) - 0.5) * 0.04;
         let scaled_precip =
             (f64::from(base_precip) * orography.precip_multipliers[index] * (1.0 + jitter)).round()
                 as i32;
-        let precip_mm = scaled_precip.clamp(PRECIP_MIN_MM, PRECIP_MAX_MM);
-        if u16::from(region.precipitation_mm) != precip_mm as u16 {
+        let cap = params.precip_caps.cap_for(region.latitude_deg);
+        let floor = params.arid_floor_mm.clamp(PRECIP_MIN_MM, cap);
+        let precip_mm = scaled_precip.clamp(floor, cap);
+        if region.precipitation_mm != precip_mm as u16 {
             diff.record_precipitation(index, precip_mm);
         }
 
         if let Some(window) = world.climate.temperature_maxima.get_mut(index) {
-            if window.len() >= EXTREME_WINDOW {
-                window.pop_front();
-            }
-            window.push_back(temperature_tenths as i16);
-            if world.tick >= EXTREME_WINDOW as u64 {
-                let mut min_temp = i16::MAX;
-                let mut max_temp = i16::MIN;
-                for value in window.iter().copied() {
-                    min_temp = min_temp.min(value);
-                    max_temp = max_temp.max(value);
-                }
-                let heat_anomaly =
-                    (i32::from(max_temp) - i32::from(min_temp)).clamp(0, HEAT_EXTREME_CLAMP);
+            let spread = rolling_spread(
+                window,
+                temperature_tenths as i16,
+                extreme_window,
+                world.tick,
+            );
+            if let Some(spread) = spread {
+                let heat_anomaly = spread.clamp(0, HEAT_EXTREME_CLAMP);
                 if heat_anomaly >= HEAT_EXTREME_THRESHOLD_TENTHS {
                     diff.record_heatwave_idx(index, heat_anomaly);
                     diff.record_cause(Entry::new(
                         format!("region:{}/temperature", region.id),
                         Code::HeatExtreme,
-                        Some(format!(
-                            "anomaly_tenths={};window={}",
-                            heat_anomaly, EXTREME_WINDOW
-                        )),
+                        CauseNote::new()
+                            .with("anomaly_tenths", heat_anomaly)
+                            .with("window", extreme_window)
+                            .into_note(),
                     ));
                 }
             }
         }
 
         if let Some(window) = world.climate.precipitation_peaks.get_mut(index) {
-            if window.len() >= EXTREME_WINDOW {
-                window.pop_front();
-            }
-            window.push_back(precip_mm as u16);
-            if world.tick >= EXTREME_WINDOW as u64 {
-                let mut min_precip = u16::MAX;
-                let mut max_precip = u16::MIN;
-                for value in window.iter().copied() {
-                    min_precip = min_precip.min(value);
-                    max_precip = max_precip.max(value);
-                }
-                let precip_anomaly =
-                    (i32::from(max_precip) - i32::from(min_precip)).clamp(0, PRECIP_EXTREME_CLAMP);
+            let spread = rolling_spread(window, precip_mm as u16, extreme_window, world.tick);
+            if let Some(spread) = spread {
+                let precip_anomaly = spread.clamp(0, PRECIP_EXTREME_CLAMP);
                 if precip_anomaly >= PRECIP_EXTREME_THRESHOLD_MM {
                     diff.record_precip_extreme(index, precip_anomaly);
                     diff.record_cause(Entry::new(
                         format!("region:{}/precip", region.id),
                         Code::StormTrackShift,
-                        Some(format!(
-                            "anomaly_mm={};window={}",
-                            precip_anomaly, EXTREME_WINDOW
-                        )),
+                        CauseNote::new()
+                            .with("anomaly_mm", precip_anomaly)
+                            .with("window", extreme_window)
+                            .into_note(),
                     ));
                 }
             }
@@ -145,12 +204,16 @@ pub(super) fn commit(
             diff.record_cause(Entry::new(
                 format!("region:{}/temperature", region.id),
                 Code::SeasonalShift,
-                Some(format!("scalar={:.3}", seasonal.scalar)),
+                CauseNote::new()
+                    .with("scalar", format_args!("{:.3}", seasonal.scalar))
+                    .into_note(),
             ));
             diff.record_cause(Entry::new(
                 format!("region:{}/precip", region.id),
                 Code::SeasonalShift,
-                Some(format!("scalar={:.3}", seasonal.scalar)),
+                CauseNote::new()
+                    .with("scalar", format_args!("{:.3}", seasonal.scalar))
+                    .into_note(),
             ));
         }
 
@@ -158,12 +221,22 @@ pub(super) fn commit(
             diff.record_cause(Entry::new(
                 format!("region:{}/temperature", region.id),
                 Code::HadleyDrift,
-                Some(format!("shift_deg={:.2}", seasonal.hadley_lat_shift)),
+                CauseNote::new()
+                    .with(
+                        "shift_deg",
+                        format_args!("{:.2}", seasonal.hadley_lat_shift),
+                    )
+                    .into_note(),
             ));
             diff.record_cause(Entry::new(
                 format!("region:{}/precip", region.id),
                 Code::HadleyDrift,
-                Some(format!("shift_deg={:.2}", seasonal.hadley_lat_shift)),
+                CauseNote::new()
+                    .with(
+                        "shift_deg",
+                        format_args!("{:.2}", seasonal.hadley_lat_shift),
+                    )
+                    .into_note(),
             ));
         }
 
@@ -171,7 +244,9 @@ pub(super) fn commit(
             diff.record_cause(Entry::new(
                 format!("region:{}/temperature", region.id),
                 Code::HadleyCell,
-                Some(format!("strength={:.2}", hadley)),
+                CauseNote::new()
+                    .with("strength", format_args!("{:.2}", hadley))
+                    .into_note(),
             ));
         }
 
@@ -179,10 +254,27 @@ pub(super) fn commit(
             diff.record_cause(Entry::new(
                 format!("region:{}/precip", region.id),
                 Code::OrographicLift,
-                Some(format!(
-                    "gradient_km={:.2};multiplier={:.2}",
-                    orography.lift_gradients[index], orography.lift_multipliers[index]
-                )),
+                CauseNote::new()
+                    .with(
+                        "gradient_km",
+                        format_args!("{:.2}", orography.lift_gradients[index]),
+                    )
+                    .with(
+                        "multiplier",
+                        format_args!("{:.2}", orography.lift_multipliers[index]),
+                    )
+                    .into_note(),
+            ));
+        }
+
+        if let Some((gradient_c, bonus_mm)) = frontal {
+            diff.record_cause(Entry::new(
+                format!("region:{}/precip", region.id),
+                Code::FrontalPrecip,
+                CauseNote::new()
+                    .with("gradient_c", format_args!("{:.1}", gradient_c))
+                    .with("bonus_mm", bonus_mm)
+                    .into_note(),
             ));
         }
 
@@ -190,29 +282,43 @@ pub(super) fn commit(
             diff.record_cause(Entry::new(
                 format!("region:{}/precip", region.id),
                 Code::RainShadow,
-                Some(format!(
-                    "shadow_factor={:.2}",
-                    orography.rain_shadow_factors[index]
-                )),
+                CauseNote::new()
+                    .with(
+                        "shadow_factor",
+                        format_args!("{:.2}", orography.rain_shadow_factors[index]),
+                    )
+                    .into_note(),
             ));
         }
 
         diff.record_cause(Entry::new(
             format!("region:{}/humidity", region.id),
             Code::HumidityTransport,
-            Some(format!(
-                "precip_ratio={:.2};insolation_tenths={}",
-                precip_ratio, insolation_tenths
-            )),
+            CauseNote::new()
+                .with("precip_ratio", format_args!("{:.2}", precip_ratio))
+                .with("insolation_tenths", insolation_tenths)
+                .into_note(),
         ));
 
         let monsoon_strength = hadley * humidity_ratio;
-        if hadley > MONSOON_STRENGTH_THRESHOLD && humidity_ratio >= MONSOON_HUMIDITY_THRESHOLD {
+        let monsoon_active = hadley > params.monsoon_strength_threshold
+            && humidity_ratio >= params.monsoon_humidity_threshold;
+        let was_active =
+            std::mem::replace(&mut world.climate.monsoon_active[index], monsoon_active);
+        if monsoon_active != was_active {
             diff.record_cause(Entry::new(
                 format!("region:{}/precip", region.id),
-                Code::MonsoonOnset,
-                Some(format!("intensity={:.2}", monsoon_strength)),
+                if monsoon_active {
+                    Code::MonsoonOnset
+                } else {
+                    Code::MonsoonRetreat
+                },
+                CauseNote::new()
+                    .with("intensity", format_args!("{:.2}", monsoon_strength))
+                    .into_note(),
             ));
+        }
+        if monsoon_active {
             monsoon_regions += 1;
         }
     }
@@ -231,19 +337,48 @@ pub(super) fn commit(
     chronicle.push(summary);
     chronicle.push("Convective bursts spiked rainfall; heat lingered over plains.".to_string());
 
-    PrecipitationOutcome { diff, chronicle }
+    Ok(PrecipitationOutcome { diff, chronicle })
+}
+
+/// Frontal rainfall for a mid-latitude region from its contrast with the poleward
+/// neighbour, returned as `(gradient_c, bonus_mm)`.
+///
+/// Uses last tick's committed temperatures so the result is independent of region order.
+fn frontal_precip(world: &World, index: usize, humidity_ratio: f64) -> Option<(f64, i32)> {
+    let region = &world.regions[index];
+    if region.latitude_deg.abs() <= HADLEY_LATITUDE_MAX {
+        return None;
+    }
+    // Row 0 is the north pole, so poleward is up in the north and down in the south.
+    let poleward_y = if region.latitude_deg > 0.0 {
+        region.y as i32 - 1
+    } else {
+        region.y as i32 + 1
+    };
+    let neighbor = orography::region_index_at(world, region.x as i32, poleward_y)?;
+    let gradient_c = f64::from(
+        i32::from(region.temperature_tenths_c)
+            - i32::from(world.regions[neighbor].temperature_tenths_c),
+    ) / 10.0;
+    if gradient_c <= 0.0 {
+        return None;
+    }
+    let bonus_mm = (gradient_c * FRONTAL_PRECIP_MM_PER_C * humidity_ratio)
+        .min(FRONTAL_PRECIP_MAX_MM)
+        .round() as i32;
+    (bonus_mm > 0).then_some((gradient_c, bonus_mm))
 }
 
 fn compute_temperature_tenths(
     latitude_deg: f64,
-    elevation_m: i32,
+    pressure_hpa: f64,
     humidity_ratio: f64,
     insolation_bias: f64,
 ) -> i32 {
     let insolation =
         (seasonality::insolation_factor(latitude_deg) * insolation_bias).clamp(0.0, 1.2);
     let base_temp_c = -25.0 + 60.0 * insolation;
-    let lapse = (f64::from(elevation_m.max(0)) / 1_000.0) * LAPSE_RATE_C_PER_KM;
+    let lapse = pressure_cooling_c(pressure_hpa);
     let humidity_bonus = (humidity_ratio - 0.5) * HUMIDITY_TEMP_BONUS;
     ((base_temp_c - lapse + humidity_bonus) * 10.0).round() as i32
 }
@@ -251,6 +386,7 @@ fn compute_temperature_tenths(
 fn compute_precip_mm(
     latitude_deg: f64,
     elevation_m: i32,
+    pressure_hpa: f64,
     humidity_ratio: f64,
     hadley_strength: f64,
     insolation_bias: f64,
@@ -262,7 +398,7 @@ fn compute_precip_mm(
     let convective = 1_000.0 + 2_200.0 * humidity_ratio * insolation;
     let hadley_bonus = 1_200.0 * hadley_strength * humidity_ratio;
     let humidity_penalty = (1.0 - humidity_ratio).max(0.0) * 700.0;
-    let thin_air_penalty = elevation_km.powf(1.15) * 120.0;
+    let thin_air_penalty = pressure_altitude_km(pressure_hpa).powf(1.15) * 120.0;
     let precip = convective + hadley_bonus + lift_bonus - humidity_penalty - thin_air_penalty;
     precip.round() as i32
 }
@@ -1,8 +1,9 @@
+use crate::detmath;
 use crate::world::World;
 
 use super::{
-    HADLEY_DRIFT_MAX_DEGREES, HADLEY_LATITUDE_MAX, PI, SEASONAL_INSOLATION_AMPLITUDE,
-    SEASONAL_SCALAR_EPSILON, SEASON_PERIOD_TICKS, TAU,
+    HADLEY_DRIFT_MAX_DEGREES, HADLEY_LATITUDE_MAX, LAT_INSOLATION_POWER,
+    SEASONAL_INSOLATION_AMPLITUDE, SEASONAL_SCALAR_EPSILON, SEASON_PERIOD_TICKS, TAU,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -35,9 +36,10 @@ pub(super) fn hadley_strength(latitude_deg: f64) -> f64 {
     }
 }
 
-pub(super) fn insolation_factor(latitude_deg: f64) -> f64 {
+/// Latitude falloff of insolation in 0..=1, shared with the astronomy kernel.
+pub(crate) fn insolation_factor(latitude_deg: f64) -> f64 {
     let closeness = (90.0 - latitude_deg.abs()).max(0.0) / 90.0;
-    closeness.powf(0.85)
+    detmath::powf(closeness, LAT_INSOLATION_POWER)
 }
 
 pub(super) fn has_seasonal_variation(value: f64) -> bool {
@@ -48,34 +50,11 @@ pub(crate) fn scalar_for_tick(tick: u64) -> f64 {
     seasonal_scalar(tick)
 }
 
-fn wrap_angle(mut angle: f64) -> f64 {
-    angle %= TAU;
-    if angle > PI {
-        angle -= TAU;
-    } else if angle < -PI {
-        angle += TAU;
-    }
-    angle
-}
-
-fn sin_series(angle: f64) -> f64 {
-    let x = wrap_angle(angle);
-    let x2 = x * x;
-    let x3 = x * x2;
-    let x5 = x3 * x2;
-    let x7 = x5 * x2;
-    let x9 = x7 * x2;
-    let x11 = x9 * x2;
-    let x13 = x11 * x2;
-    x - x3 / 6.0 + x5 / 120.0 - x7 / 5_040.0 + x9 / 362_880.0 - x11 / 39_916_800.0
-        + x13 / 6_227_020_800.0
-}
-
 fn seasonal_scalar(tick: u64) -> f64 {
     if SEASON_PERIOD_TICKS == 0 {
         return 0.0;
     }
     let tick_in_cycle = (tick % SEASON_PERIOD_TICKS) as f64;
     let phase = (tick_in_cycle / SEASON_PERIOD_TICKS as f64) * TAU;
-    sin_series(phase)
+    detmath::sin(phase)
 }
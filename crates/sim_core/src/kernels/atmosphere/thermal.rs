@@ -0,0 +1,110 @@
+use anyhow::Result;
+
+use crate::cause::{CauseNote, Code, Entry};
+use crate::diff::Diff;
+use crate::fixed::TemperatureRange;
+use crate::world::World;
+
+use super::{bounded_temperature, committed_or};
+
+/// Regions at or below this elevation count as open water for thermal inertia; the
+/// world has no dedicated ocean mask yet.
+pub(crate) const SEA_LEVEL_M: i32 = 0;
+
+/// Open-water regions and the land cells orthogonally adjacent to them, in region order.
+fn maritime_indices(world: &World) -> Vec<usize> {
+    let is_ocean = |index: usize| world.regions[index].elevation_m <= SEA_LEVEL_M;
+    world
+        .regions
+        .iter()
+        .enumerate()
+        .filter(|&(index, region)| {
+            is_ocean(index) || world.neighbor_indices(region.x, region.y).any(is_ocean)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Hold back maritime temperature changes: each committed value only moves a
+/// `1 - inertia` fraction away from last tick's stored temperature.
+pub(super) fn buffer_temperature(
+    world: &World,
+    diff: &mut Diff,
+    inertia: f64,
+    range: TemperatureRange,
+    strict: bool,
+) -> Result<()> {
+    if inertia <= 0.0 {
+        return Ok(());
+    }
+    for index in maritime_indices(world) {
+        let Ok(position) = diff
+            .temperature
+            .binary_search_by_key(&(index as u32), |value| value.region)
+        else {
+            continue;
+        };
+        let previous = i32::from(world.regions[index].temperature_tenths_c);
+        let committed = diff.temperature[position].value;
+        let step = ((1.0 - inertia) * f64::from(committed - previous)).round() as i32;
+        diff.record_temperature(
+            index,
+            bounded_temperature(strict, index, previous + step, range)?,
+        );
+    }
+    Ok(())
+}
+
+/// Chill land basins that sit below every neighbour, as cold air drains downslope and
+/// pools there. The offset grows with the basin's depth below its lowest rim, at
+/// `strength_c_per_km` °C per kilometre.
+pub(super) fn pool_cold_air(
+    world: &World,
+    diff: &mut Diff,
+    strength_c_per_km: f64,
+    range: TemperatureRange,
+    strict: bool,
+) -> Result<()> {
+    if strength_c_per_km <= 0.0 {
+        return Ok(());
+    }
+    for (index, region) in world.regions.iter().enumerate() {
+        if region.elevation_m <= SEA_LEVEL_M {
+            continue;
+        }
+        let Some(rim_m) = world
+            .neighbors4(index)
+            .map(|neighbor| world.regions[neighbor].elevation_m)
+            .min()
+        else {
+            continue;
+        };
+        let relief_m = rim_m - region.elevation_m;
+        if relief_m <= 0 {
+            continue;
+        }
+        let offset_tenths =
+            (f64::from(relief_m) / 1_000.0 * strength_c_per_km * 10.0).round() as i32;
+        if offset_tenths == 0 {
+            continue;
+        }
+        let current = committed_or(
+            &diff.temperature,
+            index,
+            i32::from(region.temperature_tenths_c),
+        );
+        diff.record_temperature(
+            index,
+            bounded_temperature(strict, index, current - offset_tenths, range)?,
+        );
+        diff.record_cause(Entry::new(
+            format!("region:{}/temperature", region.id),
+            Code::ColdAirPooling,
+            CauseNote::new()
+                .with("relief_m", relief_m)
+                .with("offset_tenths", -offset_tenths)
+                .into_note(),
+        ));
+    }
+    Ok(())
+}
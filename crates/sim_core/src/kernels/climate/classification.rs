@@ -78,14 +78,15 @@ pub(super) fn classify_biome(belt: &LatitudeBelt, dryness: f64) -> u8 {
     }
 }
 
-pub(super) fn biome_label(biome: u8) -> &'static str {
+/// Characteristic snow-free surface albedo in milli-units for each biome code.
+pub(crate) fn biome_albedo_milli(biome: u8) -> i32 {
     match biome {
-        5 => "rainforest",
-        4 => "desert",
-        3 => "steppe",
-        2 => "temperate",
-        1 => "boreal",
-        _ => "polar",
+        5 => 130, // dark closed canopy
+        4 => 350, // bright sand
+        3 => 250,
+        2 => 170,
+        1 => 150,
+        _ => 600, // tundra with persistent frost
     }
 }
 
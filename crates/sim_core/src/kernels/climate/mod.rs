@@ -1,4 +1,5 @@
-use crate::cause::{Code, Entry};
+use crate::biome;
+use crate::cause::{CauseNote, Code, Entry};
 use crate::diff::Diff;
 use crate::rng::Stream;
 use crate::schedule::KernelRun;
@@ -8,8 +9,14 @@ use anyhow::{ensure, Result};
 mod classification;
 mod diagnostics;
 
+pub(crate) use classification::biome_albedo_milli;
+
 pub const STAGE: &str = "kernel:climate";
 pub const CORE_STAGE: &str = "kernel:climate/core";
+
+/// Largest per-tick move of a region's albedo toward its new biome's surface value.
+const BIOME_ALBEDO_STEP_MILLI: i32 = 20;
+
 pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
@@ -34,14 +41,31 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
             chronicle.push(format!(
                 "Region {} shifted toward a {} biome.",
                 region.id,
-                classification::biome_label(biome)
+                biome::label(biome)
             ));
+
+            let current_albedo = i32::from(region.albedo_milli);
+            let target_albedo = classification::biome_albedo_milli(biome);
+            let next_albedo = current_albedo
+                + (target_albedo - current_albedo)
+                    .clamp(-BIOME_ALBEDO_STEP_MILLI, BIOME_ALBEDO_STEP_MILLI);
+            if next_albedo != current_albedo {
+                diff.record_albedo(region.index(), next_albedo);
+                diff.record_cause(Entry::new(
+                    format!("region:{}/albedo", region.id),
+                    Code::SurfaceAlbedo,
+                    CauseNote::new()
+                        .with("biome", biome)
+                        .with("milli", next_albedo)
+                        .into_note(),
+                ));
+            }
         }
 
         diff.record_cause(Entry::new(
             format!("region:{}/biome", region.id),
             Code::LatitudeBelt,
-            Some(format!("{}", belt.label())),
+            Some(belt.label().to_string()),
         ));
         diff.record_cause(Entry::new(
             format!("region:{}/biome", region.id),
@@ -181,6 +205,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn biome_change_nudges_albedo_toward_surface_value() {
+        let region = |id: u32, biome: u8, water: u16, elevation_m: i32, albedo_milli: u16| Region {
+            id,
+            x: id,
+            y: 0,
+            elevation_m,
+            latitude_deg: 0.0,
+            biome,
+            water,
+            soil: 5_000,
+            temperature_tenths_c: 250,
+            precipitation_mm: 0,
+            albedo_milli,
+            freshwater_flux_tenths_mm: 0,
+            ice_mass_kilotons: 0,
+            hazards: Hazards::default(),
+        };
+        // A parched highland turns desert; a saturated lowland turns rainforest.
+        let regions = vec![region(0, 2, 0, 3_000, 200), region(1, 4, 10_000, 0, 300)];
+        let world = World::new(11, 2, 1, regions);
+        let mut rng = Stream::from(world.seed, STAGE, 3);
+        let diff = update(&world, &mut rng).expect("climate update").diff;
+
+        let biomes: Vec<(u32, i32)> = diff.biome.iter().map(|c| (c.region, c.biome)).collect();
+        assert_eq!(biomes, vec![(0, 4), (1, 5)]);
+        let albedo: Vec<(u32, i32)> = diff.albedo.iter().map(|v| (v.region, v.value)).collect();
+        assert_eq!(
+            albedo,
+            vec![
+                (0, 200 + BIOME_ALBEDO_STEP_MILLI),
+                (1, 300 - BIOME_ALBEDO_STEP_MILLI)
+            ]
+        );
+        let surface_causes = diff
+            .causes
+            .iter()
+            .filter(|entry| entry.code == Code::SurfaceAlbedo)
+            .count();
+        assert_eq!(surface_causes, 2);
+    }
+
     #[test]
     fn orographic_lift_cause_for_elevated_regions() {
         let regions = vec![
@@ -1,4 +1,5 @@
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
 
 use crate::diff::Diff;
 use crate::rng::Stream;
@@ -8,11 +9,92 @@ use crate::world::World;
 pub const STAGE: &str = "CLIMATE::climate_diag";
 pub const CHRONICLE_LINE: &str = "Climate diagnostics stable; no anomalies detected.";
 
+/// Frame `diagnostics` key carrying the accumulated cryosphere sea level each tick.
+pub const SEA_LEVEL_DIAGNOSTIC: &str = "sea_level_equivalent_mm";
+
 const DIAG_MIN: i32 = -1_000;
 const DIAG_MAX: i32 = 1_000;
 
-pub fn update(world: &World, _rng: &mut Stream) -> Result<KernelRun> {
+/// Tolerance on the sum of [`DiagWeights`] when validating.
+const WEIGHT_SUM_TOLERANCE: f64 = 1e-9;
+
+/// Share of each normalised term in the composite stability index; must sum to 1.
+///
+/// Temperature enters in tenths of °C, precipitation as `(mm - 1_500) / 5`, water as
+/// `(meter - 5_000) / 5`, albedo as `(milli - 450) / 2`, and sea level in millimetres.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiagWeights {
+    pub temperature: f64,
+    pub precipitation: f64,
+    pub water: f64,
+    pub albedo: f64,
+    pub sea_level: f64,
+}
+
+impl Default for DiagWeights {
+    fn default() -> Self {
+        Self {
+            temperature: 0.45,
+            precipitation: 0.25,
+            water: 0.15,
+            albedo: 0.1,
+            sea_level: 0.05,
+        }
+    }
+}
+
+impl DiagWeights {
+    /// Reject negative or non-finite weights and weights that do not sum to 1.
+    pub fn validate(&self) -> Result<()> {
+        let weights = [
+            ("temperature", self.temperature),
+            ("precipitation", self.precipitation),
+            ("water", self.water),
+            ("albedo", self.albedo),
+            ("sea_level", self.sea_level),
+        ];
+        for (name, weight) in weights {
+            ensure!(
+                weight.is_finite() && weight >= 0.0,
+                "diag weight {} must be a non-negative number, got {}",
+                name,
+                weight
+            );
+        }
+        let sum: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        ensure!(
+            (sum - 1.0).abs() <= WEIGHT_SUM_TOLERANCE,
+            "diag weights must sum to 1.0, got {}",
+            sum
+        );
+        Ok(())
+    }
+}
+
+/// How the composite stability index is reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ClimateDiagOptions {
+    /// Record a composite for every region instead of the single global value at `r:0`.
+    pub per_region: bool,
+    /// Term weights for the composite; validated before every update.
+    pub weights: DiagWeights,
+}
+
+pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_options(world, rng, ClimateDiagOptions::default())
+}
+
+pub fn update_with_options(
+    world: &World,
+    _rng: &mut Stream,
+    options: ClimateDiagOptions,
+) -> Result<KernelRun> {
+    options.weights.validate()?;
     let mut diff = Diff::default();
+    diff.record_diagnostic(
+        SEA_LEVEL_DIAGNOSTIC,
+        world.climate.sea_level_equivalent_mm(),
+    );
 
     if world.regions.is_empty() {
         diff.record_diag_climate(0, 0);
@@ -21,6 +103,24 @@ pub fn update(world: &World, _rng: &mut Stream) -> Result<KernelRun> {
         return Ok(run);
     }
 
+    let sea_level = world.climate.sea_level_equivalent_mm() as f64;
+    if options.per_region {
+        for (index, region) in world.regions.iter().enumerate() {
+            let value = composite_index(
+                &options.weights,
+                f64::from(region.temperature_tenths_c),
+                f64::from(region.precipitation_mm),
+                f64::from(region.water),
+                f64::from(region.albedo_milli),
+                sea_level,
+            );
+            diff.record_diag_climate(index, value);
+        }
+        let mut run = KernelRun::new(diff);
+        run.chronicle.push(CHRONICLE_LINE.to_string());
+        return Ok(run);
+    }
+
     let region_count = world.regions.len() as f64;
     let mean_temp = world
         .regions
@@ -46,16 +146,15 @@ pub fn update(world: &World, _rng: &mut Stream) -> Result<KernelRun> {
         .map(|region| f64::from(region.albedo_milli))
         .sum::<f64>()
         / region_count;
-    let sea_level = world.climate.sea_level_equivalent_mm() as f64;
-
-    let composite = 0.45 * mean_temp
-        + 0.25 * ((mean_precip - 1_500.0) / 5.0)
-        + 0.15 * ((mean_water - 5_000.0) / 5.0)
-        + 0.1 * ((mean_albedo - 450.0) / 2.0)
-        + 0.05 * sea_level;
 
-    let diag_value = composite.round() as i32;
-    let clamped = diag_value.clamp(DIAG_MIN, DIAG_MAX);
+    let clamped = composite_index(
+        &options.weights,
+        mean_temp,
+        mean_precip,
+        mean_water,
+        mean_albedo,
+        sea_level,
+    );
     diff.record_diag_climate(0, clamped);
 
     let mut run = KernelRun::new(diff);
@@ -63,9 +162,28 @@ pub fn update(world: &World, _rng: &mut Stream) -> Result<KernelRun> {
     Ok(run)
 }
 
+fn composite_index(
+    weights: &DiagWeights,
+    temp: f64,
+    precip: f64,
+    water: f64,
+    albedo: f64,
+    sea_level: f64,
+) -> i32 {
+    let composite = weights.temperature * temp
+        + weights.precipitation * ((precip - 1_500.0) / 5.0)
+        + weights.water * ((water - 5_000.0) / 5.0)
+        + weights.albedo * ((albedo - 450.0) / 2.0)
+        + weights.sea_level * sea_level;
+
+    let diag_value = composite.round() as i32;
+    diag_value.clamp(DIAG_MIN, DIAG_MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::frame::make_frame;
     use crate::rng::Stream;
     use crate::world::{Hazards, Region, World};
 
@@ -153,6 +271,23 @@ mod tests {
         assert_eq!(entry.value, expected.clamp(DIAG_MIN, DIAG_MAX));
     }
 
+    #[test]
+    fn frame_diagnostics_carry_sea_level_until_reset() {
+        let mut world = seed_world(120, 1_200, 6_000, 480);
+        world.climate.add_sea_level_equivalent_mm(37);
+        let sea_level_in_frame = |world: &World| {
+            let mut rng = Stream::from(world.seed, STAGE, 2);
+            let run = update(world, &mut rng).expect("climate diag update succeeds");
+            let frame = make_frame(2, run.diff, Vec::new(), Vec::new(), false, 2, 1);
+            frame.diagnostics.get(SEA_LEVEL_DIAGNOSTIC).copied()
+        };
+        assert_eq!(sea_level_in_frame(&world), Some(37));
+
+        world.climate.reset_sea_level();
+        assert_eq!(world.climate.sea_level_equivalent_mm(), 0);
+        assert_eq!(sea_level_in_frame(&world), Some(0));
+    }
+
     #[test]
     fn diagnostic_value_clamped_to_bounds() {
         let mut world = seed_world(500, 5_000, 10_000, 900);
@@ -171,4 +306,77 @@ mod tests {
         let entry = &run.diff.diag_climate[0];
         assert_eq!(entry.value, DIAG_MIN);
     }
+
+    #[test]
+    fn per_region_mode_records_every_region() {
+        let world = seed_world(120, 1_800, 6_000, 480);
+        let mut rng = Stream::from(world.seed, STAGE, 4);
+        let global = update(&world, &mut rng).expect("climate diag update succeeds");
+
+        let mut rng = Stream::from(world.seed, STAGE, 4);
+        let options = ClimateDiagOptions {
+            per_region: true,
+            ..ClimateDiagOptions::default()
+        };
+        let per_region = update_with_options(&world, &mut rng, options)
+            .expect("per-region climate diag succeeds");
+
+        assert_eq!(per_region.diff.diag_climate.len(), world.regions.len());
+        assert_eq!(per_region.diff.diag_climate[0].region, 0);
+        assert_eq!(
+            per_region.diff.diag_climate[0].value,
+            global.diff.diag_climate[0].value
+        );
+    }
+
+    #[test]
+    fn diag_weights_retune_the_composite() {
+        let world = seed_world(180, 1_200, 7_500, 520);
+        let composite = |weights: DiagWeights| {
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            let options = ClimateDiagOptions {
+                weights,
+                ..ClimateDiagOptions::default()
+            };
+            update_with_options(&world, &mut rng, options)
+                .expect("climate diag update succeeds")
+                .diff
+                .diag_climate[0]
+                .value
+        };
+
+        // 0.45 * 180 + 0.25 * -60 + 0.15 * 500 + 0.1 * 35 + 0.05 * 0, rounded.
+        assert_eq!(composite(DiagWeights::default()), 145);
+
+        let temperature_only = DiagWeights {
+            temperature: 1.0,
+            precipitation: 0.0,
+            water: 0.0,
+            albedo: 0.0,
+            sea_level: 0.0,
+        };
+        assert_eq!(composite(temperature_only), 180);
+        let mut wetter = seed_world(180, 4_000, 9_000, 300);
+        wetter.climate.add_sea_level_equivalent_mm(500);
+        let mut rng = Stream::from(wetter.seed, STAGE, 1);
+        let options = ClimateDiagOptions {
+            weights: temperature_only,
+            ..ClimateDiagOptions::default()
+        };
+        let run = update_with_options(&wetter, &mut rng, options).expect("climate diag");
+        assert_eq!(run.diff.diag_climate[0].value, 180);
+
+        let lopsided = DiagWeights {
+            temperature: 0.9,
+            ..DiagWeights::default()
+        };
+        let err = lopsided.validate().expect_err("weights sum past 1");
+        assert!(err.to_string().contains("sum to 1.0"), "{}", err);
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let options = ClimateDiagOptions {
+            weights: lopsided,
+            ..ClimateDiagOptions::default()
+        };
+        assert!(update_with_options(&world, &mut rng, options).is_err());
+    }
 }
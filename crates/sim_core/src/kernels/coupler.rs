@@ -1,7 +1,4 @@
-use std::cell::RefCell;
-use std::ptr::NonNull;
-
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 
 use crate::cause::{Code, Entry};
 use crate::diff::Diff;
@@ -13,8 +10,92 @@ pub const CHRONICLE_LINE: &str =
 
 const BASELINE_LIMIT_TENTHS: i32 = 120;
 
-thread_local! {
-    static CONTEXT: RefCell<Option<NonNull<World>>> = RefCell::new(None);
+/// Default [`AlbedoFeedbackParams::gain`]: one tenth °C of baseline shift per 120 milli of
+/// albedo change, so only a swing of at least 60 milli moves the baseline at all and the
+/// feedback nudges the atmosphere's energy balance rather than driving it.
+pub const DEFAULT_ALBEDO_GAIN: f64 = 1.0 / 120.0;
+
+/// Default [`AlbedoFeedbackParams::per_tick_clamp`]: one tenth °C per tick keeps the
+/// coupler far slower than the four-tick seasonal cycle, so the feedback cannot ring
+/// against the seasons.
+pub const DEFAULT_PER_TICK_CLAMP_TENTHS: i32 = 1;
+
+/// Albedo → temperature baseline feedback applied by the coupler.
+///
+/// This is the only runtime pathway from albedo anomalies to temperature; the defaults
+/// reproduce the historical `/120.0`, ±1 per tick, ±120 baseline behaviour.
+///
+/// By default any part of an anomaly beyond `per_tick_clamp` is dropped. With
+/// `carry_residual` the unapplied part stays pending and keeps moving the baseline on
+/// later ticks until the full `anomaly * gain` response has landed; see
+/// [`AlbedoFeedbackParams::with_response_ticks`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlbedoFeedbackParams {
+    /// Baseline tenths °C per milli of albedo anomaly (sign inverted on apply).
+    pub gain: f64,
+    /// Largest baseline step a region may take in one tick, in tenths °C.
+    pub per_tick_clamp: i32,
+    /// Absolute bound on the accumulated baseline offset, in tenths °C.
+    pub baseline_limit: i32,
+    /// Carry the clamped-off part of an anomaly into later ticks instead of dropping it.
+    pub carry_residual: bool,
+}
+
+impl Default for AlbedoFeedbackParams {
+    fn default() -> Self {
+        Self {
+            gain: DEFAULT_ALBEDO_GAIN,
+            per_tick_clamp: DEFAULT_PER_TICK_CLAMP_TENTHS,
+            baseline_limit: BASELINE_LIMIT_TENTHS,
+            carry_residual: false,
+        }
+    }
+}
+
+impl AlbedoFeedbackParams {
+    /// Carrying feedback whose per-tick clamp lets an anomaly of `anomaly_milli` reach its
+    /// full baseline response within `ticks` ticks.
+    pub fn with_response_ticks(ticks: u32, anomaly_milli: i32) -> Self {
+        let defaults = Self::default();
+        let target = defaults.target_adjust(anomaly_milli).abs();
+        let ticks = i32::try_from(ticks.max(1)).unwrap_or(i32::MAX);
+        Self {
+            per_tick_clamp: ((target + ticks - 1) / ticks).max(1),
+            carry_residual: true,
+            ..defaults
+        }
+    }
+
+    /// Ticks a carrying coupler needs to apply the full response to `anomaly_milli`.
+    pub fn response_ticks(&self, anomaly_milli: i32) -> u32 {
+        let target = self.target_adjust(anomaly_milli).unsigned_abs();
+        target.div_ceil(self.per_tick_clamp.max(1).unsigned_abs())
+    }
+
+    /// Unbounded baseline response to an albedo anomaly in milli.
+    pub fn target_adjust(&self, anomaly_milli: i32) -> i32 {
+        (-f64::from(anomaly_milli) * self.gain).round() as i32
+    }
+
+    /// Bounded per-tick baseline adjustment for an albedo anomaly in milli.
+    pub fn baseline_adjust(&self, anomaly_milli: i32) -> i32 {
+        self.target_adjust(anomaly_milli)
+            .clamp(-self.per_tick_clamp, self.per_tick_clamp)
+    }
+
+    /// Part of `anomaly_milli` a baseline step of `adjust` accounts for; the rest stays
+    /// pending when carrying.
+    fn absorbed_anomaly(&self, anomaly_milli: i32, adjust: i32) -> i32 {
+        if adjust == self.target_adjust(anomaly_milli) || self.gain == 0.0 {
+            return anomaly_milli;
+        }
+        let absorbed = (-f64::from(adjust) / self.gain).round() as i32;
+        if anomaly_milli >= 0 {
+            absorbed.clamp(0, anomaly_milli)
+        } else {
+            absorbed.clamp(anomaly_milli, 0)
+        }
+    }
 }
 
 pub fn reconcile_with_world(
@@ -22,37 +103,29 @@ pub fn reconcile_with_world(
     atmos_diff: &Diff,
     cryo_diff: &Diff,
 ) -> Result<Diff> {
-    CONTEXT.with(|ctx| {
-        let mut guard = ctx.borrow_mut();
-        debug_assert!(guard.is_none(), "coupler context should be empty");
-        *guard = NonNull::new(world as *mut World);
-    });
-    let result = reconcile(atmos_diff, cryo_diff);
-    CONTEXT.with(|ctx| {
-        let mut guard = ctx.borrow_mut();
-        *guard = None;
-    });
-    result
+    reconcile_with_params(
+        world,
+        atmos_diff,
+        cryo_diff,
+        &AlbedoFeedbackParams::default(),
+    )
 }
 
-pub fn reconcile(atmos_diff: &Diff, cryo_diff: &Diff) -> Result<Diff> {
-    CONTEXT.with(|ctx| -> Result<Diff> {
-        let ptr = {
-            let guard = ctx.borrow();
-            guard
-                .as_ref()
-                .copied()
-                .ok_or_else(|| anyhow!("coupler world context missing"))?
-        };
-        // SAFETY: `reconcile_with_world` guarantees exclusive access for the
-        // duration of this call. The pointer remains valid until we clear the
-        // context after `reconcile` returns.
-        let world = unsafe { ptr.as_ptr().as_mut().expect("world pointer") };
-        Ok(reconcile_inner(world, atmos_diff, cryo_diff))
-    })
+pub fn reconcile_with_params(
+    world: &mut World,
+    atmos_diff: &Diff,
+    cryo_diff: &Diff,
+    params: &AlbedoFeedbackParams,
+) -> Result<Diff> {
+    Ok(reconcile_inner(world, atmos_diff, cryo_diff, params))
 }
 
-fn reconcile_inner(world: &mut World, _atmos_diff: &Diff, cryo_diff: &Diff) -> Diff {
+fn reconcile_inner(
+    world: &mut World,
+    _atmos_diff: &Diff,
+    cryo_diff: &Diff,
+    params: &AlbedoFeedbackParams,
+) -> Diff {
     let mut diff = Diff::default();
     if cryo_diff.albedo.is_empty() {
         return diff;
@@ -77,8 +150,13 @@ fn reconcile_inner(world: &mut World, _atmos_diff: &Diff, cryo_diff: &Diff) -> D
             .get_mut(index)
             .expect("climate state sized to regions");
         let previous_albedo = if *slot == 0 { current_albedo } else { *slot };
-        *slot = current_albedo;
         let anomaly = current_albedo - previous_albedo;
+        let bounded_adjust = params.baseline_adjust(anomaly);
+        *slot = if params.carry_residual {
+            previous_albedo + params.absorbed_anomaly(anomaly, bounded_adjust)
+        } else {
+            current_albedo
+        };
         if anomaly == 0 {
             continue;
         }
@@ -86,8 +164,6 @@ fn reconcile_inner(world: &mut World, _atmos_diff: &Diff, cryo_diff: &Diff) -> D
         adjusted_regions += 1;
         total_anomaly += i64::from(anomaly);
 
-        let raw_adjust = (-anomaly as f64 / 120.0).round() as i32;
-        let bounded_adjust = raw_adjust.clamp(-1, 1);
         let baseline_slot = world
             .climate
             .temperature_baseline_tenths
@@ -95,7 +171,7 @@ fn reconcile_inner(world: &mut World, _atmos_diff: &Diff, cryo_diff: &Diff) -> D
             .expect("baseline state sized");
         let previous = i32::from(*baseline_slot);
         let updated =
-            (previous + bounded_adjust).clamp(-BASELINE_LIMIT_TENTHS, BASELINE_LIMIT_TENTHS);
+            (previous + bounded_adjust).clamp(-params.baseline_limit, params.baseline_limit);
         if updated != previous {
             *baseline_slot = updated as i16;
             diff.record_temperature_baseline(index, updated);
@@ -242,4 +318,82 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn baseline_adjustment_follows_feedback_gain() {
+        let params = AlbedoFeedbackParams {
+            gain: 0.05,
+            per_tick_clamp: 3,
+            baseline_limit: 4,
+            ..AlbedoFeedbackParams::default()
+        };
+        for anomaly in [-200, -50, -10, 0, 10, 30, 50, 200] {
+            let expected = ((-anomaly as f64) * params.gain).round() as i32;
+            assert_eq!(params.baseline_adjust(anomaly), expected.clamp(-3, 3));
+        }
+
+        let mut world = seed_world();
+        world.climate.ensure_region_capacity(world.regions.len());
+        world.climate.last_albedo_milli[0] = 300;
+        world.climate.last_albedo_milli[1] = 500;
+        world.climate.temperature_baseline_tenths[1] = 2;
+        world.regions[0].albedo_milli = 340;
+        world.regions[1].albedo_milli = 300;
+        let mut cryo_diff = KernelDiff::default();
+        cryo_diff.record_albedo(0, 340);
+        cryo_diff.record_albedo(1, 300);
+
+        let diff = reconcile_with_params(&mut world, &KernelDiff::default(), &cryo_diff, &params)
+            .expect("reconcile succeeds");
+        // +40 milli → -2 tenths; -200 milli → +10 clamped to +3, then bounded by the limit.
+        assert_eq!(world.climate.temperature_baseline_tenths[0], -2);
+        assert_eq!(world.climate.temperature_baseline_tenths[1], 4);
+        assert_eq!(diff.temperature_baseline.len(), 2);
+
+        let defaults = AlbedoFeedbackParams::default();
+        assert_eq!(defaults.baseline_adjust(59), 0);
+        assert_eq!(defaults.baseline_adjust(60), -1);
+        assert_eq!(defaults.baseline_adjust(-500), 1);
+    }
+
+    #[test]
+    fn larger_per_tick_clamp_reaches_the_target_sooner() {
+        // Ticks until a +600 milli albedo jump has moved the baseline to its full target.
+        fn ticks_to_target(params: &AlbedoFeedbackParams) -> Option<u32> {
+            let mut world = World::new(5, 1, 1, vec![Region::sample(0, 0, 0).with_albedo(300)]);
+            world.regions[0].albedo_milli = 900;
+            let mut cryo_diff = KernelDiff::default();
+            cryo_diff.record_albedo(0, 900);
+            let target = params.target_adjust(600);
+            for tick in 1..=20 {
+                reconcile_with_params(&mut world, &KernelDiff::default(), &cryo_diff, params)
+                    .expect("reconcile succeeds");
+                if i32::from(world.climate.temperature_baseline_tenths[0]) == target {
+                    return Some(tick);
+                }
+            }
+            None
+        }
+
+        let slow = AlbedoFeedbackParams {
+            carry_residual: true,
+            ..AlbedoFeedbackParams::default()
+        };
+        let fast = AlbedoFeedbackParams {
+            per_tick_clamp: 5,
+            ..slow
+        };
+        assert_eq!(slow.target_adjust(600), -5);
+        assert_eq!(ticks_to_target(&slow), Some(5));
+        assert_eq!(ticks_to_target(&fast), Some(1));
+        assert_eq!(slow.response_ticks(600), 5);
+
+        let two_ticks = AlbedoFeedbackParams::with_response_ticks(2, 600);
+        assert_eq!(two_ticks.per_tick_clamp, 3);
+        assert_eq!(two_ticks.response_ticks(600), 2);
+        assert_eq!(ticks_to_target(&two_ticks), Some(2));
+
+        // The historical behaviour drops the clamped-off remainder.
+        assert_eq!(ticks_to_target(&AlbedoFeedbackParams::default()), None);
+    }
 }
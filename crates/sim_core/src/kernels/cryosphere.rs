@@ -1,11 +1,12 @@
 use anyhow::Result;
 
-use crate::cause::{Code, Entry};
+use crate::cause::{CauseNote, Code, Entry};
 use crate::diff::Diff;
 use crate::fixed::{ALBEDO_MAX, FRESHWATER_FLUX_MAX};
+use crate::kernels::climate;
 use crate::rng::Stream;
 use crate::schedule::KernelRun;
-use crate::world::World;
+use crate::world::{pressure_altitude_km, World};
 
 pub const STAGE: &str = "kernel:cryosphere";
 pub const CHRONICLE_LINE: &str = "Active layer deepened; surface darkened slightly.";
@@ -17,6 +18,8 @@ const FRESHWATER_FLUX_MAX_I32: i32 = FRESHWATER_FLUX_MAX as i32;
 const ICE_ACCUM_PER_MM: f64 = 6.5;
 const ICE_MASS_SATURATION_KT: f64 = 60_000.0;
 const ICE_MASS_MAX_KT: f64 = 200_000.0;
+/// Default coverage→albedo curve: ice coverage fraction mapped linearly onto the albedo span.
+const COVERAGE_ALBEDO_TABLE: &[(f64, f64)] = &[(0.0, 0.0), (1.0, 1.0)];
 const SNOWPACK_CAPTURE_RATIO: f32 = 0.6; // TODO(agents): rationale
 const COLD_DEGREE_DAY_ACCUM_MM: f32 = 1.4; // TODO(agents): rationale
 const WARM_DEGREE_DAY_MELT_MM: f32 = 4.8; // TODO(agents): rationale
@@ -44,7 +47,111 @@ fn active_layer_depth(temp_tenths: i16) -> i32 {
     depth
 }
 
+/// Tunable ice-mass response for the cryosphere kernel.
+///
+/// `coverage_albedo` maps ice coverage (`ice_mass / ice_mass_saturation_kt`, capped at 1)
+/// onto the fraction of the albedo span above the floor. Points are `(coverage, fraction)`
+/// sorted by coverage and interpolated linearly; values outside the table clamp to its ends.
+///
+/// `sea_level_coupling` is the fraction of each tick's glacier and snow melt that reaches
+/// the ocean and counts toward `sea_level_equivalent_mm`; negative values act as `0.0`.
+///
+/// `freezing_rise_c_per_km` raises the snow/melt threshold above 0 °C by this many degrees
+/// per kilometre of pressure altitude (the standard-atmosphere height of the surface
+/// pressure), so highland snow survives slightly warmer surface air. The atmosphere's
+/// `precip_type` keeps the flat 0 °C rule.
+///
+/// `polar_albedo_floor_milli` is the darkest an ice-free surface may become poleward of
+/// `ice_cap_latitude_deg`; the floor ramps linearly from the global 100 milli at that
+/// latitude up to this value at the pole, so bare polar ground stays bright enough to
+/// refreeze. Regions below the floor brighten at the usual per-tick albedo step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CryosphereParams {
+    pub ice_accum_per_mm: f64,
+    pub ice_mass_saturation_kt: f64,
+    pub ice_mass_max_kt: f64,
+    pub coverage_albedo: Vec<(f64, f64)>,
+    pub sea_level_coupling: f64,
+    pub freezing_rise_c_per_km: f64,
+    pub polar_albedo_floor_milli: i32,
+    pub ice_cap_latitude_deg: f64,
+}
+
+impl Default for CryosphereParams {
+    fn default() -> Self {
+        Self {
+            ice_accum_per_mm: ICE_ACCUM_PER_MM,
+            ice_mass_saturation_kt: ICE_MASS_SATURATION_KT,
+            ice_mass_max_kt: ICE_MASS_MAX_KT,
+            coverage_albedo: COVERAGE_ALBEDO_TABLE.to_vec(),
+            sea_level_coupling: 1.0,
+            freezing_rise_c_per_km: 0.0,
+            polar_albedo_floor_milli: 300,
+            ice_cap_latitude_deg: 60.0,
+        }
+    }
+}
+
+impl CryosphereParams {
+    /// Surface temperature in °C at or below which snow accumulates under `pressure_hpa`.
+    pub fn freezing_point_c(&self, pressure_hpa: f64) -> f64 {
+        pressure_altitude_km(pressure_hpa) * self.freezing_rise_c_per_km
+    }
+
+    /// Minimum albedo in milli-units for a region at `latitude_deg`.
+    pub fn albedo_floor_milli(&self, latitude_deg: f64) -> i32 {
+        let polar = self
+            .polar_albedo_floor_milli
+            .clamp(ALBEDO_FLOOR, ALBEDO_MAX_I32);
+        let band = (90.0 - self.ice_cap_latitude_deg).max(f64::EPSILON);
+        let ramp = ((latitude_deg.abs() - self.ice_cap_latitude_deg) / band).clamp(0.0, 1.0);
+        ALBEDO_FLOOR + (f64::from(polar - ALBEDO_FLOOR) * ramp).round() as i32
+    }
+
+    /// Ice-driven albedo in milli-units before latitude, noise, and thaw adjustments,
+    /// blending from the snow-free `bare_milli` toward full ice cover.
+    pub fn coverage_albedo_milli(&self, ice_mass_kt: f64, bare_milli: i32) -> f64 {
+        let coverage = if ice_mass_kt <= 0.0 {
+            0.0
+        } else {
+            (ice_mass_kt / self.ice_mass_saturation_kt).min(1.0)
+        };
+        let bare = bare_milli.clamp(ALBEDO_FLOOR, ALBEDO_MAX_I32);
+        bare as f64 + (ALBEDO_MAX_I32 - bare) as f64 * self.coverage_fraction(coverage)
+    }
+
+    fn coverage_fraction(&self, coverage: f64) -> f64 {
+        let table = self.coverage_albedo.as_slice();
+        let (first, last) = match (table.first(), table.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return coverage,
+        };
+        if coverage <= first.0 {
+            return first.1;
+        }
+        for pair in table.windows(2) {
+            let (x0, y0) = pair[0];
+            let (x1, y1) = pair[1];
+            if coverage <= x1 {
+                if x1 <= x0 {
+                    return y1;
+                }
+                return y0 + (y1 - y0) * (coverage - x0) / (x1 - x0);
+            }
+        }
+        last.1
+    }
+}
+
 pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &CryosphereParams::default())
+}
+
+pub fn update_with_params(
+    world: &mut World,
+    rng: &mut Stream,
+    params: &CryosphereParams,
+) -> Result<KernelRun> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
     let mut ice_updates = 0usize;
@@ -53,10 +160,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
     let mut contributing_regions = 0usize;
     let mut total_melt_mm = 0.0;
 
-    world.climate.ensure_region_capacity(world.regions.len());
-
-    for index in 0..world.regions.len() {
-        let region = &world.regions[index];
+    for (index, (region, climate)) in world.iter_regions_mut_with_climate().enumerate() {
         debug_assert_eq!(
             region.index(),
             index,
@@ -70,38 +174,36 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
         let existing_albedo = i32::from(region.albedo_milli);
         let existing_flux = i32::from(region.freshwater_flux_tenths_mm);
         let existing_ice_mass = region.ice_mass_kilotons as f64;
-        let mut snowpack_mm = world.climate.snowpack_mm[index] as f32;
-        let previous_active_layer = world.climate.permafrost_active_cm[index];
-        let baseline_offset = world
-            .climate
-            .temperature_baseline_tenths
-            .get(index)
-            .copied()
-            .unwrap_or(0);
+        let mut snowpack_mm = *climate.snowpack_mm as f32;
+        let previous_active_layer = *climate.permafrost_active_cm;
+        let baseline_offset = *climate.temperature_baseline_tenths;
         let seasonal_temp = temp_tenths + i32::from(baseline_offset);
         let seasonal_temp_clamped =
             seasonal_temp.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
         let mut active_layer_cm = active_layer_depth(seasonal_temp_clamped);
         active_layer_cm = active_layer_cm.clamp(0, 300);
         let thaw_delta = active_layer_cm - previous_active_layer;
-        world.climate.permafrost_active_cm[index] = active_layer_cm;
+        *climate.permafrost_active_cm = active_layer_cm;
         if active_layer_cm != previous_active_layer {
             diff.record_permafrost_active(index, active_layer_cm);
             if thaw_delta > 0 {
                 diff.record_cause(Entry::new(
                     format!("region:{}/permafrost", region.id),
                     Code::PermafrostThaw,
-                    Some(format!("depth_cm={}", active_layer_cm)),
+                    CauseNote::new()
+                        .with("depth_cm", active_layer_cm)
+                        .into_note(),
                 ));
             }
         }
 
         let temp_c = temp_tenths as f32 / 10.0;
+        let freezing_c = params.freezing_point_c(region.pressure_hpa()) as f32;
         let precip_mm = region.precipitation_mm as f32;
-        let cold_degree_days = (-temp_c).max(0.0);
-        let warm_degree_days = temp_c.max(0.0);
+        let cold_degree_days = (freezing_c - temp_c).max(0.0);
+        let warm_degree_days = (temp_c - freezing_c).max(0.0);
 
-        let snow_accum = if temp_c <= 0.0 {
+        let snow_accum = if temp_c <= freezing_c {
             precip_mm * SNOWPACK_CAPTURE_RATIO + cold_degree_days * COLD_DEGREE_DAY_ACCUM_MM
         } else {
             0.0
@@ -118,7 +220,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             .min(snowpack_mm)
             .min(MELT_PULSE_CLAMP_MM as f32);
         snowpack_mm = (snowpack_mm - actual_melt).max(0.0);
-        world.climate.snowpack_mm[index] = snowpack_mm.round() as i32;
+        *climate.snowpack_mm = snowpack_mm.round() as i32;
         let melt_pulse_mm = actual_melt.round() as i32;
         let snowmelt_contribution_mm = actual_melt as f64;
         if melt_pulse_mm > 0 {
@@ -126,7 +228,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_cause(Entry::new(
                 format!("region:{}/snowmelt", region.id),
                 Code::SnowmeltSurge,
-                Some(format!("mm={}", melt_pulse_mm)),
+                CauseNote::new().with("mm", melt_pulse_mm).into_note(),
             ));
             snowmelt_regions += 1;
         }
@@ -140,10 +242,10 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
         let mass_balance = snowfall_input - melt_output;
 
         let latitude_weight = (region.latitude_deg.abs() / 90.0).clamp(0.0, 1.0);
-        let ice_mass_delta = mass_balance * ICE_ACCUM_PER_MM;
+        let ice_mass_delta = mass_balance * params.ice_accum_per_mm;
         let mut next_ice_mass = (existing_ice_mass + ice_mass_delta).max(0.0);
-        if next_ice_mass > ICE_MASS_MAX_KT {
-            next_ice_mass = ICE_MASS_MAX_KT;
+        if next_ice_mass > params.ice_mass_max_kt {
+            next_ice_mass = params.ice_mass_max_kt;
         }
         let next_ice_mass_i32 = next_ice_mass.round() as i32;
 
@@ -154,24 +256,25 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_cause(Entry::new(
                 format!("region:{}/ice", region.id),
                 Code::IceMassVariation,
-                Some(format!("delta_kt={:+.1}", delta_kt)),
+                CauseNote::new()
+                    .with("delta_kt", format_args!("{:+.1}", delta_kt))
+                    .into_note(),
             ));
         }
 
-        let coverage = if next_ice_mass <= 0.0 {
-            0.0
-        } else {
-            (next_ice_mass / ICE_MASS_SATURATION_KT).min(1.0)
-        };
         let albedo_noise = rng.next_signed_unit() * 10.0;
-        let mut raw_albedo = (ALBEDO_FLOOR as f64
-            + (ALBEDO_MAX_I32 - ALBEDO_FLOOR) as f64 * coverage
+        // Snow-free ground keeps the biome's own albedo, matching the climate stage's target.
+        let bare_albedo = climate::biome_albedo_milli(region.biome);
+        let mut raw_albedo = (params.coverage_albedo_milli(next_ice_mass, bare_albedo)
             + latitude_weight * 40.0
             + albedo_noise)
             .round() as i32;
         raw_albedo = raw_albedo.clamp(ALBEDO_FLOOR, ALBEDO_MAX_I32);
         let thaw_bias = (thaw_delta / 5).clamp(-20, 20);
-        let biased_albedo = (raw_albedo - thaw_bias).clamp(ALBEDO_FLOOR, ALBEDO_MAX_I32);
+        let biased_albedo = (raw_albedo - thaw_bias).clamp(
+            params.albedo_floor_milli(region.latitude_deg),
+            ALBEDO_MAX_I32,
+        );
         let mut next_albedo = existing_albedo + (biased_albedo - existing_albedo).clamp(-20, 20);
         next_albedo = next_albedo.clamp(ALBEDO_FLOOR, ALBEDO_MAX_I32);
 
@@ -180,7 +283,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_cause(Entry::new(
                 format!("region:{}/albedo", region.id),
                 Code::AlbedoFeedback,
-                Some(format!("milli={}", next_albedo)),
+                CauseNote::new().with("milli", next_albedo).into_note(),
             ));
         }
 
@@ -188,7 +291,9 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_cause(Entry::new(
                 format!("region:{}/glacier", region.id),
                 Code::GlacierMassBalance,
-                Some(format!("balance_mm={:.1}", mass_balance)),
+                CauseNote::new()
+                    .with("balance_mm", format_args!("{:.1}", mass_balance))
+                    .into_note(),
             ));
         }
 
@@ -203,7 +308,9 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_cause(Entry::new(
                 format!("region:{}/freshwater", region.id),
                 Code::FreshwaterPulse,
-                Some(format!("tenths_mm={}", freshwater_clamped)),
+                CauseNote::new()
+                    .with("tenths_mm", freshwater_clamped)
+                    .into_note(),
             ));
             freshwater_regions += 1;
         }
@@ -222,7 +329,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
         }
     }
 
-    let sea_level_delta_mm = total_melt_mm.round() as i32;
+    let sea_level_delta_mm = (total_melt_mm * params.sea_level_coupling.max(0.0)).round() as i32;
     if sea_level_delta_mm != 0 {
         world
             .climate
@@ -230,7 +337,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
         diff.record_cause(Entry::new(
             "world:sea_level",
             Code::SeaLevelContribution,
-            Some(format!("mm={}", sea_level_delta_mm)),
+            CauseNote::new().with("mm", sea_level_delta_mm).into_note(),
         ));
     }
 
@@ -258,7 +365,7 @@ pub fn update(world: &mut World, rng: &mut Stream) -> Result<KernelRun> {
 mod tests {
     use super::*;
     use crate::rng::Stream;
-    use crate::world::{Hazards, Region, World};
+    use crate::world::{pressure_hpa, Hazards, Region, World};
 
     #[test]
     fn cryosphere_updates_albedo_and_flux() {
@@ -541,6 +648,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn highland_snow_accumulates_just_above_freezing() {
+        let snowpack_after = |elevation_m: i32| {
+            let regions = vec![Region {
+                id: 0,
+                x: 0,
+                y: 0,
+                elevation_m,
+                latitude_deg: 45.0,
+                biome: 0,
+                water: 5_000,
+                soil: 5_000,
+                temperature_tenths_c: 5,
+                precipitation_mm: 200,
+                albedo_milli: 300,
+                freshwater_flux_tenths_mm: 0,
+                ice_mass_kilotons: 0,
+                hazards: Hazards::default(),
+            }];
+            let mut world = World::new(9, 1, 1, regions);
+            let params = CryosphereParams {
+                freezing_rise_c_per_km: 0.5,
+                ..CryosphereParams::default()
+            };
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            update_with_params(&mut world, &mut rng, &params).expect("cryosphere update");
+            world.climate.snowpack_mm[0]
+        };
+
+        assert!(
+            snowpack_after(3_000) > 0,
+            "3 km region at 0.5 °C should gain snow"
+        );
+        assert_eq!(
+            snowpack_after(0),
+            0,
+            "sea-level region at 0.5 °C stays bare"
+        );
+        assert_eq!(
+            CryosphereParams::default().freezing_point_c(pressure_hpa(3_000)),
+            0.0
+        );
+        let rising = CryosphereParams {
+            freezing_rise_c_per_km: 0.5,
+            ..CryosphereParams::default()
+        };
+        assert!((rising.freezing_point_c(pressure_hpa(3_000)) - 1.5).abs() < 1e-9);
+        assert_eq!(rising.freezing_point_c(pressure_hpa(-200)), 0.0);
+    }
+
     #[test]
     fn active_layer_lookup_is_deterministic() {
         let temps = [-360, -240, -120, -10, 80, 180, 320];
@@ -614,4 +771,152 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn ice_free_polar_region_stays_brighter_than_equator() {
+        let bare = |id: u32, latitude_deg: f64, biome: u8| Region {
+            id,
+            x: id,
+            y: 0,
+            elevation_m: 0,
+            latitude_deg,
+            biome,
+            water: 5_000,
+            soil: 5_000,
+            temperature_tenths_c: 250,
+            precipitation_mm: 0,
+            albedo_milli: ALBEDO_FLOOR as u16,
+            freshwater_flux_tenths_mm: 0,
+            ice_mass_kilotons: 0,
+            hazards: Hazards::default(),
+        };
+        let mut world = World::new(13, 2, 1, vec![bare(0, 85.0, 0), bare(1, 0.0, 5)]);
+        for tick in 1..=20 {
+            let mut rng = Stream::from(world.seed, STAGE, tick);
+            let run = update(&mut world, &mut rng).expect("cryosphere update succeeds");
+            crate::reduce::apply(&mut world, run.diff);
+        }
+
+        let params = CryosphereParams::default();
+        let (polar, equator) = (&world.regions[0], &world.regions[1]);
+        assert_eq!(polar.ice_mass_kilotons, 0);
+        assert_eq!(equator.ice_mass_kilotons, 0);
+        assert!(i32::from(polar.albedo_milli) >= params.albedo_floor_milli(85.0));
+        assert!(polar.albedo_milli > equator.albedo_milli + 100);
+        assert_eq!(params.albedo_floor_milli(0.0), ALBEDO_FLOOR);
+        assert_eq!(params.albedo_floor_milli(-90.0), 300);
+    }
+
+    #[test]
+    fn halving_sea_level_coupling_halves_accumulation() {
+        let melt_world = || {
+            let regions = (0..4)
+                .map(|id| Region {
+                    id,
+                    x: id,
+                    y: 0,
+                    elevation_m: 0,
+                    latitude_deg: 75.0,
+                    biome: 0,
+                    water: 5_000,
+                    soil: 5_000,
+                    temperature_tenths_c: 120,
+                    precipitation_mm: 100,
+                    albedo_milli: 500,
+                    freshwater_flux_tenths_mm: 0,
+                    ice_mass_kilotons: 5_000,
+                    hazards: Hazards::default(),
+                })
+                .collect();
+            let mut world = World::new(5, 4, 1, regions);
+            world
+                .climate
+                .snowpack_mm
+                .iter_mut()
+                .for_each(|mm| *mm = 800);
+            world
+        };
+        let accumulate = |coupling: f64| {
+            let mut world = melt_world();
+            let params = CryosphereParams {
+                sea_level_coupling: coupling,
+                ..CryosphereParams::default()
+            };
+            let mut rng = Stream::from(world.seed, STAGE, 2);
+            update_with_params(&mut world, &mut rng, &params).expect("cryosphere update");
+            world.climate.sea_level_equivalent_mm()
+        };
+
+        let full = accumulate(1.0);
+        let half = accumulate(0.5);
+        assert!(full > 1, "melt should raise sea level, got {}", full);
+        assert!(
+            (full - 2 * half).abs() <= 1,
+            "half coupling gave {} against {}",
+            half,
+            full
+        );
+        assert_eq!(accumulate(0.0), 0);
+    }
+
+    #[test]
+    fn ice_free_albedo_holds_the_biome_surface_value() {
+        let desert = climate::biome_albedo_milli(4);
+        let region = Region {
+            id: 0,
+            x: 0,
+            y: 0,
+            elevation_m: 0,
+            latitude_deg: 0.0,
+            biome: 4,
+            water: 5_000,
+            soil: 5_000,
+            temperature_tenths_c: 250,
+            precipitation_mm: 0,
+            albedo_milli: desert as u16,
+            freshwater_flux_tenths_mm: 0,
+            ice_mass_kilotons: 0,
+            hazards: Hazards::default(),
+        };
+        let mut world = World::new(21, 1, 1, vec![region]);
+        for tick in 1..=20 {
+            let mut rng = Stream::from(world.seed, STAGE, tick);
+            let run = update(&mut world, &mut rng).expect("cryosphere update succeeds");
+            crate::reduce::apply(&mut world, run.diff);
+        }
+        // Only noise separates the ice-free target from the biome value; the old
+        // latitude-only target pulled sand down toward the floor.
+        let albedo = i32::from(world.regions[0].albedo_milli);
+        assert!(
+            (albedo - desert).abs() <= 10,
+            "albedo {} drifted from the desert surface value {}",
+            albedo,
+            desert
+        );
+    }
+
+    #[test]
+    fn lower_saturation_reaches_full_coverage_albedo_sooner() {
+        let default_params = CryosphereParams::default();
+        let low_saturation = CryosphereParams {
+            ice_mass_saturation_kt: 20_000.0,
+            ..CryosphereParams::default()
+        };
+        let full_coverage = ALBEDO_MAX_I32 as f64;
+
+        assert_eq!(
+            low_saturation.coverage_albedo_milli(20_000.0, ALBEDO_FLOOR),
+            full_coverage
+        );
+        assert!(default_params.coverage_albedo_milli(20_000.0, ALBEDO_FLOOR) < full_coverage);
+        assert_eq!(
+            default_params.coverage_albedo_milli(ICE_MASS_SATURATION_KT, ALBEDO_FLOOR),
+            full_coverage
+        );
+        assert_eq!(
+            default_params.coverage_albedo_milli(0.0, ALBEDO_FLOOR),
+            ALBEDO_FLOOR as f64
+        );
+        assert_eq!(default_params.coverage_albedo_milli(0.0, 350), 350.0);
+    }
 }
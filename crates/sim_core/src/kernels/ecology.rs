@@ -1,6 +1,8 @@
-use crate::cause::{Code, Entry};
+use crate::cause::{CauseNote, Code, Entry};
 use crate::diff::Diff;
-use crate::fixed::{clamp_hazard_meter, clamp_u16, resource_ratio, SOIL_MAX, WATER_MAX};
+use crate::fixed::{
+    clamp_hazard_meter, commit_resource_delta_saturating, resource_ratio, SOIL_MAX, WATER_MAX,
+};
 use crate::io::frame::Highlight;
 use crate::rng::Stream;
 use crate::schedule::KernelRun;
@@ -13,6 +15,55 @@ pub const STAGE: &str = "kernel:ecology";
 pub const DROUGHT_ALERT_THRESHOLD: u16 = 2_000;
 /// Hazard level required before emitting alerts or highlights for floods.
 pub const FLOOD_ALERT_THRESHOLD: u16 = 600;
+/// Diagnostic key counting regions whose water or soil change was clipped at a bound.
+pub const SATURATION_DIAGNOSTIC: &str = "ecology_saturated_regions";
+
+/// Tunables for the ecology kernel.
+///
+/// Desertification strips soil while the stored drought gauge sits above
+/// `desertification_threshold`, scaling linearly to `desertification_rate` soil units per
+/// tick at a full-scale gauge. The gauge only decays by half per tick once water returns,
+/// so degradation outlasts the drought and the biome drift has to rebuild the soil slowly.
+///
+/// `drought_alert` and `flood_alert` are the gauge levels a region must exceed before it
+/// emits a hazard cause, chronicle line, and highlight. `water_target_blend` is the water
+/// moved per tick for each unit of gap between a region's water ratio and its biome target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EcologyParams {
+    pub desertification_threshold: u16,
+    pub desertification_rate: f64,
+    pub drought_alert: u16,
+    pub flood_alert: u16,
+    pub water_target_blend: f64,
+}
+
+impl Default for EcologyParams {
+    fn default() -> Self {
+        Self {
+            desertification_threshold: DROUGHT_ALERT_THRESHOLD,
+            desertification_rate: 60.0,
+            drought_alert: DROUGHT_ALERT_THRESHOLD,
+            flood_alert: FLOOD_ALERT_THRESHOLD,
+            water_target_blend: 200.0,
+        }
+    }
+}
+
+impl EcologyParams {
+    /// Soil removed this tick for a region whose drought gauge reads `drought`.
+    pub fn desertification_loss(&self, drought: u16) -> i32 {
+        if drought <= self.desertification_threshold || self.desertification_rate <= 0.0 {
+            return 0;
+        }
+        let span = f64::from(
+            WATER_MAX
+                .saturating_sub(self.desertification_threshold)
+                .max(1),
+        );
+        let excess = f64::from(drought - self.desertification_threshold).min(span);
+        (excess / span * self.desertification_rate).round() as i32
+    }
+}
 
 /// Blend the previous hazard gauge toward the new target with a per-tick half-life.
 ///
@@ -71,9 +122,18 @@ fn profile_for_biome(biome: u8) -> BiomeProfile {
 }
 
 pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &EcologyParams::default())
+}
+
+pub fn update_with_params(
+    world: &World,
+    rng: &mut Stream,
+    params: &EcologyParams,
+) -> Result<KernelRun> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
     let mut highlights = Vec::new();
+    let mut saturated_regions = 0;
 
     for (index, region) in world.regions.iter().enumerate() {
         ensure!(
@@ -101,18 +161,40 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
         let water_ratio = resource_ratio(region.water, WATER_MAX);
         let soil_ratio = resource_ratio(region.soil, SOIL_MAX);
 
-        let water_drift = ((profile.water_target - water_ratio) * 200.0).round() as i32;
+        let water_drift =
+            ((profile.water_target - water_ratio) * params.water_target_blend).round() as i32;
         let soil_drift = ((profile.soil_target - soil_ratio) * 150.0).round() as i32;
         let noise = (region_rng.next_signed_unit() * 25.0) as i32;
 
-        let water_delta = (water_drift + noise).clamp(-180, 180);
+        let requested_water = (water_drift + noise).clamp(-180, 180);
         let noise_half = if noise >= 0 {
             noise / 2
         } else {
             (noise - 1) / 2
         };
-        let soil_delta = (soil_drift + noise_half).clamp(-120, 120);
+        let desertification = params.desertification_loss(region.hazards.drought);
+        let requested_soil = (soil_drift + noise_half).clamp(-120, 120) - desertification;
+        if desertification > 0 {
+            diff.record_cause(Entry::new(
+                format!("region:{}/soil", region.id),
+                Code::Desertification,
+                CauseNote::new()
+                    .with("drought", region.hazards.drought)
+                    .with("loss", desertification)
+                    .into_note(),
+            ));
+        }
 
+        // Emit the post-clamp change so clients integrating deltas track the absolute state.
+        let (new_water, water_saturated) =
+            commit_resource_delta_saturating(region.water, requested_water, WATER_MAX);
+        let (new_soil, soil_saturated) =
+            commit_resource_delta_saturating(region.soil, requested_soil, SOIL_MAX);
+        if water_saturated || soil_saturated {
+            saturated_regions += 1;
+        }
+        let water_delta = i32::from(new_water) - i32::from(region.water);
+        let soil_delta = i32::from(new_soil) - i32::from(region.soil);
         if water_delta != 0 {
             diff.record_water_delta(region.index(), water_delta);
         }
@@ -120,10 +202,10 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_soil_delta(region.index(), soil_delta);
         }
 
-        let new_water = clamp_u16(region.water as i32 + water_delta, 0, WATER_MAX);
-        let new_soil = clamp_u16(region.soil as i32 + soil_delta, 0, SOIL_MAX);
-
         let drought_target = WATER_MAX.saturating_sub(new_water);
+        // TODO(agents): rationale - flood targets only see local water; once hydrology routing
+        // exists, add accumulated upstream `freshwater_flux` discharge here and emit a distinct
+        // `MeltwaterFlood` cause so downstream basins flood from distant melt.
         let flood_target = new_water.saturating_sub(WATER_MAX - 1_500);
         let drought_level = blend_hazard(region.hazards.drought, drought_target);
         let flood_level = blend_hazard(region.hazards.flood, flood_target);
@@ -131,7 +213,7 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
             diff.record_hazard(region.index(), drought_level, flood_level);
         }
 
-        if drought_level > DROUGHT_ALERT_THRESHOLD {
+        if drought_level > params.drought_alert {
             diff.record_cause(Entry::new(
                 format!("region:{}/water", region.id),
                 Code::DroughtFlag,
@@ -143,7 +225,7 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
                 "drought",
                 drought_level as f32 / WATER_MAX as f32,
             ));
-        } else if flood_level > FLOOD_ALERT_THRESHOLD {
+        } else if flood_level > params.flood_alert {
             diff.record_cause(Entry::new(
                 format!("region:{}/water", region.id),
                 Code::FloodFlag,
@@ -166,6 +248,10 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
         }
     }
 
+    if saturated_regions > 0 {
+        diff.record_diagnostic(SATURATION_DIAGNOSTIC, saturated_regions);
+    }
+
     Ok(KernelRun {
         diff,
         chronicle,
@@ -209,6 +295,41 @@ mod tests {
         assert!(water_delta.is_positive());
     }
 
+    #[test]
+    fn saturated_regions_are_counted_and_stay_at_the_bound() {
+        let region = |id: u32, water: u16| crate::world::Region {
+            id,
+            x: id,
+            y: 0,
+            elevation_m: 100,
+            latitude_deg: 0.0,
+            biome: 5,
+            water,
+            soil: 5_000,
+            temperature_tenths_c: 0,
+            precipitation_mm: 0,
+            albedo_milli: 350,
+            freshwater_flux_tenths_mm: 0,
+            ice_mass_kilotons: 0,
+            hazards: crate::world::Hazards::default(),
+        };
+        let mut world =
+            crate::world::World::new(1, 2, 1, vec![region(0, WATER_MAX), region(1, 5_000)]);
+        // No drift toward the biome target, so only the noise moves water; for this seed it
+        // pushes the full region upward.
+        let params = EcologyParams {
+            water_target_blend: 0.0,
+            ..EcologyParams::default()
+        };
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let diff = update_with_params(&world, &mut rng, &params).unwrap().diff;
+
+        assert_eq!(diff.diagnostics.get(SATURATION_DIAGNOSTIC), Some(&1));
+        assert!(diff.water.iter().all(|delta| delta.region != 0));
+        reduce::apply(&mut world, diff);
+        assert_eq!(world.regions[0].water, WATER_MAX);
+    }
+
     proptest! {
         #[test]
         fn ecology_diff_keeps_resources_within_bounds(
@@ -250,13 +371,53 @@ mod tests {
                 .first()
                 .map(|delta| delta.delta)
                 .unwrap_or(0);
-            let next_water = clamp_u16(water as i32 + water_delta, 0, WATER_MAX);
-            let next_soil = clamp_u16(soil as i32 + soil_delta, 0, SOIL_MAX);
-            prop_assert!(next_water <= WATER_MAX);
-            prop_assert!(next_soil <= SOIL_MAX);
-            prop_assert!(i32::from(next_water) >= 0);
-            prop_assert!(i32::from(next_soil) >= 0);
+            // Deltas are emitted post-clamp, so integrating them never leaves the bounds.
+            let next_water = i32::from(water) + water_delta;
+            let next_soil = i32::from(soil) + soil_delta;
+            prop_assert!((0..=i32::from(WATER_MAX)).contains(&next_water));
+            prop_assert!((0..=i32::from(SOIL_MAX)).contains(&next_soil));
+        }
+    }
+
+    #[test]
+    fn drought_regions_raise_proportional_hazard_highlights() {
+        use crate::world::{Hazards, Region, World};
+        let parched = |id: u32, drought: u16| {
+            Region::sample(id, id, 0)
+                .with_biome(4)
+                .with_water(0)
+                .with_hazards(Hazards { drought, flood: 0 })
+        };
+        let world = World::new(
+            3,
+            3,
+            1,
+            vec![
+                parched(0, 9_000),
+                parched(1, 3_000),
+                Region::sample(2, 2, 0).with_biome(5).with_water(9_000),
+            ],
+        );
+        let mut rng = Stream::from(world.seed, STAGE, 1);
+        let run = update(&world, &mut rng).unwrap();
+
+        assert_eq!(run.highlights.len(), 2);
+        for highlight in &run.highlights {
+            assert_eq!(highlight.kind, "hazard_flag");
+            assert_eq!(highlight.info.kind, "drought");
+            let gauge = run
+                .diff
+                .hazards
+                .iter()
+                .find(|hazard| hazard.region == highlight.region)
+                .expect("drought gauge recorded")
+                .drought;
+            assert!(gauge > DROUGHT_ALERT_THRESHOLD);
+            assert_eq!(highlight.info.level, gauge as f32 / WATER_MAX as f32);
         }
+        let (severe, moderate) = (&run.highlights[0], &run.highlights[1]);
+        assert_eq!((severe.region, moderate.region), (0, 1));
+        assert!(severe.info.level > moderate.info.level);
     }
 
     #[test]
@@ -316,6 +477,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sustained_drought_degrades_soil() {
+        let run_ticks = |drought: u16| {
+            let mut world = world::World::new(
+                3,
+                1,
+                1,
+                vec![world::Region {
+                    id: 0,
+                    x: 0,
+                    y: 0,
+                    elevation_m: 100,
+                    latitude_deg: 0.0,
+                    biome: 2,
+                    water: 5_500,
+                    soil: 5_500,
+                    temperature_tenths_c: 0,
+                    precipitation_mm: 0,
+                    albedo_milli: 350,
+                    freshwater_flux_tenths_mm: 0,
+                    ice_mass_kilotons: 0,
+                    hazards: world::Hazards { drought, flood: 0 },
+                }],
+            );
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            let mut desertified = false;
+            for _ in 0..6 {
+                let run = update(&world, &mut rng).expect("ecology update");
+                desertified |= run
+                    .diff
+                    .causes
+                    .iter()
+                    .any(|entry| entry.code == Code::Desertification);
+                reduce::apply(&mut world, run.diff);
+                world.regions[0].hazards.drought = drought;
+            }
+            (world.regions[0].soil, desertified)
+        };
+
+        let (parched_soil, parched_flagged) = run_ticks(9_000);
+        let (baseline_soil, baseline_flagged) = run_ticks(0);
+        assert!(parched_flagged);
+        assert!(!baseline_flagged);
+        assert!(
+            parched_soil + 200 < baseline_soil,
+            "parched {} vs baseline {}",
+            parched_soil,
+            baseline_soil
+        );
+        assert_eq!(EcologyParams::default().desertification_loss(WATER_MAX), 60);
+        assert_eq!(EcologyParams::default().desertification_loss(1_000), 0);
+    }
+
+    #[test]
+    fn lower_flood_alert_flags_moderately_wet_regions() {
+        let world = world::World::new(
+            3,
+            1,
+            1,
+            vec![world::Region::sample(0, 0, 0)
+                .with_biome(5)
+                .with_water(8_500)
+                .with_hazards(world::Hazards {
+                    drought: 0,
+                    flood: 1_000,
+                })],
+        );
+        let flood_causes = |params: &EcologyParams| {
+            let mut rng = Stream::from(world.seed, STAGE, 1);
+            let run = update_with_params(&world, &mut rng, params).expect("ecology update");
+            let gauge = run
+                .diff
+                .hazards
+                .first()
+                .expect("flood gauge recorded")
+                .flood;
+            let flagged = run
+                .diff
+                .causes
+                .iter()
+                .filter(|entry| entry.code == Code::FloodFlag)
+                .count();
+            (gauge, flagged, run.highlights.len())
+        };
+
+        let (gauge, default_flags, default_highlights) = flood_causes(&EcologyParams::default());
+        assert!(
+            gauge > 300 && gauge <= FLOOD_ALERT_THRESHOLD,
+            "gauge {}",
+            gauge
+        );
+        assert_eq!((default_flags, default_highlights), (0, 0));
+
+        let sensitive = EcologyParams {
+            flood_alert: 300,
+            ..EcologyParams::default()
+        };
+        assert_eq!(flood_causes(&sensitive), (gauge, 1, 1));
+    }
+
     fn find_zero_noise_seed() -> Option<u64> {
         for seed in 0..10_000 {
             let stream = Stream::from(seed, STAGE, 1);
@@ -0,0 +1,173 @@
+//! Exogenous global temperature forcing, e.g. an emissions scenario.
+//!
+//! A [`ForcingSeries`] maps ticks to a global offset in tenths of °C. Each tick the
+//! stage moves every region's `temperature_baseline_tenths` by the change in offset
+//! since the last tick, so the baseline carries exactly the scheduled offset on top of
+//! whatever the albedo coupler has accumulated.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::diff::Diff;
+use crate::world::World;
+
+pub const STAGE: &str = "kernel:forcing";
+
+/// Diagnostic key carrying the offset applied this tick.
+pub const FORCING_DIAGNOSTIC: &str = "forcing_offset_tenths";
+
+/// Piecewise-constant `tick -> offset_tenths` schedule.
+///
+/// The offset at tick `t` is the value listed for the greatest tick `<= t`, and zero
+/// before the first listed tick.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForcingSeries {
+    offsets: BTreeMap<u64, i32>,
+}
+
+impl ForcingSeries {
+    /// Load a `tick,offset_tenths` CSV from disk.
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open forcing file {:?}", path))?;
+        Self::from_reader(file).with_context(|| format!("invalid forcing file {:?}", path))
+    }
+
+    /// Parse `tick,offset_tenths` rows; blank lines, `#` comments, and a leading header
+    /// row are skipped.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let mut offsets = BTreeMap::new();
+        let mut first_row = true;
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let row = line.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+            let (tick, offset) = row
+                .split_once(',')
+                .with_context(|| format!("line {}: expected tick,offset_tenths", number + 1))?;
+            let (tick, offset) = (tick.trim(), offset.trim());
+            let header = first_row && tick.parse::<u64>().is_err();
+            first_row = false;
+            if header {
+                continue;
+            }
+            let tick = tick
+                .parse::<u64>()
+                .with_context(|| format!("line {}: invalid tick {:?}", number + 1, tick))?;
+            let offset = offset
+                .parse::<i32>()
+                .with_context(|| format!("line {}: invalid offset {:?}", number + 1, offset))?;
+            offsets.insert(tick, offset);
+        }
+        Ok(Self { offsets })
+    }
+
+    pub fn from_offsets<I: IntoIterator<Item = (u64, i32)>>(offsets: I) -> Self {
+        Self {
+            offsets: offsets.into_iter().collect(),
+        }
+    }
+
+    /// Scheduled offset in tenths of °C for `tick`.
+    pub fn offset_at(&self, tick: u64) -> i32 {
+        self.offsets
+            .range(..=tick)
+            .next_back()
+            .map_or(0, |(_, offset)| *offset)
+    }
+}
+
+/// Record every baseline shifted by the change in scheduled offset since the last tick,
+/// along with the new offset for the reducer to commit.
+///
+/// A world without a forcing series yields an empty diff.
+pub fn update(world: &World, tick: u64) -> Diff {
+    let mut diff = Diff::default();
+    let Some(series) = world.forcing.as_ref() else {
+        return diff;
+    };
+    let offset = series.offset_at(tick);
+    let delta = offset - world.climate.applied_forcing_tenths;
+    diff.forcing_offset_tenths = Some(offset);
+    diff.record_diagnostic(FORCING_DIAGNOSTIC, offset);
+    if delta == 0 {
+        return diff;
+    }
+
+    for index in 0..world.regions.len() {
+        let baseline = world
+            .climate
+            .temperature_baseline_tenths
+            .get(index)
+            .copied()
+            .map_or(0, i32::from);
+        diff.record_temperature_baseline(index, baseline + delta);
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::seed::build_world;
+    use crate::testing::test_seed;
+    use crate::tick_once;
+
+    #[test]
+    fn csv_parses_with_header_and_holds_last_value() {
+        let csv = "tick,offset_tenths\n# ramp\n1,10\n\n4, 25\n";
+        let series = ForcingSeries::from_reader(csv.as_bytes()).expect("csv parses");
+        assert_eq!(series, ForcingSeries::from_offsets([(1, 10), (4, 25)]));
+        assert_eq!(series.offset_at(0), 0);
+        assert_eq!(series.offset_at(3), 10);
+        assert_eq!(series.offset_at(9), 25);
+        assert!(ForcingSeries::from_reader("1,warm\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn applied_offset_is_committed_by_the_reducer() {
+        let mut world = build_world(&test_seed("forcing_commit", 2, 1), None);
+        world.forcing = Some(ForcingSeries::from_offsets([(1, 30)]));
+
+        let diff = update(&world, 1);
+        assert_eq!(diff.forcing_offset_tenths, Some(30));
+        assert_eq!(world.climate.applied_forcing_tenths, 0);
+        crate::reduce::apply(&mut world, diff);
+        assert_eq!(world.climate.applied_forcing_tenths, 30);
+
+        let steady = update(&world, 2);
+        assert_eq!(steady.forcing_offset_tenths, Some(30));
+        assert!(steady.temperature_baseline.is_empty());
+    }
+
+    #[test]
+    fn positive_forcing_warms_every_region_by_its_offset() {
+        let seed = test_seed("forcing", 4, 3);
+        let mut unforced = build_world(&seed, None);
+        let mut forced = unforced.clone();
+        forced.forcing = Some(ForcingSeries::from_offsets([(1, 50)]));
+        let world_seed = unforced.seed;
+        for tick in 1..=3 {
+            tick_once(&mut unforced, world_seed, tick).expect("unforced tick");
+            let (diff, _, _) = tick_once(&mut forced, world_seed, tick).expect("forced tick");
+            assert_eq!(diff.diagnostics.get(FORCING_DIAGNOSTIC), Some(&50));
+        }
+
+        for (warm, base) in forced.regions.iter().zip(&unforced.regions) {
+            let warming = i32::from(warm.temperature_tenths_c - base.temperature_tenths_c);
+            assert!(
+                (45..=55).contains(&warming),
+                "region {} warmed {}",
+                warm.id,
+                warming
+            );
+        }
+        assert_eq!(forced.climate.applied_forcing_tenths, 50);
+    }
+}
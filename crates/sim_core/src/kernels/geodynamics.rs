@@ -2,24 +2,68 @@ use anyhow::{ensure, Result};
 
 use crate::cause::{Code, Entry};
 use crate::diff::Diff;
+use crate::fixed::clamp_elevation;
 use crate::rng::Stream;
 use crate::schedule::KernelRun;
 use crate::world::World;
 
 pub const STAGE: &str = "kernel:geodynamics";
 
-const EVENT_DENOMINATOR: u64 = 1_000;
-const MIN_ELEVATION_M: i32 = -1_000; // TODO(agents): rationale — extend seed clamp for bathymetry adjustments.
-const MAX_ELEVATION_M: i32 = 4_000; // TODO(agents): rationale — allow moderate uplift beyond seed cap.
+/// Eruption odds and volcanic episode clustering.
+///
+/// Each region erupts with probability `1 / event_denominator` per tick. With
+/// `episode_ticks > 0`, an eruption opens an episode during which the region's odds are
+/// multiplied by up to `episode_rate_multiplier`, fading linearly back to baseline over
+/// the episode so eruptions arrive in bursts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeodynamicsParams {
+    pub event_denominator: u64,
+    pub episode_ticks: u16,
+    pub episode_rate_multiplier: f64,
+}
 
-const NEIGHBOR_OFFSETS: &[(i32, i32)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+impl Default for GeodynamicsParams {
+    fn default() -> Self {
+        Self {
+            event_denominator: 1_000,
+            episode_ticks: 0,
+            episode_rate_multiplier: 10.0,
+        }
+    }
+}
+
+impl GeodynamicsParams {
+    /// Effective denominator for a region with `remaining` episode ticks left.
+    fn denominator(&self, remaining: u16) -> u64 {
+        let base = self.event_denominator.max(1);
+        if self.episode_ticks == 0 || remaining == 0 {
+            return base;
+        }
+        let fade = f64::from(remaining.min(self.episode_ticks)) / f64::from(self.episode_ticks);
+        let boost = 1.0 + (self.episode_rate_multiplier - 1.0).max(0.0) * fade;
+        ((base as f64 / boost).round() as u64).max(1)
+    }
+}
 
 pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &GeodynamicsParams::default())
+}
+
+/// With clustering enabled, the diff carries every region's episode counter for the
+/// reducer to commit.
+pub fn update_with_params(
+    world: &World,
+    rng: &mut Stream,
+    params: &GeodynamicsParams,
+) -> Result<KernelRun> {
     let mut diff = Diff::default();
     let mut chronicle = Vec::new();
-
-    let width = world.width as i32;
-    let height = world.height as i32;
+    let clustering = params.episode_ticks > 0;
+    let mut episodes = Vec::new();
+    if clustering {
+        episodes = world.climate.volcanic_episode.clone();
+        episodes.resize(world.regions.len(), 0);
+    }
 
     for (index, region) in world.regions.iter().enumerate() {
         ensure!(
@@ -30,9 +74,19 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
         );
 
         let mut region_rng = rng.derive(region.index() as u64);
-        if region_rng.next_u64() % EVENT_DENOMINATOR != 0 {
+        let remaining = if clustering {
+            let remaining = episodes[index];
+            episodes[index] = remaining.saturating_sub(1);
+            remaining
+        } else {
+            0
+        };
+        if region_rng.next_u64() % params.denominator(remaining) != 0 {
             continue;
         }
+        if clustering {
+            episodes[index] = params.episode_ticks;
+        }
 
         let uplift = region_rng.next_u64() & 1 == 0;
         let magnitude_m = (region_rng.next_f64() * 90.0 + 10.0).round() as i32;
@@ -54,18 +108,11 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
 
         let neighbor_delta = (primary_delta / 2).clamp(-50, 50);
         if neighbor_delta != 0 {
-            for (dx, dy) in NEIGHBOR_OFFSETS {
-                let nx = region.x as i32 + dx;
-                let ny = region.y as i32 + dy;
-                if nx < 0 || nx >= width || ny < 0 || ny >= height {
-                    continue;
-                }
-                let neighbor_index = (ny * width + nx) as usize;
-                if let Some(neighbor) = world.regions.get(neighbor_index) {
-                    let neighbor_new =
-                        clamp_elevation(neighbor.elevation_m.saturating_add(neighbor_delta));
-                    diff.record_elevation(neighbor_index, neighbor_new);
-                }
+            for neighbor_index in world.neighbors4(region.index()) {
+                let neighbor = &world.regions[neighbor_index];
+                let neighbor_new =
+                    clamp_elevation(neighbor.elevation_m.saturating_add(neighbor_delta));
+                diff.record_elevation(neighbor_index, neighbor_new);
             }
         }
 
@@ -86,6 +133,10 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
         ));
     }
 
+    if clustering {
+        diff.volcanic_episode = Some(episodes);
+    }
+
     Ok(KernelRun {
         diff,
         chronicle,
@@ -93,10 +144,6 @@ pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
     })
 }
 
-fn clamp_elevation(value: i32) -> i32 {
-    value.clamp(MIN_ELEVATION_M, MAX_ELEVATION_M)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,4 +201,51 @@ mod tests {
         assert!(!diff.elevation.is_empty());
         assert!(!chronicle.is_empty());
     }
+
+    #[test]
+    fn clustering_makes_follow_up_eruptions_more_likely() {
+        const WINDOW: u64 = 5;
+        let follow_up_rate = |params: &GeodynamicsParams| {
+            let mut world = test_world();
+            let eruptions: Vec<u64> = (1..=20_000)
+                .filter(|&tick| {
+                    let mut rng = Stream::from(world.seed, STAGE, tick);
+                    let run = update_with_params(&world, &mut rng, params)
+                        .expect("geodynamics update succeeds");
+                    let erupted = !run.diff.elevation.is_empty();
+                    // Commit only the episode counters so the terrain stays fixed.
+                    world.climate.volcanic_episode = run.diff.volcanic_episode.unwrap_or_default();
+                    erupted
+                })
+                .collect();
+            let followed = eruptions
+                .windows(2)
+                .filter(|pair| pair[1] - pair[0] <= WINDOW)
+                .count();
+            (followed as f64 / eruptions.len() as f64, world)
+        };
+
+        let baseline = GeodynamicsParams {
+            event_denominator: 100,
+            ..GeodynamicsParams::default()
+        };
+        let clustered = GeodynamicsParams {
+            episode_ticks: WINDOW as u16,
+            episode_rate_multiplier: 20.0,
+            ..baseline.clone()
+        };
+        let (baseline_rate, quiet_world) = follow_up_rate(&baseline);
+        let (clustered_rate, _) = follow_up_rate(&clustered);
+        assert!(
+            clustered_rate > baseline_rate * 3.0,
+            "clustered {} vs baseline {}",
+            clustered_rate,
+            baseline_rate
+        );
+        assert!(quiet_world
+            .climate
+            .volcanic_episode
+            .iter()
+            .all(|&ticks| ticks == 0));
+    }
 }
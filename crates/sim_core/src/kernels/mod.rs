@@ -5,4 +5,81 @@ pub mod climate_diag;
 pub mod coupler;
 pub mod cryosphere;
 pub mod ecology;
+pub mod forcing;
 pub mod geodynamics;
+pub mod storms;
+
+use crate::schedule::Schedule;
+
+/// Introspection record for one stage of the standard schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KernelInfo {
+    /// The stage's `STAGE` label, as used for RNG streams and logs.
+    pub stage: &'static str,
+    /// Position in the tick order, starting at 0.
+    pub order: usize,
+    /// Whether the stage varies across ensemble members.
+    pub per_member: bool,
+}
+
+/// Every stage [`crate::tick_once`] runs, in execution order.
+pub fn registry() -> Vec<KernelInfo> {
+    Schedule::standard()
+        .stages()
+        .iter()
+        .enumerate()
+        .map(|(order, stage)| KernelInfo {
+            stage: stage.label,
+            order,
+            per_member: stage.per_member,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn registry_lists_every_stage_label_once() {
+        let registry = registry();
+        let labels: Vec<&str> = registry.iter().map(|info| info.stage).collect();
+        assert_eq!(
+            labels,
+            vec![
+                astronomy::STAGE,
+                geodynamics::STAGE,
+                forcing::STAGE,
+                atmosphere::STAGE,
+                storms::STAGE,
+                cryosphere::STAGE,
+                coupler::STAGE,
+                climate_diag::STAGE,
+                climate::CORE_STAGE,
+                ecology::STAGE,
+            ]
+        );
+        assert_eq!(labels.iter().collect::<BTreeSet<_>>().len(), labels.len());
+        assert!(registry
+            .iter()
+            .enumerate()
+            .all(|(index, info)| info.order == index));
+
+        let per_member: Vec<&str> = registry
+            .iter()
+            .filter(|info| info.per_member)
+            .map(|info| info.stage)
+            .collect();
+        assert_eq!(
+            per_member,
+            vec![
+                geodynamics::STAGE,
+                atmosphere::STAGE,
+                storms::STAGE,
+                cryosphere::STAGE,
+            ]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Mid-latitude storm tracks.
+//!
+//! Storm centres persist in [`ClimateState::storm_tracks`] and drift one column east per
+//! tick. A storm that runs out of lifetime, or off the east edge of a
+//! [`WrapMode::Plane`] grid, dissipates; cylinder grids wrap it around instead. New storms
+//! are born on mid-latitude rows from the stage RNG, so genesis follows the
+//! `(seed, stage, tick)` stream and varies across ensemble members.
+//!
+//! [`ClimateState::storm_tracks`]: crate::world::ClimateState::storm_tracks
+
+use anyhow::{ensure, Result};
+
+use crate::cause::{CauseNote, Code, Entry};
+use crate::diff::Diff;
+use crate::fixed::PRECIP_MAX_MM;
+use crate::kernels::atmosphere::{HADLEY_LATITUDE_MAX, POLAR_REGIME_LATITUDE_MIN};
+use crate::rng::{stream_label, Stream};
+use crate::schedule::KernelRun;
+use crate::world::{StormCell, World, WrapMode};
+
+pub const STAGE: &str = "kernel:storms";
+
+/// Matches the atmosphere kernel's `precip_extreme` clamp.
+const PRECIP_EXTREME_CLAMP: i32 = 2_000;
+
+/// Storm count and strength.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StormParams {
+    /// Number of concurrent storm centres; `0` disables the pass.
+    pub count: u32,
+    /// Ticks a newly born storm lives, including its genesis tick; must be at least 1.
+    pub lifetime_ticks: u32,
+    /// Precipitation added at a storm centre, in mm per tick.
+    pub intensity_mm: i32,
+    /// Chebyshev radius in cells over which the boost falls off as `1 / (1 + distance)`.
+    pub radius: u32,
+    /// Ceiling for boosted precipitation in mm; match the reducer's precipitation limit.
+    pub precip_max_mm: u16,
+}
+
+impl Default for StormParams {
+    fn default() -> Self {
+        Self {
+            count: 2,
+            lifetime_ticks: 8,
+            intensity_mm: 250,
+            radius: 1,
+            precip_max_mm: PRECIP_MAX_MM,
+        }
+    }
+}
+
+/// Storm centres for this tick: last tick's survivors one column east, then new storms
+/// drawn from `rng` until `params.count` are live.
+///
+/// Worlds without a mid-latitude (30–60°) row have no storm track and yield no centres.
+pub fn advance_tracks(world: &World, rng: &mut Stream, params: &StormParams) -> Vec<StormCell> {
+    let mut rows: Vec<u32> = world
+        .regions_in_belt(HADLEY_LATITUDE_MAX, POLAR_REGIME_LATITUDE_MIN)
+        .map(|(_, region)| region.y)
+        .collect();
+    rows.dedup();
+    if rows.is_empty() || world.width == 0 || params.count == 0 {
+        return Vec::new();
+    }
+
+    let wrap = world.wrap == WrapMode::Cylinder;
+    let mut tracks: Vec<StormCell> = world
+        .climate
+        .storm_tracks
+        .iter()
+        .filter(|cell| cell.ticks_left > 0)
+        .filter_map(|cell| {
+            let x = cell.x + 1;
+            let x = match (x < world.width, wrap) {
+                (true, _) => x,
+                (false, true) => 0,
+                (false, false) => return None,
+            };
+            Some(StormCell {
+                x,
+                y: cell.y,
+                ticks_left: cell.ticks_left - 1,
+            })
+        })
+        .take(params.count as usize)
+        .collect();
+
+    let mut genesis = rng.derive(stream_label("storms.genesis"));
+    while tracks.len() < params.count as usize {
+        let y = rows[genesis.next_below(rows.len() as u64) as usize];
+        let x = genesis.next_below(u64::from(world.width)) as u32;
+        tracks.push(StormCell {
+            x,
+            y,
+            ticks_left: params.lifetime_ticks.saturating_sub(1),
+        });
+    }
+    tracks
+}
+
+pub fn update(world: &World, rng: &mut Stream) -> Result<KernelRun> {
+    update_with_params(world, rng, &StormParams::default())
+}
+
+/// Advance the storm tracks, boost precipitation around each centre, and flag it as
+/// `precip_extreme`.
+///
+/// Overlapping storms add their boosts. The flagged extreme replaces any rolling anomaly
+/// the atmosphere recorded for the same region this tick. Fails when
+/// `params.lifetime_ticks` is 0, since every storm lives through its genesis tick.
+pub fn update_with_params(
+    world: &World,
+    rng: &mut Stream,
+    params: &StormParams,
+) -> Result<KernelRun> {
+    ensure!(
+        params.lifetime_ticks > 0,
+        "storm lifetime_ticks must be at least 1"
+    );
+    let tracks = advance_tracks(world, rng, params);
+    let mut diff = Diff::default();
+    if tracks != world.climate.storm_tracks {
+        diff.storm_tracks = Some(tracks.clone());
+    }
+    if tracks.is_empty() || params.intensity_mm <= 0 {
+        return Ok(KernelRun::new(diff));
+    }
+
+    let width = world.width as usize;
+    let height = world.height as usize;
+    let radius = params.radius as usize;
+    let wrap = world.wrap == WrapMode::Cylinder;
+    let mut boost = vec![0_i32; world.regions.len()];
+
+    for (storm, cell) in tracks.iter().enumerate() {
+        let (cx, cy) = (cell.x as usize, cell.y as usize);
+        for y in cy.saturating_sub(radius)..=(cy + radius).min(height - 1) {
+            for x in 0..width {
+                let dx = x.abs_diff(cx);
+                let dx = if wrap { dx.min(width - dx) } else { dx };
+                let distance = dx.max(y.abs_diff(cy));
+                if distance > radius {
+                    continue;
+                }
+                let index = y * width + x;
+                if let Some(slot) = boost.get_mut(index) {
+                    *slot += params.intensity_mm / (1 + distance as i32);
+                }
+            }
+        }
+        let center = cy * width + cx;
+        diff.record_cause(Entry::new(
+            format!("region:{}/precip", center),
+            Code::StormTrack,
+            CauseNote::new()
+                .with("storm", storm)
+                .with("x", cx)
+                .with("y", cy)
+                .with("intensity_mm", params.intensity_mm)
+                .into_note(),
+        ));
+    }
+
+    for (index, (region, added)) in world.regions.iter().zip(boost).enumerate() {
+        if added <= 0 {
+            continue;
+        }
+        let precip =
+            (i32::from(region.precipitation_mm) + added).min(i32::from(params.precip_max_mm));
+        diff.record_precipitation(index, precip);
+        diff.record_precip_extreme(index, added.min(PRECIP_EXTREME_CLAMP));
+    }
+
+    Ok(KernelRun::new(diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::latitude_from_row;
+
+    fn storm_world(seed: u64) -> World {
+        World::from_fields(seed, 8, 8, &[0; 64], &[0; 64], latitude_from_row).expect("world builds")
+    }
+
+    fn stage_rng(world: &World, tick: u64) -> Stream {
+        Stream::with_kind(world.rng_kind, world.seed, STAGE, tick)
+    }
+
+    #[test]
+    fn storms_drift_east_along_mid_latitude_rows() {
+        let mut world = storm_world(41);
+        let params = StormParams::default();
+        let born = advance_tracks(&world, &mut stage_rng(&world, 1), &params);
+        assert_eq!(born.len(), params.count as usize);
+        for cell in &born {
+            assert!(
+                [1, 2, 5, 6].contains(&cell.y),
+                "row {} is not mid-latitude",
+                cell.y
+            );
+            assert_eq!(cell.ticks_left, params.lifetime_ticks - 1);
+        }
+
+        world.climate.storm_tracks = vec![StormCell {
+            x: 2,
+            y: 5,
+            ticks_left: 3,
+        }];
+        let run = update_with_params(
+            &world,
+            &mut stage_rng(&world, 2),
+            &StormParams { count: 1, ..params },
+        )
+        .expect("storms run");
+        let drifted = StormCell {
+            x: 3,
+            y: 5,
+            ticks_left: 2,
+        };
+        assert_eq!(run.diff.storm_tracks, Some(vec![drifted]));
+        let targets: Vec<&str> = run
+            .diff
+            .causes
+            .iter()
+            .filter(|entry| entry.code == Code::StormTrack)
+            .map(|entry| entry.target.as_str())
+            .collect();
+        assert_eq!(targets, ["region:43/precip"]);
+        let extreme = run
+            .diff
+            .precip_extreme
+            .iter()
+            .find(|value| value.region == 43)
+            .expect("centre flagged");
+        assert!(extreme.value >= params.intensity_mm);
+    }
+
+    #[test]
+    fn storms_leave_plane_grids_and_wrap_on_cylinders() {
+        let mut world = storm_world(5);
+        world.climate.storm_tracks = vec![StormCell {
+            x: 7,
+            y: 2,
+            ticks_left: 4,
+        }];
+        let params = StormParams {
+            count: 1,
+            radius: 0,
+            ..StormParams::default()
+        };
+
+        let plane = advance_tracks(&world, &mut stage_rng(&world, 3), &params);
+        assert_eq!(plane.len(), 1);
+        assert_eq!(plane[0].ticks_left, params.lifetime_ticks - 1);
+
+        world.wrap = WrapMode::Cylinder;
+        let cylinder = advance_tracks(&world, &mut stage_rng(&world, 3), &params);
+        assert_eq!(
+            cylinder,
+            [StormCell {
+                x: 0,
+                y: 2,
+                ticks_left: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn storm_footprints_wrap_only_on_cylinders() {
+        let mut world = storm_world(5);
+        world.climate.storm_tracks = vec![StormCell {
+            x: 6,
+            y: 2,
+            ticks_left: 4,
+        }];
+        let params = StormParams {
+            count: 1,
+            ..StormParams::default()
+        };
+        // The centre drifts to the east edge (x = 7); x = 0 on the same row is across the seam.
+        let seam = 2 * 8;
+        let boosts_seam = |world: &World| {
+            update_with_params(world, &mut stage_rng(world, 3), &params)
+                .expect("storms run")
+                .diff
+                .precipitation
+                .iter()
+                .any(|value| value.region == seam)
+        };
+        assert!(!boosts_seam(&world));
+        world.wrap = WrapMode::Cylinder;
+        assert!(boosts_seam(&world));
+    }
+
+    #[test]
+    fn storms_replay_identically_and_vary_by_ensemble_member() {
+        let world = storm_world(7);
+        let first = update(&world, &mut stage_rng(&world, 12)).expect("first run");
+        let second = update(&world, &mut stage_rng(&world, 12)).expect("second run");
+        assert_eq!(first.diff.causes, second.diff.causes);
+        assert_eq!(first.diff.precipitation, second.diff.precipitation);
+        assert_eq!(first.diff.precip_extreme, second.diff.precip_extreme);
+        assert!(!first.diff.precipitation.is_empty());
+
+        let params = StormParams::default();
+        let births: Vec<Vec<StormCell>> = (0..4)
+            .map(|member| {
+                let mut rng = stage_rng(&world, 12).for_ensemble_member(member);
+                advance_tracks(&world, &mut rng, &params)
+            })
+            .collect();
+        assert!(births.iter().any(|cells| cells != &births[0]));
+    }
+
+    #[test]
+    fn worlds_without_mid_latitudes_have_no_storms() {
+        let world =
+            World::from_fields(3, 4, 2, &[0; 8], &[0; 8], |_, _| 10.0).expect("world builds");
+        let mut rng = stage_rng(&world, 1);
+        assert!(advance_tracks(&world, &mut rng, &StormParams::default()).is_empty());
+    }
+
+    #[test]
+    fn zero_lifetime_is_rejected() {
+        let world = storm_world(5);
+        let params = StormParams {
+            lifetime_ticks: 0,
+            ..StormParams::default()
+        };
+        let err = update_with_params(&world, &mut stage_rng(&world, 1), &params)
+            .expect_err("zero lifetime fails");
+        assert!(err.to_string().contains("lifetime_ticks"), "{}", err);
+    }
+}
@@ -1,24 +1,53 @@
+pub mod biome;
 pub mod cause;
+pub mod detmath;
+pub mod diag;
 pub mod diff;
 pub mod fixed;
 pub mod io;
 pub mod kernels;
 pub mod reduce;
+pub mod regrid;
 pub mod rng;
 pub mod schedule;
+pub mod testing;
 pub mod world;
 
-use anyhow::{ensure, Result};
+use std::time::Instant;
+
+use anyhow::{ensure, Context, Result};
 use diff::Diff;
 use io::frame::Highlight;
 use kernels::{
-    astronomy, atmosphere, climate, climate_diag, coupler, cryosphere, ecology, geodynamics,
+    astronomy, atmosphere, climate, climate_diag, coupler, cryosphere, ecology, forcing,
+    geodynamics, storms,
 };
-use reduce::apply;
+use reduce::{apply_in_range, apply_strict_in_range, ReduceLimits};
 use rng::{stream_label, Stream};
-use schedule::run_kernel;
+use schedule::{run_uncommitted, KernelRun, Schedule, StageId, StageTiming};
 use world::World;
 
+/// Run-wide switches that change how a tick treats its kernels' output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SimParams {
+    /// Fail the tick when a stage diff holds any value the reducer would clamp, or when the
+    /// atmosphere would clamp a humidity or temperature itself, instead of clamping it
+    /// silently. Meant for model development; off by default.
+    pub strict: bool,
+    /// Omit insolation and tide envelope entries that match the previous tick's values
+    /// (see [`astronomy::AstronomyParams::only_changed`]).
+    pub sparse_astronomy: bool,
+    /// Bounds for surface temperatures and their baselines, applied by the atmosphere and
+    /// the reducer alike; defaults to ±50 °C.
+    pub temperature_range: fixed::TemperatureRange,
+    /// Per-regime precipitation ceilings for the atmosphere; the reducer and storm tracks
+    /// clamp precipitation to the largest of them.
+    pub precip_caps: atmosphere::PrecipCaps,
+    /// Record prevailing wind vectors in the atmosphere diff; set it together with
+    /// [`io::frame::FrameOptions::include_wind`].
+    pub record_wind: bool,
+}
+
 /// Execute a single deterministic simulation tick.
 ///
 /// This function orchestrates the kernel update order and commits their diffs to the
@@ -28,6 +57,56 @@ pub fn tick_once(
     world: &mut World,
     seed: u64,
     tick: u64,
+) -> Result<(Diff, Vec<String>, Vec<Highlight>)> {
+    tick_once_with_params(world, seed, tick, &SimParams::default())
+}
+
+/// [`tick_once`] under explicit [`SimParams`].
+pub fn tick_once_with_params(
+    world: &mut World,
+    seed: u64,
+    tick: u64,
+    params: &SimParams,
+) -> Result<(Diff, Vec<String>, Vec<Highlight>)> {
+    tick_inner(world, seed, tick, params, None, true)
+}
+
+/// [`tick_once_timed`] output: the [`tick_once`] tuple plus per-stage timings.
+pub type TimedTick = (Diff, Vec<String>, Vec<Highlight>, Vec<StageTiming>);
+
+/// [`tick_once`] that also reports wall-clock time spent in each scheduled stage.
+///
+/// Output is identical to [`tick_once`]; timings are in schedule order.
+pub fn tick_once_timed(world: &mut World, seed: u64, tick: u64) -> Result<TimedTick> {
+    let mut timings = Vec::with_capacity(Schedule::standard().stages().len());
+    let (diff, chronicle, highlights) = tick_inner(
+        world,
+        seed,
+        tick,
+        &SimParams::default(),
+        Some(&mut timings),
+        true,
+    )?;
+    Ok((diff, chronicle, highlights, timings))
+}
+
+/// [`tick_once`] for headless runs that never look at frames.
+///
+/// Every kernel diff is still applied, so the world ends exactly where [`tick_once`]
+/// would leave it, but the aggregate diff, chronicle, and highlights are never built.
+pub fn tick_once_headless(world: &mut World, seed: u64, tick: u64) -> Result<()> {
+    tick_inner(world, seed, tick, &SimParams::default(), None, false)?;
+    Ok(())
+}
+
+/// Runs every scheduled stage; with `collect == false` the returned tuple stays empty.
+fn tick_inner(
+    world: &mut World,
+    seed: u64,
+    tick: u64,
+    params: &SimParams,
+    mut timings: Option<&mut Vec<StageTiming>>,
+    collect: bool,
 ) -> Result<(Diff, Vec<String>, Vec<Highlight>)> {
     ensure!(
         tick == world.tick + 1,
@@ -40,90 +119,151 @@ pub fn tick_once(
     let mut chronicle = Vec::new();
     let mut highlights = Vec::new();
 
-    let climate_stage_rng = Stream::from(seed, climate::STAGE, tick);
-
-    // Astronomy kernel establishes irradiance and tide envelopes.
-    let astronomy_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        astronomy::STAGE,
-        |world, rng| astronomy::update(&*world, rng),
-    )?;
-    chronicle.extend(astronomy_run.chronicle);
-    highlights.extend(astronomy_run.highlights);
+    let rng_kind = world.rng_kind;
+    let climate_stage_rng = Stream::with_kind(rng_kind, seed, climate::STAGE, tick);
+    // Only stages flagged `per_member` in the schedule vary across ensemble members.
+    let stochastic_rng = climate_stage_rng.for_ensemble_member(world.ensemble_member);
+    // The coupler reconciles this tick's atmosphere and cryosphere outputs.
+    let mut atmosphere_diff = Diff::default();
+    let mut cryosphere_diff = Diff::default();
+    let astronomy_params = astronomy::AstronomyParams {
+        only_changed: params.sparse_astronomy,
+        ..astronomy::AstronomyParams::default()
+    };
+    let tuning = world.tuning.clone();
+    let geodynamics_params = geodynamics::GeodynamicsParams {
+        episode_ticks: tuning.volcanic_episode_ticks,
+        episode_rate_multiplier: tuning.volcanic_episode_rate_multiplier,
+        ..geodynamics::GeodynamicsParams::default()
+    };
+    let atmosphere_params = atmosphere::AtmosphereParams {
+        arid_floor_mm: tuning.arid_floor_mm,
+        ocean_inertia: tuning.ocean_inertia,
+        cold_air_pooling: tuning.cold_air_pooling,
+        evapotranspiration: tuning.evapotranspiration,
+        temperature_range: params.temperature_range,
+        precip_caps: params.precip_caps,
+        record_wind: params.record_wind,
+        strict: params.strict,
+        ..atmosphere::AtmosphereParams::default()
+    };
+    let limits = ReduceLimits {
+        temperature: params.temperature_range,
+        precip_max_mm: params.precip_caps.max_mm(),
+    };
+    let storm_params = storms::StormParams {
+        precip_max_mm: limits.precip_max_mm,
+        ..storms::StormParams::default()
+    };
+    let albedo_feedback = coupler::AlbedoFeedbackParams {
+        carry_residual: tuning.albedo_carry_residual,
+        ..coupler::AlbedoFeedbackParams::default()
+    };
+    let diag_options = climate_diag::ClimateDiagOptions {
+        weights: tuning.diag_weights,
+        ..climate_diag::ClimateDiagOptions::default()
+    };
+    let ecology_params = ecology::EcologyParams {
+        drought_alert: tuning.drought_alert,
+        flood_alert: tuning.flood_alert,
+        ..ecology::EcologyParams::default()
+    };
 
-    // Geodynamics kernel adjusts topography before climate updates.
-    let geodynamics_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        geodynamics::STAGE,
-        |world, rng| geodynamics::update(&*world, rng),
-    )?;
-    chronicle.extend(geodynamics_run.chronicle);
-    highlights.extend(geodynamics_run.highlights);
+    for stage in Schedule::standard().stages() {
+        let started = timings.is_some().then(Instant::now);
+        let kernel_rng = if stage.per_member {
+            &stochastic_rng
+        } else {
+            &climate_stage_rng
+        };
+        let run = match stage.id {
+            // Astronomy kernel establishes irradiance and tide envelopes.
+            StageId::Astronomy => run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                astronomy::update_with_params(&*world, rng, &astronomy_params)
+            })?,
+            // Geodynamics kernel adjusts topography before climate updates.
+            StageId::Geodynamics => {
+                run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                    geodynamics::update_with_params(&*world, rng, &geodynamics_params)
+                })?
+            }
+            // Exogenous forcing shifts baselines before the atmosphere reads them.
+            StageId::Forcing => KernelRun::new(forcing::update(&*world, tick)),
+            // Atmospheric energy balance precedes climate classification.
+            StageId::Atmosphere => {
+                // Strict runs fail inside the kernel, before its clamps hide an overflow.
+                let run = run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                    atmosphere::update_with_params(world, rng, &atmosphere_params)
+                })
+                .with_context(|| format!("stage {} at tick {}", stage.label, tick))?;
+                atmosphere_diff = run.diff.clone();
+                run
+            }
+            // Storm tracks add to the atmosphere's committed precipitation.
+            StageId::Storms => run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                storms::update_with_params(&*world, rng, &storm_params)
+            })?,
+            StageId::Cryosphere => {
+                let run = run_uncommitted(world, kernel_rng, stage.label, cryosphere::update)?;
+                cryosphere_diff = run.diff.clone();
+                run
+            }
+            StageId::Coupler => {
+                let coupler_diff = coupler::reconcile_with_params(
+                    world,
+                    &atmosphere_diff,
+                    &cryosphere_diff,
+                    &albedo_feedback,
+                )?;
+                let coupler_active = !coupler_diff.is_empty();
+                let mut run = KernelRun::new(coupler_diff);
+                if coupler_active {
+                    run.chronicle.push(coupler::CHRONICLE_LINE.to_string());
+                }
+                run
+            }
+            StageId::ClimateDiag => {
+                let mut climate_diag_rng = Stream::with_kind(rng_kind, seed, stage.label, tick);
+                climate_diag::update_with_options(&*world, &mut climate_diag_rng, diag_options)?
+            }
+            StageId::Climate => run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                climate::update(&*world, rng)
+            })?,
+            // Ecology kernel uses the climate-updated world state.
+            StageId::Ecology => run_uncommitted(world, kernel_rng, stage.label, |world, rng| {
+                ecology::update_with_params(&*world, rng, &ecology_params)
+            })?,
+        };
 
-    // Atmospheric energy balance precedes climate classification.
-    let atmosphere_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        atmosphere::STAGE,
-        |world, rng| atmosphere::update(world, rng),
-    )?;
-    if !atmosphere_run.chronicle.is_empty() {
-        chronicle.push("Hadley belt drifted northward under seasonal tilt.".to_string());
-    }
-    highlights.extend(atmosphere_run.highlights);
+        // Commit before the next stage reads the world.
+        if collect {
+            aggregate_diff.merge(&run.diff);
+        }
+        if params.strict {
+            apply_strict_in_range(world, run.diff, limits)
+                .with_context(|| format!("stage {} at tick {}", stage.label, tick))?;
+        } else {
+            apply_in_range(world, run.diff, limits);
+        }
 
-    let cryosphere_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        cryosphere::STAGE,
-        |world, rng| cryosphere::update(world, rng),
-    )?;
-    chronicle.extend(cryosphere_run.chronicle);
-    highlights.extend(cryosphere_run.highlights);
-
-    let coupler_diff =
-        coupler::reconcile_with_world(world, &atmosphere_run.diff, &cryosphere_run.diff)?;
-    let coupler_active = !coupler_diff.is_empty();
-    aggregate_diff.merge(&coupler_diff);
-    apply(world, coupler_diff);
-    if coupler_active {
-        chronicle.push(coupler::CHRONICLE_LINE.to_string());
+        if collect {
+            match stage.chronicle_summary {
+                Some(summary) if !run.chronicle.is_empty() => chronicle.push(summary.to_string()),
+                Some(_) => {}
+                None => chronicle.extend(run.chronicle),
+            }
+            if stage.produces_highlights {
+                highlights.extend(run.highlights);
+            }
+        }
+        if let (Some(timings), Some(started)) = (timings.as_deref_mut(), started) {
+            timings.push(StageTiming {
+                label: stage.label,
+                elapsed: started.elapsed(),
+            });
+        }
     }
 
-    let mut climate_diag_rng = Stream::from(seed, climate_diag::STAGE, tick);
-    let climate_diag_run = climate_diag::update(&*world, &mut climate_diag_rng)?;
-    aggregate_diff.merge(&climate_diag_run.diff);
-    apply(world, climate_diag_run.diff.clone());
-    chronicle.extend(climate_diag_run.chronicle);
-    highlights.extend(climate_diag_run.highlights);
-
-    let climate_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        climate::CORE_STAGE,
-        |world, rng| climate::update(&*world, rng),
-    )?;
-    chronicle.extend(climate_run.chronicle);
-    highlights.extend(climate_run.highlights);
-
-    // Ecology kernel uses the climate-updated world state.
-    let ecology_run = run_kernel(
-        world,
-        &mut aggregate_diff,
-        &climate_stage_rng,
-        ecology::STAGE,
-        |world, rng| ecology::update(&*world, rng),
-    )?;
-    chronicle.extend(ecology_run.chronicle);
-    highlights.extend(ecology_run.highlights);
-
     // Chronicle stream reserved for downstream narrative kernels.
     let mut chronicle_rng = climate_stage_rng.derive(stream_label("kernel:chronicle"));
     let _ = chronicle_rng.next_u64();
@@ -136,19 +276,13 @@ pub fn tick_once(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::io::seed::{build_world, Seed};
+    use crate::io::seed::build_world;
     use crate::kernels::climate_diag;
+    use crate::testing::test_seed;
 
     #[test]
     fn tick_advances_world() {
-        let seed_json = r#"{
-            "name": "test",
-            "width": 2,
-            "height": 1,
-            "elevation_noise": {"octaves": 1, "freq": 0.1, "amp": 1.0, "seed": 42},
-            "humidity_bias": {"equator": 0.2, "poles": -0.2}
-        }"#;
-        let seed: Seed = serde_json::from_str(seed_json).unwrap();
+        let seed = test_seed("test", 2, 1);
         let mut world = build_world(&seed, Some(777));
         let prev_tick = world.tick;
         let next_tick = prev_tick + 1;
@@ -157,16 +291,176 @@ mod tests {
         assert_eq!(world.tick, next_tick);
     }
 
+    #[test]
+    fn strict_mode_fails_on_temperatures_the_atmosphere_would_clamp() {
+        let seed = test_seed("strict", 4, 4);
+        let params = SimParams {
+            temperature_range: fixed::TemperatureRange {
+                min_tenths: -10,
+                max_tenths: 10,
+            },
+            ..SimParams::default()
+        };
+
+        let mut lenient = build_world(&seed, None);
+        let world_seed = lenient.seed;
+        tick_once_with_params(&mut lenient, world_seed, 1, &params).expect("lenient tick");
+        assert!(lenient.regions.iter().all(|region| params
+            .temperature_range
+            .contains(i32::from(region.temperature_tenths_c))));
+
+        let mut strict = build_world(&seed, None);
+        let strict_params = SimParams {
+            strict: true,
+            ..params
+        };
+        let err = tick_once_with_params(&mut strict, world_seed, 1, &strict_params).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(
+            message.contains("stage kernel:atmosphere at tick 1"),
+            "{}",
+            message
+        );
+        assert!(message.contains("temperature value"), "{}", message);
+    }
+
+    #[test]
+    fn timed_tick_matches_untimed_and_reports_every_stage() {
+        let seed = test_seed("timed", 3, 2);
+        let mut plain = build_world(&seed, Some(31));
+        let mut timed = plain.clone();
+        let world_seed = plain.seed;
+        let (diff, chronicle, highlights) = tick_once(&mut plain, world_seed, 1).unwrap();
+        let (timed_diff, timed_chronicle, timed_highlights, timings) =
+            tick_once_timed(&mut timed, world_seed, 1).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&diff).unwrap(),
+            serde_json::to_string(&timed_diff).unwrap()
+        );
+        assert_eq!(chronicle, timed_chronicle);
+        assert_eq!(highlights, timed_highlights);
+        let labels: Vec<&str> = timings.iter().map(|timing| timing.label).collect();
+        let expected: Vec<&str> = Schedule::standard()
+            .stages()
+            .iter()
+            .map(|stage| stage.label)
+            .collect();
+        assert_eq!(labels, expected);
+    }
+
+    #[test]
+    fn headless_ticks_leave_the_same_world_as_full_ticks() {
+        let seed = test_seed("headless", 4, 3);
+        let mut full = build_world(&seed, Some(53));
+        let mut headless = full.clone();
+        let world_seed = full.seed;
+        for tick in 1..=50 {
+            tick_once(&mut full, world_seed, tick).unwrap();
+            tick_once_headless(&mut headless, world_seed, tick).unwrap();
+        }
+
+        assert_eq!(headless.tick, 50);
+        assert_eq!(
+            serde_json::to_string(&full).unwrap(),
+            serde_json::to_string(&headless).unwrap()
+        );
+    }
+
+    #[test]
+    fn worlds_ticked_on_separate_threads_match_serial_runs() {
+        use crate::io::frame::make_frame;
+
+        fn run(seed_value: u64) -> Vec<String> {
+            let seed = test_seed("threads", 4, 3);
+            let mut world = build_world(&seed, Some(seed_value));
+            (1..=100)
+                .map(|tick| {
+                    let (diff, chronicle, highlights) =
+                        tick_once(&mut world, seed_value, tick).unwrap();
+                    make_frame(tick, diff, highlights, chronicle, false, 4, 3)
+                        .to_ndjson()
+                        .unwrap()
+                })
+                .collect()
+        }
+
+        let serial = [run(31), run(32)];
+        let threads = [31, 32].map(|seed| std::thread::spawn(move || run(seed)));
+        let parallel = threads.map(|handle| handle.join().expect("tick thread panicked"));
+        assert_ne!(serial[0], serial[1]);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn widened_temperature_range_lets_the_equator_pass_fifty_degrees() {
+        use crate::fixed::{TemperatureRange, TEMP_MAX_TENTHS_C};
+        use crate::world::Region;
+
+        fn hot_world() -> World {
+            let region = Region::sample(0, 0, 0).with_latitude(0.0);
+            let mut world = World::new(5, 1, 1, vec![region]);
+            world.climate.temperature_baseline_tenths[0] = 400;
+            world
+        }
+
+        let mut clamped = hot_world();
+        tick_once(&mut clamped, 5, 1).expect("tick succeeds");
+        assert_eq!(clamped.regions[0].temperature_tenths_c, TEMP_MAX_TENTHS_C);
+
+        let params = SimParams {
+            temperature_range: TemperatureRange {
+                min_tenths: -800,
+                max_tenths: 800,
+            },
+            ..SimParams::default()
+        };
+        let mut widened = hot_world();
+        tick_once_with_params(&mut widened, 5, 1, &params).expect("tick succeeds");
+        assert!(
+            widened.regions[0].temperature_tenths_c > TEMP_MAX_TENTHS_C,
+            "temperature {}",
+            widened.regions[0].temperature_tenths_c
+        );
+    }
+
+    #[test]
+    fn ensemble_members_share_geometry_but_not_geodynamics() {
+        use crate::cause::Code;
+
+        let seed = test_seed("ensemble", 4, 3);
+        let members: Vec<World> = (1..=2)
+            .map(|member| {
+                let mut world = build_world(&seed, Some(97));
+                world.ensemble_member = member;
+                world
+            })
+            .collect();
+        let elevation =
+            |world: &World| -> Vec<i32> { world.regions.iter().map(|r| r.elevation_m).collect() };
+        assert_eq!(elevation(&members[0]), elevation(&members[1]));
+
+        let event_ticks: Vec<Vec<u64>> = members
+            .into_iter()
+            .map(|mut world| {
+                let world_seed = world.seed;
+                (1..=400)
+                    .filter(|&tick| {
+                        let (diff, _, _) = tick_once(&mut world, world_seed, tick).unwrap();
+                        diff.causes.iter().any(|cause| {
+                            matches!(cause.code, Code::OrogenyBelt | Code::SubsidenceDeltas)
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        assert!(!event_ticks[0].is_empty() && !event_ticks[1].is_empty());
+        assert_ne!(event_ticks[0], event_ticks[1]);
+    }
+
     #[test]
     fn diagnostic_chronicle_emitted_before_climate_updates() {
-        let seed_json = r#"{
-            "name": "chron_test",
-            "width": 2,
-            "height": 1,
-            "elevation_noise": {"octaves": 1, "freq": 0.1, "amp": 1.0, "seed": 7},
-            "humidity_bias": {"equator": 0.1, "poles": -0.1}
-        }"#;
-        let seed: Seed = serde_json::from_str(seed_json).unwrap();
+        let seed = test_seed("chron_test", 2, 1);
         let mut world = build_world(&seed, Some(111));
         let seed_value = world.seed;
         let next_tick = world.tick + 1;
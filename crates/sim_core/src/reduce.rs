@@ -1,16 +1,171 @@
+use anyhow::{bail, ensure, Context, Result};
+
 use crate::diff::Diff;
 use crate::fixed::{
-    clamp_biome_index, clamp_hazard_meter, clamp_i16, clamp_u16, commit_resource_delta, ALBEDO_MAX,
-    FRESHWATER_FLUX_MAX, SOIL_MAX, WATER_MAX,
+    clamp_biome_index, clamp_elevation, clamp_hazard_meter, clamp_u16, commit_resource_delta,
+    TemperatureRange, ALBEDO_MAX, FRESHWATER_FLUX_MAX, MAX_ELEVATION_M, MIN_ELEVATION_M,
+    PRECIP_MAX_MM, SOIL_MAX, WATER_MAX,
 };
+use crate::io::frame::Frame;
 use crate::world::World;
 
-const TEMP_MIN_TENTHS_C: i16 = -500;
-const TEMP_MAX_TENTHS_C: i16 = 500;
-const PRECIP_MAX_MM: u16 = 5_000;
+/// Number of values [`apply_counting`] had to clamp into range, per diff field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClampCounts {
+    pub biome: usize,
+    pub water: usize,
+    pub soil: usize,
+    pub elevation: usize,
+    pub temperature: usize,
+    pub temperature_baseline: usize,
+    pub precipitation: usize,
+    pub albedo: usize,
+    pub freshwater_flux: usize,
+    pub ice_mass: usize,
+    pub hazards: usize,
+    /// The first clamped value, in apply order.
+    pub first: Option<ClampSite>,
+}
+
+/// Where a single out-of-range value landed, for strict-mode errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClampSite {
+    pub field: &'static str,
+    pub region: u32,
+    /// The value as requested, before clamping.
+    pub value: i32,
+}
+
+impl ClampCounts {
+    pub fn total(&self) -> usize {
+        self.biome
+            + self.water
+            + self.soil
+            + self.elevation
+            + self.temperature
+            + self.temperature_baseline
+            + self.precipitation
+            + self.albedo
+            + self.freshwater_flux
+            + self.ice_mass
+            + self.hazards
+    }
+
+    fn note(&mut self, field: &'static str, region: u32, value: i32) {
+        self.first.get_or_insert(ClampSite {
+            field,
+            region,
+            value,
+        });
+    }
+}
+
+/// Run-configurable bounds the reducer clamps to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReduceLimits {
+    /// Bounds for temperatures and their baselines; ±50 °C by default.
+    pub temperature: TemperatureRange,
+    /// Per-tick precipitation ceiling in mm; [`PRECIP_MAX_MM`] by default.
+    pub precip_max_mm: u16,
+}
+
+impl Default for ReduceLimits {
+    fn default() -> Self {
+        Self {
+            temperature: TemperatureRange::default(),
+            precip_max_mm: PRECIP_MAX_MM,
+        }
+    }
+}
 
-pub fn apply(world: &mut World, mut diff: Diff) {
+pub fn apply(world: &mut World, diff: Diff) {
+    apply_in_range(world, diff, ReduceLimits::default());
+}
+
+/// [`apply`] clamping temperatures, baselines, and precipitation to `limits` instead of
+/// the defaults.
+pub fn apply_in_range(world: &mut World, diff: Diff, limits: ReduceLimits) {
+    apply_inner::<false>(world, diff, &mut ClampCounts::default(), limits);
+}
+
+/// Strict-mode [`apply`]: any value that would need clamping is an error naming the first
+/// offending region and field.
+///
+/// The diff is still applied (clamped) before the error returns, so callers should
+/// abandon the tick rather than keep simulating the world.
+pub fn apply_strict(world: &mut World, diff: Diff) -> Result<()> {
+    apply_strict_in_range(world, diff, ReduceLimits::default())
+}
+
+/// [`apply_strict`] against custom [`ReduceLimits`].
+pub fn apply_strict_in_range(world: &mut World, diff: Diff, limits: ReduceLimits) -> Result<()> {
+    let mut counts = ClampCounts::default();
+    apply_inner::<true>(world, diff, &mut counts, limits);
+    if let Some(site) = counts.first {
+        bail!(
+            "strict mode: region {} {} value {} is out of range ({} clamped value(s) in this diff)",
+            site.region,
+            site.field,
+            site.value,
+            counts.total()
+        );
+    }
+    Ok(())
+}
+
+/// Re-apply a recorded frame to `world`, advancing it to the frame's tick without
+/// running any kernel.
+///
+/// Frames omit climate bookkeeping (baselines, rolling windows, snowpack) and hazard
+/// meters, so only the region fields and astronomy caches they carry are reconstructed.
+pub fn apply_frame(world: &mut World, frame: &Frame) -> Result<()> {
+    ensure!(
+        frame.t == world.tick + 1,
+        "frame for tick {} cannot follow world tick {}",
+        frame.t,
+        world.tick
+    );
+    let diff = frame
+        .diff
+        .to_diff()
+        .with_context(|| format!("frame for tick {} has an invalid diff", frame.t))?;
+    apply(world, diff);
+    world.tick = frame.t;
+    Ok(())
+}
+
+/// Same as [`apply`], additionally reporting how many values were clamped.
+///
+/// Lets runners and tests assert that a model never pushes meters out of range.
+pub fn apply_counting(world: &mut World, diff: Diff) -> ClampCounts {
+    let mut counts = ClampCounts::default();
+    apply_inner::<true>(world, diff, &mut counts, ReduceLimits::default());
+    counts
+}
+
+fn out_of_range(value: i32, min: i32, max: i32) -> bool {
+    value < min || value > max
+}
+
+// `COUNT` is a const parameter so plain `apply` compiles the tallies away entirely.
+fn apply_inner<const COUNT: bool>(
+    world: &mut World,
+    mut diff: Diff,
+    counts: &mut ClampCounts,
+    limits: ReduceLimits,
+) {
+    let range = limits.temperature;
     world.climate.ensure_region_capacity(world.regions.len());
+    if let Some(offset) = diff.forcing_offset_tenths {
+        world.climate.applied_forcing_tenths = offset;
+    }
+    if let Some(tracks) = diff.storm_tracks.take() {
+        world.climate.storm_tracks = tracks;
+    }
+    if let Some(episodes) = diff.volcanic_episode.take() {
+        world.climate.volcanic_episode = episodes;
+        world.climate.ensure_region_capacity(world.regions.len());
+    }
     diff.biome.sort_by_key(|change| change.region);
     diff.water.sort_by_key(|delta| delta.region);
     diff.soil.sort_by_key(|delta| delta.region);
@@ -28,11 +183,17 @@ pub fn apply(world: &mut World, mut diff: Diff) {
     diff.melt_pulse.sort_by_key(|value| value.region);
     diff.ice_mass.sort_by_key(|value| value.region);
     diff.heatwave_idx.sort_by_key(|value| value.region);
+    diff.precip_type.sort_by_key(|value| value.region);
     diff.diag_climate.sort_by_key(|value| value.region);
+    diff.wind.sort_by_key(|vector| vector.region);
     diff.hazards.sort_by_key(|hazard| hazard.region);
 
     for change in diff.biome {
         if let Some(region) = world.regions.get_mut(change.region as usize) {
+            if COUNT && out_of_range(change.biome, i32::from(u8::MIN), i32::from(u8::MAX)) {
+                counts.biome += 1;
+                counts.note("biome", change.region, change.biome);
+            }
             region.biome = clamp_biome_index(change.biome);
         }
     }
@@ -47,28 +208,59 @@ pub fn apply(world: &mut World, mut diff: Diff) {
         }
     }
 
+    for value in &diff.tide_envelope {
+        if let Some(slot) = world
+            .climate
+            .last_tide_envelope_tenths
+            .get_mut(value.region as usize)
+        {
+            *slot = value.value;
+        }
+    }
+
     for delta in diff.water {
         if let Some(region) = world.regions.get_mut(delta.region as usize) {
+            if COUNT
+                && out_of_range(
+                    i32::from(region.water) + delta.delta,
+                    0,
+                    i32::from(WATER_MAX),
+                )
+            {
+                counts.water += 1;
+                counts.note("water", delta.region, i32::from(region.water) + delta.delta);
+            }
             region.water = commit_resource_delta(region.water, delta.delta, WATER_MAX);
         }
     }
 
     for delta in diff.soil {
         if let Some(region) = world.regions.get_mut(delta.region as usize) {
+            if COUNT && out_of_range(i32::from(region.soil) + delta.delta, 0, i32::from(SOIL_MAX)) {
+                counts.soil += 1;
+                counts.note("soil", delta.region, i32::from(region.soil) + delta.delta);
+            }
             region.soil = commit_resource_delta(region.soil, delta.delta, SOIL_MAX);
         }
     }
 
     for value in diff.elevation {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
-            region.elevation_m = value.value;
+            if COUNT && out_of_range(value.value, MIN_ELEVATION_M, MAX_ELEVATION_M) {
+                counts.elevation += 1;
+                counts.note("elevation", value.region, value.value);
+            }
+            region.elevation_m = clamp_elevation(value.value);
         }
     }
 
     for value in diff.temperature {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
-            region.temperature_tenths_c =
-                clamp_i16(value.value, TEMP_MIN_TENTHS_C, TEMP_MAX_TENTHS_C);
+            if COUNT && !range.contains(value.value) {
+                counts.temperature += 1;
+                counts.note("temperature", value.region, value.value);
+            }
+            region.temperature_tenths_c = range.clamp(value.value) as i16;
         }
     }
 
@@ -78,36 +270,66 @@ pub fn apply(world: &mut World, mut diff: Diff) {
             .temperature_baseline_tenths
             .get_mut(value.region as usize)
         {
-            *slot = clamp_i16(value.value, TEMP_MIN_TENTHS_C, TEMP_MAX_TENTHS_C);
+            if COUNT && !range.contains(value.value) {
+                counts.temperature_baseline += 1;
+                counts.note("temperature_baseline", value.region, value.value);
+            }
+            *slot = range.clamp(value.value) as i16;
         }
     }
 
     for value in diff.precipitation {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
-            region.precipitation_mm = clamp_u16(value.value, 0, PRECIP_MAX_MM);
+            if COUNT && out_of_range(value.value, 0, i32::from(limits.precip_max_mm)) {
+                counts.precipitation += 1;
+                counts.note("precipitation", value.region, value.value);
+            }
+            region.precipitation_mm = clamp_u16(value.value, 0, limits.precip_max_mm);
         }
     }
 
     for value in diff.albedo {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
+            if COUNT && out_of_range(value.value, 0, i32::from(ALBEDO_MAX)) {
+                counts.albedo += 1;
+                counts.note("albedo", value.region, value.value);
+            }
             region.albedo_milli = clamp_u16(value.value, 0, ALBEDO_MAX);
         }
     }
 
     for value in diff.freshwater_flux {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
+            if COUNT && out_of_range(value.value, 0, i32::from(FRESHWATER_FLUX_MAX)) {
+                counts.freshwater_flux += 1;
+                counts.note("freshwater_flux", value.region, value.value);
+            }
             region.freshwater_flux_tenths_mm = clamp_u16(value.value, 0, FRESHWATER_FLUX_MAX);
         }
     }
 
     for value in diff.ice_mass {
         if let Some(region) = world.regions.get_mut(value.region as usize) {
+            if COUNT && value.value < 0 {
+                counts.ice_mass += 1;
+                counts.note("ice_mass", value.region, value.value);
+            }
             region.ice_mass_kilotons = value.value.max(0) as u32;
         }
     }
 
     for hazard in diff.hazards {
         if let Some(region) = world.regions.get_mut(hazard.region as usize) {
+            if COUNT {
+                if hazard.drought > WATER_MAX {
+                    counts.hazards += 1;
+                    counts.note("drought", hazard.region, i32::from(hazard.drought));
+                }
+                if hazard.flood > WATER_MAX {
+                    counts.hazards += 1;
+                    counts.note("flood", hazard.region, i32::from(hazard.flood));
+                }
+            }
             region.hazards.drought = clamp_hazard_meter(hazard.drought);
             region.hazards.flood = clamp_hazard_meter(hazard.flood);
         }
@@ -115,9 +337,11 @@ pub fn apply(world: &mut World, mut diff: Diff) {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
     use crate::diff::{BiomeChange, HazardEvent, ResourceDelta, ScalarValue};
+    use crate::fixed::TEMP_MAX_TENTHS_C;
     use crate::world::{Hazards, Region};
     use proptest::prelude::*;
 
@@ -192,6 +416,61 @@ mod tests {
         World::new(0, 2, 2, regions)
     }
 
+    #[test]
+    fn apply_clamps_elevation_to_geodynamics_bounds() {
+        let mut world = test_world();
+        let mut diff = Diff::default();
+        diff.record_elevation(0, 99_999);
+        diff.record_elevation(1, -99_999);
+
+        let counts = apply_counting(&mut world, diff);
+        assert_eq!(world.regions[0].elevation_m, MAX_ELEVATION_M);
+        assert_eq!(world.regions[1].elevation_m, MIN_ELEVATION_M);
+        assert_eq!(counts.elevation, 2);
+    }
+
+    #[test]
+    fn apply_counting_reports_temperature_clamps() {
+        let mut world = test_world();
+        let mut diff = Diff::default();
+        diff.record_temperature(0, 720);
+        diff.record_temperature(1, -120);
+
+        let counts = apply_counting(&mut world, diff);
+        assert_eq!(counts.temperature, 1);
+        assert_eq!(counts.total(), 1);
+        assert_eq!(world.regions[0].temperature_tenths_c, TEMP_MAX_TENTHS_C);
+
+        let mut in_range = Diff::default();
+        in_range.record_temperature(2, 150);
+        assert_eq!(apply_counting(&mut world, in_range), ClampCounts::default());
+    }
+
+    #[test]
+    fn strict_apply_rejects_out_of_range_temperature() {
+        let mut diff = Diff::default();
+        diff.record_temperature(1, 150);
+        diff.record_temperature(3, 720);
+
+        let mut strict = test_world();
+        let err = apply_strict(&mut strict, diff.clone()).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("region 3 temperature value 720"),
+            "{}",
+            message
+        );
+
+        let mut lenient = test_world();
+        apply(&mut lenient, diff.clone());
+        assert_eq!(lenient.regions[3].temperature_tenths_c, TEMP_MAX_TENTHS_C);
+        assert_eq!(lenient.regions[1].temperature_tenths_c, 150);
+
+        let mut in_range = Diff::default();
+        in_range.record_temperature(3, -499);
+        assert!(apply_strict(&mut test_world(), in_range).is_ok());
+    }
+
     #[test]
     fn apply_sorts_entries_and_clamps_values() {
         let mut unsorted_diff = Diff::default();
@@ -0,0 +1,271 @@
+//! Regridding a world to a new resolution for multi-resolution experiments.
+
+use anyhow::{ensure, Result};
+
+use crate::world::{ClimateState, Hazards, Region, World};
+
+/// How [`World::resample`] derives each target cell from the source grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResampleMode {
+    /// Copy the source cell under the target cell's centre.
+    Nearest,
+    /// Average every source cell the target cell covers; upsampling degenerates to
+    /// nearest-neighbour. Biomes take the most common code in the block (lowest on ties).
+    Average,
+}
+
+impl World {
+    /// Regrid onto `new_width`×`new_height`, producing contiguous row-major ids and
+    /// per-region climate state sized to the new grid.
+    ///
+    /// Rolling climate windows, the monsoon flag, and volcanic episodes always come from
+    /// the cell under the target centre; scalar caches follow `mode`. Global bookkeeping
+    /// (tick, seed, sea level, applied forcing, attached forcing, tuning) carries over
+    /// unchanged, while the seed provenance hash is cleared because the result no longer
+    /// matches its seed.
+    pub fn resample(&self, new_width: u32, new_height: u32, mode: ResampleMode) -> Result<World> {
+        ensure!(
+            new_width > 0 && new_height > 0,
+            "cannot resample to an empty {}x{} grid",
+            new_width,
+            new_height
+        );
+        ensure!(
+            self.regions.len() == self.width as usize * self.height as usize,
+            "world has {} regions but a {}x{} grid",
+            self.regions.len(),
+            self.width,
+            self.height
+        );
+        ensure!(!self.regions.is_empty(), "cannot resample an empty world");
+
+        let mut source_climate = self.climate.clone();
+        source_climate.ensure_region_capacity(self.regions.len());
+        let mut regions = Vec::with_capacity(new_width as usize * new_height as usize);
+        let mut blocks = Vec::with_capacity(regions.capacity());
+        for y in 0..new_height {
+            for x in 0..new_width {
+                let centre = self.index_at(
+                    nearest(x, new_width, self.width),
+                    nearest(y, new_height, self.height),
+                );
+                let block = match mode {
+                    ResampleMode::Nearest => vec![centre],
+                    ResampleMode::Average => {
+                        let (x0, x1) = span(x, new_width, self.width);
+                        let (y0, y1) = span(y, new_height, self.height);
+                        (y0..y1)
+                            .flat_map(|sy| (x0..x1).map(move |sx| (sx, sy)))
+                            .map(|(sx, sy)| self.index_at(sx, sy))
+                            .collect()
+                    }
+                };
+                regions.push(self.merge_block(regions.len() as u32, x, y, &block));
+                blocks.push((centre, block));
+            }
+        }
+
+        let mut world = World::new(self.seed, new_width, new_height, regions);
+        world.tick = self.tick;
+        world.rng_kind = self.rng_kind;
+        world.ensemble_member = self.ensemble_member;
+        world.wrap = self.wrap;
+        world.forcing = self.forcing.clone();
+        world.tuning = self.tuning.clone();
+
+        let source = &source_climate;
+        let climate: &mut ClimateState = &mut world.climate;
+        climate.sea_level_equivalent_mm = source.sea_level_equivalent_mm;
+        climate.applied_forcing_tenths = source.applied_forcing_tenths;
+        climate.set_extreme_window(source.extreme_window)?;
+        climate.lunar_period_ticks = source.lunar_period_ticks;
+        for (index, (centre, block)) in blocks.iter().enumerate() {
+            let mean_of = |values: &[i32]| mean(block.iter().map(|&i| i64::from(values[i])));
+            climate.temperature_baseline_tenths[index] = mean(
+                block
+                    .iter()
+                    .map(|&i| i64::from(source.temperature_baseline_tenths[i])),
+            ) as i16;
+            climate.last_albedo_milli[index] = mean_of(&source.last_albedo_milli) as i32;
+            climate.last_insolation_tenths[index] = mean_of(&source.last_insolation_tenths) as i32;
+            climate.last_tide_envelope_tenths[index] =
+                mean_of(&source.last_tide_envelope_tenths) as i32;
+            climate.snowpack_mm[index] = mean_of(&source.snowpack_mm) as i32;
+            climate.permafrost_active_cm[index] = mean_of(&source.permafrost_active_cm) as i32;
+            climate.temperature_maxima[index] = source.temperature_maxima[*centre].clone();
+            climate.precipitation_peaks[index] = source.precipitation_peaks[*centre].clone();
+            climate.monsoon_active[index] = source.monsoon_active[*centre];
+            climate.volcanic_episode[index] = source.volcanic_episode[*centre];
+        }
+        Ok(world)
+    }
+
+    fn index_at(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    fn merge_block(&self, id: u32, x: u32, y: u32, block: &[usize]) -> Region {
+        let cells: Vec<&Region> = block.iter().map(|&index| &self.regions[index]).collect();
+        let field = |f: fn(&Region) -> i64| mean(cells.iter().map(|region| f(region)));
+        let latitude_deg =
+            cells.iter().map(|region| region.latitude_deg).sum::<f64>() / cells.len() as f64;
+        Region {
+            id,
+            x,
+            y,
+            elevation_m: field(|r| i64::from(r.elevation_m)) as i32,
+            latitude_deg,
+            biome: most_common_biome(&cells),
+            water: field(|r| i64::from(r.water)) as u16,
+            soil: field(|r| i64::from(r.soil)) as u16,
+            temperature_tenths_c: field(|r| i64::from(r.temperature_tenths_c)) as i16,
+            precipitation_mm: field(|r| i64::from(r.precipitation_mm)) as u16,
+            albedo_milli: field(|r| i64::from(r.albedo_milli)) as u16,
+            freshwater_flux_tenths_mm: field(|r| i64::from(r.freshwater_flux_tenths_mm)) as u16,
+            ice_mass_kilotons: field(|r| i64::from(r.ice_mass_kilotons)) as u32,
+            hazards: Hazards {
+                drought: field(|r| i64::from(r.hazards.drought)) as u16,
+                flood: field(|r| i64::from(r.hazards.flood)) as u16,
+            },
+        }
+    }
+}
+
+/// Source coordinate under the centre of target cell `target` along one axis.
+fn nearest(target: u32, target_len: u32, source_len: u32) -> u32 {
+    let scaled = (2 * u64::from(target) + 1) * u64::from(source_len) / (2 * u64::from(target_len));
+    (scaled as u32).min(source_len - 1)
+}
+
+/// Half-open source range covered by target cell `target` along one axis; never empty.
+fn span(target: u32, target_len: u32, source_len: u32) -> (u32, u32) {
+    let start = (u64::from(target) * u64::from(source_len) / u64::from(target_len)) as u32;
+    let end = ((u64::from(target) + 1) * u64::from(source_len) / u64::from(target_len)) as u32;
+    (start, end.max(start + 1).min(source_len))
+}
+
+/// Integer mean rounded half away from zero, so block means never drift with sign.
+fn mean<I: Iterator<Item = i64>>(values: I) -> i64 {
+    let (sum, count) = values.fold((0i64, 0i64), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        return 0;
+    }
+    let half = count / 2;
+    if sum >= 0 {
+        (sum + half) / count
+    } else {
+        (sum - half) / count
+    }
+}
+
+fn most_common_biome(cells: &[&Region]) -> u8 {
+    let mut counts = [0usize; 256];
+    for region in cells {
+        counts[usize::from(region.biome)] += 1;
+    }
+    let mut best = 0usize;
+    for (biome, &count) in counts.iter().enumerate() {
+        if count > counts[best] {
+            best = biome;
+        }
+    }
+    best as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{latitude_from_row, Tuning};
+
+    #[test]
+    fn averaging_downsample_matches_block_means() {
+        let elevation: Vec<i32> = (0..16).map(|i| i * 100).collect();
+        let biome = [1, 1, 4, 4, 1, 2, 4, 5, 0, 0, 3, 3, 2, 0, 3, 5];
+        let mut world = World::from_fields(8, 4, 4, &elevation, &biome, latitude_from_row)
+            .expect("fields match grid");
+        for (index, region) in world.regions.iter_mut().enumerate() {
+            region.temperature_tenths_c = index as i16 * 10 - 50;
+            region.water = 1_000 + index as u16;
+        }
+        for (index, baseline) in world
+            .climate
+            .temperature_baseline_tenths
+            .iter_mut()
+            .enumerate()
+        {
+            *baseline = index as i16;
+        }
+
+        let small = world
+            .resample(2, 2, ResampleMode::Average)
+            .expect("resample succeeds");
+        assert_eq!((small.width, small.height), (2, 2));
+        assert_eq!(small.regions.len(), 4);
+        // Each 2x2 block of source indices, in target row-major order.
+        let blocks = [[0, 1, 4, 5], [2, 3, 6, 7], [8, 9, 12, 13], [10, 11, 14, 15]];
+        for (index, (region, block)) in small.regions.iter().zip(blocks).enumerate() {
+            assert_eq!(region.index(), index);
+            let block_mean = |f: &dyn Fn(usize) -> i64| mean(block.iter().map(|&i| f(i)));
+            assert_eq!(
+                i64::from(region.elevation_m),
+                block_mean(&|i| i as i64 * 100)
+            );
+            assert_eq!(
+                i64::from(region.temperature_tenths_c),
+                block_mean(&|i| i as i64 * 10 - 50)
+            );
+            assert_eq!(i64::from(region.water), block_mean(&|i| 1_000 + i as i64));
+            assert_eq!(
+                i64::from(small.climate.temperature_baseline_tenths[index]),
+                block_mean(&|i| i as i64)
+            );
+            assert_eq!(region.latitude_deg, latitude_from_row(region.y, 2));
+        }
+        assert_eq!(
+            small.regions.iter().map(|r| r.biome).collect::<Vec<_>>(),
+            vec![1, 4, 0, 3]
+        );
+
+        let climate = &small.climate;
+        assert_eq!(climate.temperature_baseline_tenths.len(), 4);
+        assert_eq!(climate.last_albedo_milli.len(), 4);
+        assert_eq!(climate.last_insolation_tenths.len(), 4);
+        assert_eq!(climate.last_tide_envelope_tenths.len(), 4);
+        assert_eq!(climate.temperature_maxima.len(), 4);
+        assert_eq!(climate.precipitation_peaks.len(), 4);
+        assert_eq!(climate.snowpack_mm.len(), 4);
+        assert_eq!(climate.permafrost_active_cm.len(), 4);
+        assert_eq!(climate.monsoon_active.len(), 4);
+        assert_eq!(climate.volcanic_episode.len(), 4);
+    }
+
+    #[test]
+    fn nearest_upsample_copies_covering_cell() {
+        let mut world = World::from_fields(2, 2, 1, &[10, 20], &[1, 5], latitude_from_row)
+            .expect("fields match grid");
+        world.tuning = Tuning {
+            arid_floor_mm: 40,
+            ..Tuning::default()
+        };
+        let large = world
+            .resample(4, 2, ResampleMode::Nearest)
+            .expect("resample succeeds");
+        let elevation: Vec<i32> = large.regions.iter().map(|r| r.elevation_m).collect();
+        assert_eq!(elevation, vec![10, 10, 20, 20, 10, 10, 20, 20]);
+        assert_eq!(large.tuning, world.tuning);
+
+        let mut windowed = world.clone();
+        windowed
+            .climate
+            .set_extreme_window(9)
+            .expect("valid window");
+        let large = windowed
+            .resample(3, 1, ResampleMode::Nearest)
+            .expect("resample succeeds");
+        assert_eq!(large.climate.extreme_window, 9);
+        let climate = &large.climate;
+        assert!(climate.temperature_maxima.iter().all(|w| w.len() == 9));
+        assert!(climate.precipitation_peaks.iter().all(|w| w.len() == 9));
+        assert!(world.resample(0, 2, ResampleMode::Nearest).is_err());
+    }
+}
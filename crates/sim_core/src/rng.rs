@@ -5,17 +5,61 @@
 //! tick. Substreams can be derived deterministically without mutating the
 //! parent stream, which allows kernels to spawn region-level RNGs while
 //! preserving reproducibility.
+//!
+//! The output algorithm is named by [`RngKind`] so other implementations can
+//! reproduce a run bit for bit; stream id and counter derivation are shared by
+//! every kind.
+
+use serde::{Deserialize, Serialize};
+
+/// Named sample algorithm behind every [`Stream`] in a run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RngKind {
+    /// Weyl counter (`+= 0x9E3779B97F4A7C15`) hashed with the stream id through the
+    /// SplitMix64 finalizer. The original algorithm and the default.
+    #[default]
+    #[serde(rename = "splitmix64")]
+    SplitMix64,
+    /// PCG with a 64-bit LCG (multiplier `6364136223846793005`, odd increment
+    /// `stream_id << 1 | 1`) and the RXS-M-XS 64/64 output permutation of the
+    /// pre-advance state.
+    #[serde(rename = "pcg_rxs_m_xs_64")]
+    PcgRxsMXs64,
+}
+
+impl RngKind {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Stream {
     /// Upper 64 bits store the logical stream id; lower 64 bits store the
-    /// rolling counter for splitmix-style generation.
+    /// rolling counter (SplitMix64) or LCG state (PCG).
     state: u128,
+    kind: RngKind,
+}
+
+/// Serializable snapshot of a [`Stream`]'s exact position, for checkpointing.
+///
+/// The halves are stored separately because JSON has no portable 128-bit integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamState {
+    pub stream_id: u64,
+    pub counter: u64,
+    #[serde(default, skip_serializing_if = "RngKind::is_default")]
+    pub kind: RngKind,
 }
 
 impl Stream {
-    /// Construct a stream for the given `(seed, stage, tick)` triple.
+    /// Construct a [`RngKind::SplitMix64`] stream for the given `(seed, stage, tick)` triple.
     pub fn from(seed: u64, stage: &str, tick: u64) -> Self {
+        Self::with_kind(RngKind::default(), seed, stage, tick)
+    }
+
+    /// Construct a stream for `(seed, stage, tick)` that samples with `kind`.
+    pub fn with_kind(kind: RngKind, seed: u64, stage: &str, tick: u64) -> Self {
         let stage_hash = fnv1a64(stage.as_bytes());
         let mut stream_id = seed
             .wrapping_mul(0xA0761D6478BD642F)
@@ -26,6 +70,28 @@ impl Stream {
         let counter = mix64(stream_id ^ 0xD1342543DE82EF95);
         Self {
             state: (u128::from(stream_id) << 64) | u128::from(counter),
+            kind,
+        }
+    }
+
+    pub fn kind(&self) -> RngKind {
+        self.kind
+    }
+
+    /// Capture the stream id and counter so a paused run can resume mid-sequence.
+    pub fn save_state(&self) -> StreamState {
+        StreamState {
+            stream_id: (self.state >> 64) as u64,
+            counter: self.state as u64,
+            kind: self.kind,
+        }
+    }
+
+    /// Rebuild a stream at exactly the position captured by [`Stream::save_state`].
+    pub fn load_state(state: StreamState) -> Self {
+        Self {
+            state: (u128::from(state.stream_id) << 64) | u128::from(state.counter),
+            kind: state.kind,
         }
     }
 
@@ -36,16 +102,37 @@ impl Stream {
         let counter = mix64(derived ^ 0xBF58476D1CE4E5B9);
         Self {
             state: (u128::from(derived) << 64) | u128::from(counter),
+            kind: self.kind,
+        }
+    }
+
+    /// Stream for ensemble member `member`; member 0 is this stream unchanged, so the
+    /// default member reproduces a non-ensemble run exactly.
+    pub fn for_ensemble_member(&self, member: u64) -> Self {
+        if member == 0 {
+            return self.clone();
         }
+        self.derive(stream_label("ensemble_member")).derive(member)
     }
 
     /// Advance the stream and return the next `u64` sample.
     pub fn next_u64(&mut self) -> u64 {
         let stream_id = (self.state >> 64) as u64;
-        let mut counter = self.state as u64;
-        counter = counter.wrapping_add(0x9E3779B97F4A7C15);
-        self.state = (u128::from(stream_id) << 64) | u128::from(counter);
-        mix64(stream_id ^ counter)
+        let counter = self.state as u64;
+        match self.kind {
+            RngKind::SplitMix64 => {
+                let next = counter.wrapping_add(0x9E3779B97F4A7C15);
+                self.state = (u128::from(stream_id) << 64) | u128::from(next);
+                mix64(stream_id ^ next)
+            }
+            RngKind::PcgRxsMXs64 => {
+                let next = counter
+                    .wrapping_mul(0x5851F42D4C957F2D)
+                    .wrapping_add((stream_id << 1) | 1);
+                self.state = (u128::from(stream_id) << 64) | u128::from(next);
+                pcg_rxs_m_xs(counter)
+            }
+        }
     }
 
     /// Advance the stream and return the next `f32` sample in `[0, 1)`.
@@ -64,6 +151,31 @@ impl Stream {
     pub fn next_signed_unit(&mut self) -> f64 {
         self.next_f64() * 2.0 - 1.0
     }
+
+    /// Advance the stream and return an unbiased sample in `[0, bound)`; `bound` must be
+    /// non-zero.
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        debug_assert!(bound > 0, "next_below needs a non-zero bound");
+        // Reject the top partial range so every residue is equally likely.
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let sample = self.next_u64();
+            if sample < limit {
+                return sample % bound;
+            }
+        }
+    }
+
+    /// A permutation of `0..n` from a Fisher–Yates shuffle driven by this stream, for
+    /// visiting regions in a seed-stable order that carries no spatial bias.
+    pub fn shuffle_indices(&mut self, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            let j = self.next_below(i as u64 + 1) as usize;
+            indices.swap(i, j);
+        }
+        indices
+    }
 }
 
 /// Produce a deterministic label for deriving child streams.
@@ -71,7 +183,7 @@ pub fn stream_label(name: &str) -> u64 {
     fnv1a64(name.as_bytes())
 }
 
-fn fnv1a64(bytes: &[u8]) -> u64 {
+pub(crate) fn fnv1a64(bytes: &[u8]) -> u64 {
     let mut hash = 0xcbf29ce484222325u64;
     for &b in bytes {
         hash ^= u64::from(b);
@@ -86,9 +198,14 @@ fn mix64(mut z: u64) -> u64 {
     z ^ (z >> 31)
 }
 
+fn pcg_rxs_m_xs(state: u64) -> u64 {
+    let word = ((state >> ((state >> 59) + 5)) ^ state).wrapping_mul(0xAEF17502108EF2D9);
+    (word >> 43) ^ word
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Stream;
+    use super::{RngKind, Stream, StreamState};
 
     #[test]
     fn derive_is_deterministic() {
@@ -105,4 +222,57 @@ mod tests {
         let mut ecology = Stream::from(1, "ecology", 10);
         assert_ne!(climate.next_u64(), ecology.next_u64());
     }
+
+    #[test]
+    fn saved_state_resumes_exact_sequence() {
+        let mut stream = Stream::from(9, "checkpoint", 3).derive(4);
+        for _ in 0..5 {
+            stream.next_u64();
+        }
+        let json = serde_json::to_string(&stream.save_state()).expect("state serializes");
+        let expected: Vec<u64> = (0..5).map(|_| stream.next_u64()).collect();
+
+        let state: StreamState = serde_json::from_str(&json).expect("state parses");
+        let mut restored = Stream::load_state(state);
+        let replayed: Vec<u64> = (0..5).map(|_| restored.next_u64()).collect();
+        assert_eq!(replayed, expected);
+        assert_eq!(restored.save_state(), stream.save_state());
+    }
+
+    #[test]
+    fn shuffle_indices_is_a_deterministic_permutation() {
+        let shuffle = |tick: u64| Stream::from(11, "kernel:shuffle", tick).shuffle_indices(64);
+        let first = shuffle(3);
+        assert_eq!(first, shuffle(3));
+        assert_ne!(first, shuffle(4));
+
+        let mut sorted = first.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+        assert_ne!(first, sorted);
+
+        let mut stream = Stream::from(11, "kernel:shuffle", 3);
+        assert!(stream.shuffle_indices(0).is_empty());
+        assert_eq!(stream.shuffle_indices(1), vec![0]);
+    }
+
+    #[test]
+    fn rng_kinds_share_derivation_but_not_samples() {
+        let splitmix = Stream::from(5, "kind", 2).derive(3);
+        let pcg = Stream::with_kind(RngKind::PcgRxsMXs64, 5, "kind", 2).derive(3);
+        assert_eq!(pcg.kind(), RngKind::PcgRxsMXs64);
+        assert_eq!(splitmix.save_state().stream_id, pcg.save_state().stream_id);
+
+        let sample =
+            |mut stream: Stream| -> Vec<u64> { (0..4).map(|_| stream.next_u64()).collect() };
+        assert_ne!(sample(splitmix.clone()), sample(pcg.clone()));
+        assert_eq!(sample(pcg.clone()), sample(pcg.clone()));
+
+        let json = serde_json::to_string(&pcg.save_state()).expect("state serializes");
+        let restored = Stream::load_state(serde_json::from_str(&json).expect("state parses"));
+        assert_eq!(sample(restored), sample(pcg));
+        assert!(!serde_json::to_string(&splitmix.save_state())
+            .expect("state serializes")
+            .contains("kind"));
+    }
 }
@@ -1,7 +1,13 @@
+use std::time::Duration;
+
 use anyhow::Result;
 
 use crate::diff::Diff;
 use crate::io::frame::Highlight;
+use crate::kernels::{
+    astronomy, atmosphere, climate, climate_diag, coupler, cryosphere, ecology, forcing,
+    geodynamics, storms,
+};
 use crate::reduce::apply;
 use crate::rng::{stream_label, Stream};
 use crate::world::World;
@@ -23,29 +29,234 @@ impl KernelRun {
     }
 }
 
+/// Stages executed by [`crate::tick_once`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageId {
+    Astronomy,
+    Geodynamics,
+    Forcing,
+    Atmosphere,
+    Storms,
+    Cryosphere,
+    Coupler,
+    ClimateDiag,
+    Climate,
+    Ecology,
+}
+
+/// How a stage is driven each tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageKind {
+    /// Runs through [`run_kernel`] on a substream of the climate stage stream.
+    Kernel,
+    /// Reconciles earlier kernel diffs against the world instead of drawing randomness.
+    Coupler,
+    /// Draws from its own `(seed, label, tick)` stream rather than a climate substream.
+    Diagnostic,
+    /// Applies exogenous inputs attached to the world; draws no randomness.
+    External,
+}
+
+/// One entry in the tick schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stage {
+    pub id: StageId,
+    /// RNG stage label; also identifies the stage in logs and tests.
+    pub label: &'static str,
+    pub kind: StageKind,
+    /// Whether the stage draws from the ensemble-member stream, so its output varies
+    /// across ensemble members. Stages without it may still draw randomness, but from the
+    /// stream every member shares.
+    pub per_member: bool,
+    /// Whether the stage's highlights are forwarded to the frame.
+    pub produces_highlights: bool,
+    /// When set, a non-empty stage chronicle is replaced by this single line.
+    pub chronicle_summary: Option<&'static str>,
+}
+
+const STANDARD_STAGES: &[Stage] = &[
+    Stage {
+        id: StageId::Astronomy,
+        label: astronomy::STAGE,
+        kind: StageKind::Kernel,
+        per_member: false,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Geodynamics,
+        label: geodynamics::STAGE,
+        kind: StageKind::Kernel,
+        per_member: true,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Forcing,
+        label: forcing::STAGE,
+        kind: StageKind::External,
+        per_member: false,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Atmosphere,
+        label: atmosphere::STAGE,
+        kind: StageKind::Kernel,
+        per_member: true,
+        produces_highlights: false,
+        chronicle_summary: Some("Hadley belt drifted northward under seasonal tilt."),
+    },
+    Stage {
+        id: StageId::Storms,
+        label: storms::STAGE,
+        kind: StageKind::Kernel,
+        per_member: true,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Cryosphere,
+        label: cryosphere::STAGE,
+        kind: StageKind::Kernel,
+        per_member: true,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Coupler,
+        label: coupler::STAGE,
+        kind: StageKind::Coupler,
+        per_member: false,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::ClimateDiag,
+        label: climate_diag::STAGE,
+        kind: StageKind::Diagnostic,
+        per_member: false,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Climate,
+        label: climate::CORE_STAGE,
+        kind: StageKind::Kernel,
+        per_member: false,
+        produces_highlights: false,
+        chronicle_summary: None,
+    },
+    Stage {
+        id: StageId::Ecology,
+        label: ecology::STAGE,
+        kind: StageKind::Kernel,
+        per_member: false,
+        produces_highlights: true,
+        chronicle_summary: None,
+    },
+];
+
+/// Ordered stage registry that drives [`crate::tick_once`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    stages: &'static [Stage],
+}
+
+impl Schedule {
+    /// The documented order: astronomy → geodynamics → forcing → atmosphere → storms →
+    /// cryosphere → coupler → climate_diag → climate → ecology.
+    pub const fn standard() -> Self {
+        Self {
+            stages: STANDARD_STAGES,
+        }
+    }
+
+    pub fn stages(&self) -> &'static [Stage] {
+        self.stages
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Wall-clock time spent in one stage, as reported by [`crate::tick_once_timed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StageTiming {
+    pub label: &'static str,
+    pub elapsed: Duration,
+}
+
 pub fn run_kernel<F>(
     world: &mut World,
     aggregate_diff: &mut Diff,
     parent_stream: &Stream,
     stage_label: &str,
-    mut runner: F,
+    runner: F,
 ) -> Result<KernelRun>
 where
     F: FnMut(&mut World, &mut Stream) -> Result<KernelRun>,
 {
-    let mut kernel_rng = parent_stream.derive(stream_label(stage_label));
-    let run = runner(world, &mut kernel_rng)?;
+    let run = run_uncommitted(world, parent_stream, stage_label, runner)?;
     aggregate_diff.merge(&run.diff);
     apply(world, run.diff.clone());
     Ok(run)
 }
 
+/// [`run_kernel`] without the commit: the caller owns applying `run.diff`.
+pub fn run_uncommitted<F>(
+    world: &mut World,
+    parent_stream: &Stream,
+    stage_label: &str,
+    mut runner: F,
+) -> Result<KernelRun>
+where
+    F: FnMut(&mut World, &mut Stream) -> Result<KernelRun>,
+{
+    let mut kernel_rng = parent_stream.derive(stream_label(stage_label));
+    runner(world, &mut kernel_rng)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cause::{Code, Entry};
     use crate::world::{Hazards, Region};
 
+    #[test]
+    fn standard_schedule_matches_documented_order() {
+        let ids: Vec<StageId> = Schedule::standard()
+            .stages()
+            .iter()
+            .map(|stage| stage.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                StageId::Astronomy,
+                StageId::Geodynamics,
+                StageId::Forcing,
+                StageId::Atmosphere,
+                StageId::Storms,
+                StageId::Cryosphere,
+                StageId::Coupler,
+                StageId::ClimateDiag,
+                StageId::Climate,
+                StageId::Ecology,
+            ]
+        );
+        let highlight_stages: Vec<StageId> = Schedule::default()
+            .stages()
+            .iter()
+            .filter(|stage| stage.produces_highlights)
+            .map(|stage| stage.id)
+            .collect();
+        assert_eq!(highlight_stages, vec![StageId::Ecology]);
+    }
+
     fn seed_world() -> World {
         let region = Region {
             id: 0,
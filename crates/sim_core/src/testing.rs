@@ -0,0 +1,251 @@
+//! Golden-run harness for regression checks.
+//!
+//! [`run_golden`] replays a seed for a fixed number of ticks and returns the
+//! NDJSON frame lines exactly as `simstep` writes them. [`first_divergence`]
+//! compares two runs line by line and reports the first tick and JSON field
+//! that differ, so a determinism break points at the kernel output that moved.
+//! [`test_seed`] builds the small seed most unit tests start from.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::io::frame::make_frame;
+use crate::io::seed::{build_world, format_seed_hash, Seed};
+use crate::tick_once;
+
+/// Run `ticks` ticks for the given seed JSON and collect one NDJSON line per frame.
+///
+/// Lines carry no trailing newline so they compare directly against
+/// `str::lines()` output from a stored reference file.
+pub fn run_golden(seed_json: &str, ticks: u64) -> Result<Vec<String>> {
+    let seed = Seed::from_reader(seed_json.as_bytes())?;
+    let mut world = build_world(&seed, None);
+    let mut lines = Vec::with_capacity(ticks as usize);
+    for step in 0..ticks {
+        let next_tick = world.tick + 1;
+        let seed_value = world.seed;
+        let (diff, chronicle, highlights) = tick_once(&mut world, seed_value, next_tick)
+            .with_context(|| format!("golden run failed at tick {}", next_tick))?;
+        let mut frame = make_frame(
+            next_tick,
+            diff,
+            highlights,
+            chronicle,
+            false,
+            world.width,
+            world.height,
+        );
+        if step == 0 {
+            frame.world.seed_hash = world.seed_hash.map(format_seed_hash);
+        }
+        let line = frame.to_ndjson()?;
+        lines.push(line.trim_end_matches('\n').to_string());
+    }
+    Ok(lines)
+}
+
+/// Small seed for tests: one octave of white elevation noise (noise seed 7) and a
+/// 0.2 / -0.2 humidity bias. Tests that need other settings adjust the returned fields.
+pub fn test_seed(name: &str, width: u32, height: u32) -> Seed {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "width": width,
+        "height": height,
+        "elevation_noise": {"octaves": 1, "freq": 0.1, "amp": 1.0, "seed": 7},
+        "humidity_bias": {"equator": 0.2, "poles": -0.2},
+    }))
+    .expect("test seed parses")
+}
+
+/// First point at which two golden runs disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Zero-based line index within the run.
+    pub line: usize,
+    /// Frame tick parsed from the expected line, when available.
+    pub tick: Option<u64>,
+    /// Dotted JSON path of the first differing field (e.g. `diff.temp.r:3`).
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tick = self
+            .tick
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "?".to_string());
+        write!(
+            f,
+            "line {} (t={}) diverged at `{}`\n  expected: {}\n  actual:   {}",
+            self.line + 1,
+            tick,
+            self.field,
+            self.expected,
+            self.actual
+        )
+    }
+}
+
+/// Compare two runs and return the first mismatching line, if any.
+///
+/// A run that is shorter than the other diverges at the first missing line
+/// with the field reported as `<missing>`.
+pub fn first_divergence<E, A>(expected: &[E], actual: &[A]) -> Option<Divergence>
+where
+    E: AsRef<str>,
+    A: AsRef<str>,
+{
+    let shared = expected.len().min(actual.len());
+    for line in 0..shared {
+        let left = expected[line].as_ref();
+        let right = actual[line].as_ref();
+        if left == right {
+            continue;
+        }
+        return Some(describe_line(line, left, right));
+    }
+
+    if expected.len() != actual.len() {
+        let line = shared;
+        let expected_line = expected.get(line).map(|l| l.as_ref().to_string());
+        let actual_line = actual.get(line).map(|l| l.as_ref().to_string());
+        let tick = expected_line
+            .as_deref()
+            .or(actual_line.as_deref())
+            .and_then(parse_tick);
+        return Some(Divergence {
+            line,
+            tick,
+            field: "<missing>".to_string(),
+            expected: expected_line.unwrap_or_else(|| "<none>".to_string()),
+            actual: actual_line.unwrap_or_else(|| "<none>".to_string()),
+        });
+    }
+
+    None
+}
+
+fn describe_line(line: usize, expected: &str, actual: &str) -> Divergence {
+    let parsed = (
+        serde_json::from_str::<Value>(expected),
+        serde_json::from_str::<Value>(actual),
+    );
+    if let (Ok(left), Ok(right)) = parsed {
+        let mut path = Vec::new();
+        if let Some((left_value, right_value)) = first_value_mismatch(&left, &right, &mut path) {
+            return Divergence {
+                line,
+                tick: left.get("t").and_then(Value::as_u64),
+                field: path.join("."),
+                expected: render(left_value),
+                actual: render(right_value),
+            };
+        }
+    }
+
+    Divergence {
+        line,
+        tick: parse_tick(expected),
+        field: "<line>".to_string(),
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+fn first_value_mismatch<'a>(
+    left: &'a Value,
+    right: &'a Value,
+    path: &mut Vec<String>,
+) -> Option<(Option<&'a Value>, Option<&'a Value>)> {
+    match (left, right) {
+        (Value::Object(left_map), Value::Object(right_map)) => {
+            let mut keys: Vec<&String> = left_map.keys().chain(right_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                path.push(key.clone());
+                match (left_map.get(key), right_map.get(key)) {
+                    (Some(l), Some(r)) => {
+                        if let Some(found) = first_value_mismatch(l, r, path) {
+                            return Some(found);
+                        }
+                    }
+                    (l, r) => return Some((l, r)),
+                }
+                path.pop();
+            }
+            None
+        }
+        (Value::Array(left_items), Value::Array(right_items)) => {
+            let longest = left_items.len().max(right_items.len());
+            for index in 0..longest {
+                path.push(index.to_string());
+                match (left_items.get(index), right_items.get(index)) {
+                    (Some(l), Some(r)) => {
+                        if let Some(found) = first_value_mismatch(l, r, path) {
+                            return Some(found);
+                        }
+                    }
+                    (l, r) => return Some((l, r)),
+                }
+                path.pop();
+            }
+            None
+        }
+        _ if left == right => None,
+        _ => Some((Some(left), Some(right))),
+    }
+}
+
+fn render(value: Option<&Value>) -> String {
+    value
+        .map(Value::to_string)
+        .unwrap_or_else(|| "<absent>".to_string())
+}
+
+fn parse_tick(line: &str) -> Option<u64> {
+    serde_json::from_str::<Value>(line)
+        .ok()
+        .and_then(|value| value.get("t").and_then(Value::as_u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_runs_do_not_diverge() {
+        let lines = ["{\"t\":1}", "{\"t\":2}"];
+        assert_eq!(first_divergence(&lines, &lines), None);
+    }
+
+    #[test]
+    fn divergence_reports_tick_and_nested_field() {
+        let expected = [
+            r#"{"t":1,"diff":{"temp":{"r:0":10}}}"#,
+            r#"{"t":2,"diff":{"temp":{"r:0":12,"r:1":4}}}"#,
+        ];
+        let actual = [
+            r#"{"t":1,"diff":{"temp":{"r:0":10}}}"#,
+            r#"{"t":2,"diff":{"temp":{"r:0":12,"r:1":5}}}"#,
+        ];
+        let divergence = first_divergence(&expected, &actual).expect("runs diverge");
+        assert_eq!(divergence.line, 1);
+        assert_eq!(divergence.tick, Some(2));
+        assert_eq!(divergence.field, "diff.temp.r:1");
+        assert_eq!(divergence.expected, "4");
+        assert_eq!(divergence.actual, "5");
+    }
+
+    #[test]
+    fn truncated_run_diverges_at_missing_line() {
+        let expected = [r#"{"t":1}"#, r#"{"t":2}"#];
+        let actual = [r#"{"t":1}"#];
+        let divergence = first_divergence(&expected, &actual).expect("runs diverge");
+        assert_eq!(divergence.line, 1);
+        assert_eq!(divergence.tick, Some(2));
+        assert_eq!(divergence.field, "<missing>");
+    }
+}
@@ -1,23 +1,23 @@
 use std::collections::VecDeque;
 
+use anyhow::{bail, ensure, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::detmath;
+use crate::diff::{Diff, DiffField};
+use crate::io::frame::FrameDiff;
+use crate::kernels::climate_diag::DiagWeights;
+use crate::kernels::ecology::{DROUGHT_ALERT_THRESHOLD, FLOOD_ALERT_THRESHOLD};
+use crate::kernels::forcing::ForcingSeries;
+use crate::rng::{fnv1a64, RngKind};
+
 /// Hazard gauges for a region.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Hazards {
     pub drought: u16,
     pub flood: u16,
 }
 
-impl Default for Hazards {
-    fn default() -> Self {
-        Self {
-            drought: 0,
-            flood: 0,
-        }
-    }
-}
-
 /// Region level state tracked by the simulation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Region {
@@ -38,9 +38,131 @@ pub struct Region {
 }
 
 impl Region {
+    /// A sea-level equatorial region with mid-range water and soil, for test fixtures.
+    ///
+    /// Chain the `with_*` setters to vary the fields a test cares about.
+    pub fn sample(id: u32, x: u32, y: u32) -> Self {
+        Self {
+            id,
+            x,
+            y,
+            elevation_m: 0,
+            latitude_deg: 0.0,
+            biome: 0,
+            water: 5_000,
+            soil: 5_000,
+            temperature_tenths_c: 0,
+            precipitation_mm: 0,
+            albedo_milli: 400,
+            freshwater_flux_tenths_mm: 0,
+            ice_mass_kilotons: 0,
+            hazards: Hazards::default(),
+        }
+    }
+
+    pub fn with_elevation(mut self, elevation_m: i32) -> Self {
+        self.elevation_m = elevation_m;
+        self
+    }
+
+    pub fn with_latitude(mut self, latitude_deg: f64) -> Self {
+        self.latitude_deg = latitude_deg;
+        self
+    }
+
+    pub fn with_biome(mut self, biome: u8) -> Self {
+        self.biome = biome;
+        self
+    }
+
+    pub fn with_water(mut self, water: u16) -> Self {
+        self.water = water;
+        self
+    }
+
+    pub fn with_soil(mut self, soil: u16) -> Self {
+        self.soil = soil;
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature_tenths_c: i16) -> Self {
+        self.temperature_tenths_c = temperature_tenths_c;
+        self
+    }
+
+    pub fn with_precipitation(mut self, precipitation_mm: u16) -> Self {
+        self.precipitation_mm = precipitation_mm;
+        self
+    }
+
+    pub fn with_albedo(mut self, albedo_milli: u16) -> Self {
+        self.albedo_milli = albedo_milli;
+        self
+    }
+
+    pub fn with_freshwater_flux(mut self, freshwater_flux_tenths_mm: u16) -> Self {
+        self.freshwater_flux_tenths_mm = freshwater_flux_tenths_mm;
+        self
+    }
+
+    pub fn with_ice_mass(mut self, ice_mass_kilotons: u32) -> Self {
+        self.ice_mass_kilotons = ice_mass_kilotons;
+        self
+    }
+
+    pub fn with_hazards(mut self, hazards: Hazards) -> Self {
+        self.hazards = hazards;
+        self
+    }
+
     pub fn index(&self) -> usize {
         self.id as usize
     }
+
+    /// Surface air pressure from [`pressure_hpa`].
+    pub fn pressure_hpa(&self) -> f64 {
+        pressure_hpa(self.elevation_m)
+    }
+
+    /// Current value of `field` widened to `i32`; ice mass saturates at `i32::MAX`.
+    pub fn field_value(&self, field: RegionField) -> i32 {
+        match field {
+            RegionField::Temperature => i32::from(self.temperature_tenths_c),
+            RegionField::Precipitation => i32::from(self.precipitation_mm),
+            RegionField::Albedo => i32::from(self.albedo_milli),
+            RegionField::Biome => i32::from(self.biome),
+            RegionField::Elevation => self.elevation_m,
+            RegionField::Water => i32::from(self.water),
+            RegionField::Soil => i32::from(self.soil),
+            RegionField::IceMass => i32::try_from(self.ice_mass_kilotons).unwrap_or(i32::MAX),
+        }
+    }
+}
+
+/// Applied-state [`Region`] fields exposed to renderers through [`World::field_values`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RegionField {
+    Temperature,
+    Precipitation,
+    Albedo,
+    Biome,
+    Elevation,
+    Water,
+    Soil,
+    IceMass,
+}
+
+impl RegionField {
+    pub const ALL: [RegionField; 8] = [
+        RegionField::Temperature,
+        RegionField::Precipitation,
+        RegionField::Albedo,
+        RegionField::Biome,
+        RegionField::Elevation,
+        RegionField::Water,
+        RegionField::Soil,
+        RegionField::IceMass,
+    ];
 }
 
 /// Global world state.
@@ -52,19 +174,131 @@ pub struct World {
     pub height: u32,
     pub regions: Vec<Region>,
     pub climate: ClimateState,
+    /// Provenance hash of the seed document and effective world seed, when built from one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_hash: Option<u64>,
+    /// Sample algorithm for every stage stream this world derives.
+    #[serde(default, skip_serializing_if = "RngKind::is_default")]
+    pub rng_kind: RngKind,
+    /// Ensemble member mixed into the per-member kernels' streams (geodynamics,
+    /// atmosphere, storms, cryosphere); geometry built from the seed never sees it.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ensemble_member: u64,
+    /// How the grid edges connect for [`World::neighbors4`].
+    #[serde(default, skip_serializing_if = "WrapMode::is_default")]
+    pub wrap: WrapMode,
+    /// Kernel knobs set by the seed's `tuning` section; `tick_once` hands them to the
+    /// kernels every tick.
+    #[serde(default, skip_serializing_if = "Tuning::is_default")]
+    pub tuning: Tuning,
+    /// Optional exogenous temperature forcing applied before the atmosphere each tick.
+    #[serde(skip)]
+    pub forcing: Option<ForcingSeries>,
 }
 
 impl World {
+    /// Debug builds assert [`World::validate_invariants`] on the supplied regions.
     pub fn new(seed: u64, width: u32, height: u32, regions: Vec<Region>) -> Self {
         let climate = ClimateState::from_regions(&regions);
-        Self {
+        let world = Self {
             tick: 0,
             seed,
             width,
             height,
             regions,
             climate,
+            seed_hash: None,
+            rng_kind: RngKind::default(),
+            ensemble_member: 0,
+            wrap: WrapMode::default(),
+            tuning: Tuning::default(),
+            forcing: None,
+        };
+        debug_assert!(
+            world.validate_invariants().is_ok(),
+            "{:#}",
+            world.validate_invariants().unwrap_err()
+        );
+        world
+    }
+
+    /// Check region state that kernels assume but the type does not enforce.
+    ///
+    /// Every `latitude_deg` must be finite and within [-90, 90]; insolation and belt math
+    /// are undefined outside that range.
+    pub fn validate_invariants(&self) -> Result<()> {
+        for region in &self.regions {
+            ensure!(
+                (-90.0..=90.0).contains(&region.latitude_deg),
+                "region {} latitude {} is outside [-90, 90]",
+                region.id,
+                region.latitude_deg
+            );
         }
+        Ok(())
+    }
+
+    /// Build a world from explicit row-major fields instead of seed noise.
+    ///
+    /// Regions get contiguous ids, `latitude_fn(y, height)` supplies each row's latitude
+    /// (see [`latitude_from_row`] for the seed generator's mapping), and every other
+    /// meter starts at zero.
+    pub fn from_fields<L: Fn(u32, u32) -> f64>(
+        seed: u64,
+        width: u32,
+        height: u32,
+        elevation: &[i32],
+        biome: &[u8],
+        latitude_fn: L,
+    ) -> Result<Self> {
+        let cells = width as usize * height as usize;
+        ensure!(
+            elevation.len() == cells,
+            "elevation has {} cells but a {}x{} grid needs {}",
+            elevation.len(),
+            width,
+            height,
+            cells
+        );
+        ensure!(
+            biome.len() == cells,
+            "biome has {} cells but a {}x{} grid needs {}",
+            biome.len(),
+            width,
+            height,
+            cells
+        );
+
+        let mut regions = Vec::with_capacity(cells);
+        for y in 0..height {
+            let latitude_deg = latitude_fn(y, height);
+            ensure!(
+                (-90.0..=90.0).contains(&latitude_deg),
+                "row {} latitude {} is outside [-90, 90]",
+                y,
+                latitude_deg
+            );
+            for x in 0..width {
+                let index = regions.len();
+                regions.push(Region {
+                    id: index as u32,
+                    x,
+                    y,
+                    elevation_m: elevation[index],
+                    latitude_deg,
+                    biome: biome[index],
+                    water: 0,
+                    soil: 0,
+                    temperature_tenths_c: 0,
+                    precipitation_mm: 0,
+                    albedo_milli: 0,
+                    freshwater_flux_tenths_mm: 0,
+                    ice_mass_kilotons: 0,
+                    hazards: Hazards::default(),
+                });
+            }
+        }
+        Ok(Self::new(seed, width, height, regions))
     }
 
     pub fn region_key(index: usize) -> String {
@@ -74,6 +308,442 @@ impl World {
     pub fn region_index_from_key(key: &str) -> Option<usize> {
         key.strip_prefix("r:").and_then(|v| v.parse::<usize>().ok())
     }
+
+    /// Stable FNV-1a hash of the simulation state: tick, seed, grid, RNG kind, regions, and
+    /// every climate field including the rolling windows plain serialization skips.
+    ///
+    /// The seed provenance hash and any attached forcing series are left out, so worlds
+    /// that would tick identically hash identically.
+    pub fn content_hash(&self) -> u64 {
+        fnv1a64(&self.content_bytes())
+    }
+
+    fn content_bytes(&self) -> Vec<u8> {
+        let climate = &self.climate;
+        let content = WorldContent {
+            tick: self.tick,
+            seed: self.seed,
+            width: self.width,
+            height: self.height,
+            rng_kind: self.rng_kind,
+            ensemble_member: self.ensemble_member,
+            wrap: self.wrap,
+            tuning: &self.tuning,
+            regions: &self.regions,
+            climate,
+            temperature_maxima: &climate.temperature_maxima,
+            precipitation_peaks: &climate.precipitation_peaks,
+            snowpack_mm: &climate.snowpack_mm,
+            permafrost_active_cm: &climate.permafrost_active_cm,
+            monsoon_active: &climate.monsoon_active,
+            volcanic_episode: &climate.volcanic_episode,
+        };
+        serde_json::to_vec(&content).expect("world content serializes to json")
+    }
+
+    /// `field` for every region as a flat array in region order, ready for heatmaps.
+    pub fn field_values(&self, field: RegionField) -> Vec<i32> {
+        self.regions
+            .iter()
+            .map(|region| region.field_value(field))
+            .collect()
+    }
+
+    /// Regions whose absolute latitude falls in `[min_lat, max_lat)`, in index order.
+    ///
+    /// Bands are hemisphere-symmetric to match the climate kernel's latitude belts.
+    pub fn regions_in_belt(
+        &self,
+        min_lat: f64,
+        max_lat: f64,
+    ) -> impl Iterator<Item = (usize, &Region)> + '_ {
+        self.regions.iter().enumerate().filter(move |(_, region)| {
+            let lat = region.latitude_deg.abs();
+            lat >= min_lat && lat < max_lat
+        })
+    }
+
+    /// Mean of `f` over each latitude band (see [`World::regions_in_belt`]).
+    ///
+    /// Sums run in region index order so results are deterministic; empty bands yield `0.0`.
+    pub fn zonal_mean<F: Fn(&Region) -> f64>(&self, bands: &[(f64, f64)], f: F) -> Vec<f64> {
+        bands
+            .iter()
+            .map(|&(min_lat, max_lat)| {
+                let (sum, count) = self
+                    .regions_in_belt(min_lat, max_lat)
+                    .fold((0.0, 0usize), |(sum, count), (_, region)| {
+                        (sum + f(region), count + 1)
+                    });
+                if count == 0 {
+                    0.0
+                } else {
+                    sum / count as f64
+                }
+            })
+            .collect()
+    }
+
+    /// Each region paired with its [`RegionClimate`] slots, in index order.
+    ///
+    /// Grows the climate vectors to the region count first, so the pairs never fall out
+    /// of alignment the way parallel indexing can.
+    pub fn iter_regions_mut_with_climate(
+        &mut self,
+    ) -> impl Iterator<Item = (&mut Region, RegionClimate<'_>)> {
+        self.climate.ensure_region_capacity(self.regions.len());
+        self.regions.iter_mut().zip(self.climate.iter_regions_mut())
+    }
+
+    /// Orthogonal neighbours of region `index`, in [`NEIGHBOR_OFFSETS`] order.
+    ///
+    /// Off-grid offsets are dropped, except that under [`WrapMode::Cylinder`] the west
+    /// and east edges join on grids wider than two cells (narrower grids would wrap onto
+    /// the cell itself or a neighbour already listed). Out-of-range indices yield nothing.
+    pub fn neighbors4(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .filter_map(move |&(dx, dy)| self.neighbor_at(index, dx, dy))
+    }
+
+    /// Region `(dx, dy)` cells away from region `index`, under the same edge rules as
+    /// [`World::neighbors4`].
+    pub fn neighbor_at(&self, index: usize, dx: i32, dy: i32) -> Option<usize> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        if width <= 0 || index >= self.regions.len() {
+            return None;
+        }
+        let x = (index % width as usize) as i32;
+        let y = (index / width as usize) as i32;
+        let nx = if self.wrap == WrapMode::Cylinder && width > 2 {
+            (x + dx).rem_euclid(width)
+        } else {
+            x + dx
+        };
+        let ny = y + dy;
+        if nx < 0 || nx >= width || ny < 0 || ny >= height {
+            return None;
+        }
+        let neighbor = (ny * width + nx) as usize;
+        (neighbor < self.regions.len()).then_some(neighbor)
+    }
+
+    /// [`World::neighbors4`] of the region at `(x, y)`.
+    pub fn neighbor_indices(&self, x: u32, y: u32) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors4(y as usize * self.width as usize + x as usize)
+    }
+
+    /// Apply an externally authored [`FrameDiff`], such as an editing tool's change,
+    /// through [`crate::reduce::apply`] without advancing the tick.
+    ///
+    /// Every `"r:<n>"` key must parse and name one of this world's regions; otherwise
+    /// nothing is applied.
+    pub fn apply_frame_diff(&mut self, edit: &FrameDiff) -> Result<()> {
+        let diff = edit.to_diff().context("invalid frame diff")?;
+        let region_count = self.regions.len();
+        let stray = DiffField::ALL
+            .iter()
+            .flat_map(|&field| {
+                diff.iter_regions(field)
+                    .map(move |(region, _)| (field.name(), region))
+            })
+            .chain(diff.wind.iter().map(|vector| ("wind", vector.region)))
+            .find(|&(_, region)| region as usize >= region_count);
+        if let Some((field, region)) = stray {
+            bail!(
+                "{} edit for {} is outside the world's {} regions",
+                field,
+                Self::region_key(region as usize),
+                region_count
+            );
+        }
+        crate::reduce::apply(self, diff);
+        Ok(())
+    }
+
+    /// Region-level changes that would turn `self` into `other`, for A/B debugging.
+    ///
+    /// Water and soil are recorded as deltas (`other - self`); every other field carries
+    /// `other`'s value. Only regions present in both worlds are compared, and equal
+    /// fields are left out, so identical worlds yield an empty diff.
+    pub fn diff_against(&self, other: &World) -> Diff {
+        let mut diff = Diff::default();
+        for (index, (before, after)) in self.regions.iter().zip(&other.regions).enumerate() {
+            if before.biome != after.biome {
+                diff.record_biome(index, after.biome);
+            }
+            diff.record_water_delta(index, i32::from(after.water) - i32::from(before.water));
+            diff.record_soil_delta(index, i32::from(after.soil) - i32::from(before.soil));
+            if before.elevation_m != after.elevation_m {
+                diff.record_elevation(index, after.elevation_m);
+            }
+            if before.temperature_tenths_c != after.temperature_tenths_c {
+                diff.record_temperature(index, i32::from(after.temperature_tenths_c));
+            }
+            if before.precipitation_mm != after.precipitation_mm {
+                diff.record_precipitation(index, i32::from(after.precipitation_mm));
+            }
+            if before.albedo_milli != after.albedo_milli {
+                diff.record_albedo(index, i32::from(after.albedo_milli));
+            }
+            if before.freshwater_flux_tenths_mm != after.freshwater_flux_tenths_mm {
+                diff.record_freshwater_flux(index, i32::from(after.freshwater_flux_tenths_mm));
+            }
+            if before.ice_mass_kilotons != after.ice_mass_kilotons {
+                diff.record_ice_mass(
+                    index,
+                    i32::try_from(after.ice_mass_kilotons).unwrap_or(i32::MAX),
+                );
+            }
+            if before.hazards.drought != after.hazards.drought
+                || before.hazards.flood != after.hazards.flood
+            {
+                diff.record_hazard(index, after.hazards.drought, after.hazards.flood);
+            }
+            let baseline = |world: &World| {
+                world
+                    .climate
+                    .temperature_baseline_tenths
+                    .get(index)
+                    .copied()
+            };
+            if let (Some(before_baseline), Some(after_baseline)) = (baseline(self), baseline(other))
+            {
+                if before_baseline != after_baseline {
+                    diff.record_temperature_baseline(index, i32::from(after_baseline));
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// Semantic world content hashed by [`World::content_hash`] and compared by `==`.
+#[derive(Serialize)]
+struct WorldContent<'a> {
+    tick: u64,
+    seed: u64,
+    width: u32,
+    height: u32,
+    rng_kind: RngKind,
+    #[serde(skip_serializing_if = "is_zero")]
+    ensemble_member: u64,
+    #[serde(skip_serializing_if = "WrapMode::is_default")]
+    wrap: WrapMode,
+    #[serde(skip_serializing_if = "Tuning::is_default")]
+    tuning: &'a Tuning,
+    regions: &'a [Region],
+    climate: &'a ClimateState,
+    temperature_maxima: &'a [VecDeque<i16>],
+    precipitation_peaks: &'a [VecDeque<u16>],
+    snowpack_mm: &'a [i32],
+    permafrost_active_cm: &'a [i32],
+    monsoon_active: &'a [bool],
+    volcanic_episode: &'a [u16],
+}
+
+/// Equal when [`World::content_hash`] covers identical content; provenance and forcing
+/// are ignored.
+impl PartialEq for World {
+    fn eq(&self, other: &Self) -> bool {
+        self.content_bytes() == other.content_bytes()
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// Orthogonal neighbour offsets in canonical order: west, east, north, south.
+///
+/// Every kernel that visits or ranks neighbours walks them in this order, so it doubles
+/// as the tie-break rule.
+pub const NEIGHBOR_OFFSETS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Lowest-elevation orthogonal neighbour of `(x, y)`, whether or not it is below `(x, y)`.
+///
+/// Equal elevations resolve to the earliest neighbour in [`NEIGHBOR_OFFSETS`] order
+/// (W, E, N, S), so flow routing never depends on region iteration order.
+pub fn lowest_neighbor(world: &World, x: u32, y: u32) -> Option<usize> {
+    // `min_by_key` keeps the first of several equal minima.
+    world
+        .neighbor_indices(x, y)
+        .min_by_key(|&index| world.regions[index].elevation_m)
+}
+
+/// Mean sea-level pressure of the standard atmosphere, in hPa.
+pub const SEA_LEVEL_PRESSURE_HPA: f64 = 1_013.25;
+/// Sea-level air temperature of the standard atmosphere, in kelvin.
+const SEA_LEVEL_TEMPERATURE_K: f64 = 288.15;
+/// Standard-atmosphere lapse rate, in kelvin per metre.
+const STANDARD_LAPSE_K_PER_M: f64 = 0.006_5;
+/// Barometric exponent `g·M / (R·L)` for the standard atmosphere.
+const PRESSURE_EXPONENT: f64 = 5.255_88;
+
+/// Surface air pressure in hPa at `elevation_m`, from the standard-atmosphere barometric
+/// formula. Below sea level the surface is open water, so pressure stays at
+/// [`SEA_LEVEL_PRESSURE_HPA`].
+pub fn pressure_hpa(elevation_m: i32) -> f64 {
+    let height_m = f64::from(elevation_m.max(0));
+    let ratio = 1.0 - STANDARD_LAPSE_K_PER_M * height_m / SEA_LEVEL_TEMPERATURE_K;
+    SEA_LEVEL_PRESSURE_HPA * detmath::powf(ratio, PRESSURE_EXPONENT)
+}
+
+/// How much colder than sea level, in °C, air at `pressure_hpa` is in the same standard
+/// atmosphere as [`pressure_hpa`] — 6.5 °C per kilometre of altitude.
+pub fn pressure_cooling_c(pressure_hpa: f64) -> f64 {
+    let ratio = detmath::powf(
+        pressure_hpa / SEA_LEVEL_PRESSURE_HPA,
+        PRESSURE_EXPONENT.recip(),
+    );
+    SEA_LEVEL_TEMPERATURE_K * (1.0 - ratio)
+}
+
+/// Height in km at which the standard atmosphere of [`pressure_hpa`] has `pressure_hpa`;
+/// never below sea level.
+pub fn pressure_altitude_km(pressure_hpa: f64) -> f64 {
+    (pressure_cooling_c(pressure_hpa) / (STANDARD_LAPSE_K_PER_M * 1_000.0)).max(0.0)
+}
+
+/// Latitude at the centre of grid row `y`, running from north (+90°) to south (-90°).
+pub fn latitude_from_row(y: u32, height: u32) -> f64 {
+    let ratio = (f64::from(y) + 0.5) / f64::from(height);
+    90.0 - ratio * 180.0
+}
+
+/// [`latitude_from_row`] for an equal-area grid: rows split the sphere into bands of
+/// equal surface area, so they crowd toward the equator.
+pub fn latitude_equal_area(y: u32, height: u32) -> f64 {
+    let ratio = (f64::from(y) + 0.5) / f64::from(height);
+    (1.0 - 2.0 * ratio).clamp(-1.0, 1.0).asin().to_degrees()
+}
+
+/// How the grid edges connect for neighbour lookups.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapMode {
+    /// Every edge is a boundary.
+    #[default]
+    Plane,
+    /// The west and east edges join, as around a globe; the top and bottom rows stay
+    /// boundaries.
+    Cylinder,
+}
+
+impl WrapMode {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Optional kernel couplings and thresholds a run can opt into; every default matches the
+/// kernel's own default parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tuning {
+    /// [`crate::kernels::atmosphere::AtmosphereParams::evapotranspiration`].
+    pub evapotranspiration: f64,
+    /// [`crate::kernels::atmosphere::AtmosphereParams::ocean_inertia`], `0..=1`.
+    pub ocean_inertia: f64,
+    /// [`crate::kernels::atmosphere::AtmosphereParams::arid_floor_mm`].
+    pub arid_floor_mm: i32,
+    /// [`crate::kernels::atmosphere::AtmosphereParams::cold_air_pooling`].
+    pub cold_air_pooling: f64,
+    /// [`crate::kernels::geodynamics::GeodynamicsParams::episode_ticks`]; `0` keeps
+    /// volcanic events unclustered.
+    pub volcanic_episode_ticks: u16,
+    /// [`crate::kernels::geodynamics::GeodynamicsParams::episode_rate_multiplier`].
+    pub volcanic_episode_rate_multiplier: f64,
+    /// [`crate::kernels::coupler::AlbedoFeedbackParams::carry_residual`].
+    pub albedo_carry_residual: bool,
+    /// [`crate::kernels::ecology::EcologyParams::drought_alert`].
+    pub drought_alert: u16,
+    /// [`crate::kernels::ecology::EcologyParams::flood_alert`].
+    pub flood_alert: u16,
+    /// [`crate::kernels::climate_diag::ClimateDiagOptions::weights`].
+    pub diag_weights: DiagWeights,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            evapotranspiration: 0.0,
+            ocean_inertia: 0.0,
+            arid_floor_mm: 0,
+            cold_air_pooling: 0.0,
+            volcanic_episode_ticks: 0,
+            volcanic_episode_rate_multiplier: 10.0,
+            albedo_carry_residual: false,
+            drought_alert: DROUGHT_ALERT_THRESHOLD,
+            flood_alert: FLOOD_ALERT_THRESHOLD,
+            diag_weights: DiagWeights::default(),
+        }
+    }
+}
+
+impl Tuning {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Reject values the kernels cannot use: negative or non-finite rates, an ocean
+    /// inertia outside `0..=1`, a volcanic multiplier below 1, or invalid diag weights.
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("evapotranspiration", self.evapotranspiration),
+            ("cold_air_pooling", self.cold_air_pooling),
+        ] {
+            ensure!(
+                value.is_finite() && value >= 0.0,
+                "tuning {} must be a non-negative number, got {}",
+                name,
+                value
+            );
+        }
+        ensure!(
+            self.ocean_inertia.is_finite() && (0.0..=1.0).contains(&self.ocean_inertia),
+            "tuning ocean_inertia must be a number within 0..=1, got {}",
+            self.ocean_inertia
+        );
+        ensure!(
+            self.arid_floor_mm >= 0,
+            "tuning arid_floor_mm must not be negative, got {}",
+            self.arid_floor_mm
+        );
+        ensure!(
+            self.volcanic_episode_rate_multiplier.is_finite()
+                && self.volcanic_episode_rate_multiplier >= 1.0,
+            "tuning volcanic_episode_rate_multiplier must be at least 1, got {}",
+            self.volcanic_episode_rate_multiplier
+        );
+        self.diag_weights.validate()
+    }
+}
+
+/// How grid rows map onto latitude.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Projection {
+    /// Rows evenly spaced in latitude ([`latitude_from_row`]); polar rows cover less area.
+    #[default]
+    Equirectangular,
+    /// Rows of equal surface area ([`latitude_equal_area`]), so equal-weight global means
+    /// weigh every region alike.
+    EqualArea,
+}
+
+impl Projection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Latitude at the centre of grid row `y` of `height`.
+    pub fn latitude(&self, y: u32, height: u32) -> f64 {
+        match self {
+            Self::Equirectangular => latitude_from_row(y, height),
+            Self::EqualArea => latitude_equal_area(y, height),
+        }
+    }
 }
 
 /// Slow-changing climate coordination state carried between ticks.
@@ -82,18 +752,146 @@ pub struct ClimateState {
     pub temperature_baseline_tenths: Vec<i16>,
     pub last_albedo_milli: Vec<i32>,
     pub last_insolation_tenths: Vec<i32>,
+    /// Tide envelope last committed for each region, so astronomy can skip unchanged values.
+    #[serde(default)]
+    pub last_tide_envelope_tenths: Vec<i32>,
     #[serde(skip)]
     pub temperature_maxima: Vec<VecDeque<i16>>,
     #[serde(skip)]
     pub precipitation_peaks: Vec<VecDeque<u16>>,
+    /// Snow water equivalent held by each region, in millimetres; the cryosphere
+    /// accumulates and melts it. Skipped by plain world JSON and carried by snapshots.
     #[serde(skip)]
     pub snowpack_mm: Vec<i32>,
+    /// Seasonal thaw depth of each region's permafrost active layer, in centimetres
+    /// (0..=300); thaw causes fire when it deepens. Skipped by plain world JSON and
+    /// carried by snapshots.
     #[serde(skip)]
     pub permafrost_active_cm: Vec<i32>,
+    /// Whether each region is inside an active monsoon, so onset/retreat fire on transitions.
+    #[serde(skip)]
+    pub monsoon_active: Vec<bool>,
+    /// Ticks left in each region's volcanic episode; zero unless geodynamics clustering
+    /// is enabled.
+    #[serde(skip)]
+    pub volcanic_episode: Vec<u16>,
     pub sea_level_equivalent_mm: i32,
+    /// Forcing offset already folded into the baselines, in tenths of °C.
+    #[serde(default)]
+    pub applied_forcing_tenths: i32,
+    /// Samples held by each rolling extreme window; change it with
+    /// [`ClimateState::set_extreme_window`] so the windows resize with it.
+    #[serde(
+        default = "default_extreme_window",
+        skip_serializing_if = "is_default_extreme_window"
+    )]
+    pub extreme_window: usize,
+    /// Ticks per spring–neap–spring tide cycle; the astronomy kernel derives the lunar
+    /// phase from the tick and this period (see [`ClimateState::lunar_wave`]).
+    #[serde(
+        default = "default_lunar_period",
+        skip_serializing_if = "is_default_lunar_period"
+    )]
+    pub lunar_period_ticks: u32,
+    /// Live mid-latitude storm centres, in genesis order; the storms kernel drifts,
+    /// ages, and replaces them each tick.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub storm_tracks: Vec<StormCell>,
+}
+
+/// One live storm centre carried between ticks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StormCell {
+    pub x: u32,
+    pub y: u32,
+    /// Ticks the storm survives after this one.
+    pub ticks_left: u32,
+}
+
+/// Mutable view of one region's slots across the per-region [`ClimateState`] vectors.
+#[derive(Debug)]
+pub struct RegionClimate<'a> {
+    pub temperature_baseline_tenths: &'a mut i16,
+    pub last_albedo_milli: &'a mut i32,
+    pub last_insolation_tenths: &'a mut i32,
+    pub last_tide_envelope_tenths: &'a mut i32,
+    pub temperature_maxima: &'a mut VecDeque<i16>,
+    pub precipitation_peaks: &'a mut VecDeque<u16>,
+    pub snowpack_mm: &'a mut i32,
+    pub permafrost_active_cm: &'a mut i32,
+    pub monsoon_active: &'a mut bool,
+    pub volcanic_episode: &'a mut u16,
+}
+
+/// Steps every per-region vector together; backs [`ClimateState::iter_regions_mut`].
+struct RegionClimateIterMut<'a> {
+    temperature_baseline_tenths: std::slice::IterMut<'a, i16>,
+    last_albedo_milli: std::slice::IterMut<'a, i32>,
+    last_insolation_tenths: std::slice::IterMut<'a, i32>,
+    last_tide_envelope_tenths: std::slice::IterMut<'a, i32>,
+    temperature_maxima: std::slice::IterMut<'a, VecDeque<i16>>,
+    precipitation_peaks: std::slice::IterMut<'a, VecDeque<u16>>,
+    snowpack_mm: std::slice::IterMut<'a, i32>,
+    permafrost_active_cm: std::slice::IterMut<'a, i32>,
+    monsoon_active: std::slice::IterMut<'a, bool>,
+    volcanic_episode: std::slice::IterMut<'a, u16>,
+}
+
+impl<'a> Iterator for RegionClimateIterMut<'a> {
+    type Item = RegionClimate<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(RegionClimate {
+            temperature_baseline_tenths: self.temperature_baseline_tenths.next()?,
+            last_albedo_milli: self.last_albedo_milli.next()?,
+            last_insolation_tenths: self.last_insolation_tenths.next()?,
+            last_tide_envelope_tenths: self.last_tide_envelope_tenths.next()?,
+            temperature_maxima: self.temperature_maxima.next()?,
+            precipitation_peaks: self.precipitation_peaks.next()?,
+            snowpack_mm: self.snowpack_mm.next()?,
+            permafrost_active_cm: self.permafrost_active_cm.next()?,
+            monsoon_active: self.monsoon_active.next()?,
+            volcanic_episode: self.volcanic_episode.next()?,
+        })
+    }
+}
+
+/// Default rolling-window length for heat and precipitation extremes: a season and a
+/// half, so one seasonal swing alone rarely spans a whole window.
+pub const DEFAULT_EXTREME_WINDOW: usize = 6;
+/// Shortest extreme window; a single sample has no spread to measure.
+pub const MIN_EXTREME_WINDOW: usize = 2;
+
+/// Default tide cycle: two spring ticks, then two neap ticks.
+pub const DEFAULT_LUNAR_PERIOD_TICKS: u32 = 4;
+/// Shortest tide cycle; one spring tick and one neap tick.
+pub const MIN_LUNAR_PERIOD_TICKS: u32 = 2;
+
+fn default_lunar_period() -> u32 {
+    DEFAULT_LUNAR_PERIOD_TICKS
+}
+
+fn is_default_lunar_period(period: &u32) -> bool {
+    *period == DEFAULT_LUNAR_PERIOD_TICKS
+}
+
+fn default_extreme_window() -> usize {
+    DEFAULT_EXTREME_WINDOW
 }
 
-pub(crate) const EXTREME_WINDOW: usize = 6; // TODO(agents): rationale
+fn is_default_extreme_window(len: &usize) -> bool {
+    *len == DEFAULT_EXTREME_WINDOW
+}
+
+fn resize_window<T: Copy + Default>(window: &mut VecDeque<T>, len: usize) {
+    while window.len() > len {
+        window.pop_front();
+    }
+    let oldest = window.front().copied().unwrap_or_default();
+    while window.len() < len {
+        window.push_front(oldest);
+    }
+}
 
 impl ClimateState {
     pub fn from_regions(regions: &[Region]) -> Self {
@@ -105,24 +903,57 @@ impl ClimateState {
         let last_insolation_tenths = vec![0; regions.len()];
         let mut temperature_maxima = Vec::with_capacity(regions.len());
         let mut precipitation_peaks = Vec::with_capacity(regions.len());
-        let mut snowpack_mm = Vec::with_capacity(regions.len());
-        let mut permafrost_active_cm = Vec::with_capacity(regions.len());
         for _ in regions {
-            temperature_maxima.push(Self::new_temperature_window());
-            precipitation_peaks.push(Self::new_precipitation_window());
-            snowpack_mm.push(Self::new_snowpack_cache());
-            permafrost_active_cm.push(Self::new_permafrost_cache());
+            temperature_maxima.push(Self::new_temperature_window(DEFAULT_EXTREME_WINDOW));
+            precipitation_peaks.push(Self::new_precipitation_window(DEFAULT_EXTREME_WINDOW));
         }
         Self {
             temperature_baseline_tenths,
             last_albedo_milli,
             last_insolation_tenths,
+            last_tide_envelope_tenths: vec![0; regions.len()],
             temperature_maxima,
             precipitation_peaks,
-            snowpack_mm,
-            permafrost_active_cm,
+            snowpack_mm: vec![0; regions.len()],
+            permafrost_active_cm: vec![0; regions.len()],
+            monsoon_active: vec![false; regions.len()],
+            volcanic_episode: vec![0; regions.len()],
             sea_level_equivalent_mm: 0,
+            applied_forcing_tenths: 0,
+            extreme_window: DEFAULT_EXTREME_WINDOW,
+            lunar_period_ticks: DEFAULT_LUNAR_PERIOD_TICKS,
+            storm_tracks: Vec::new(),
+        }
+    }
+
+    /// Tidal forcing for `tick` in `-1.0..=1.0`: a cosine over `lunar_period_ticks`,
+    /// sampled mid-tick so no tick sits exactly on a spring/neap crossing for periods
+    /// divisible by four. Non-negative values are spring tides.
+    pub fn lunar_wave(&self, tick: u64) -> f64 {
+        let period = self.lunar_period_ticks.max(MIN_LUNAR_PERIOD_TICKS);
+        let phase = ((tick % u64::from(period)) as f64 + 0.5) / f64::from(period);
+        detmath::sin(std::f64::consts::TAU * phase + std::f64::consts::FRAC_PI_2)
+    }
+
+    /// Resize every rolling extreme window to `len` samples.
+    ///
+    /// Shrinking drops the oldest samples; growing repeats each window's oldest sample so
+    /// the padding introduces no spread of its own.
+    pub fn set_extreme_window(&mut self, len: usize) -> Result<()> {
+        ensure!(
+            len >= MIN_EXTREME_WINDOW,
+            "extreme window {} is shorter than {} samples",
+            len,
+            MIN_EXTREME_WINDOW
+        );
+        self.extreme_window = len;
+        for window in &mut self.temperature_maxima {
+            resize_window(window, len);
+        }
+        for window in &mut self.precipitation_peaks {
+            resize_window(window, len);
         }
+        Ok(())
     }
 
     pub fn ensure_region_capacity(&mut self, region_count: usize) {
@@ -135,25 +966,64 @@ impl ClimateState {
         if self.last_insolation_tenths.len() < region_count {
             self.last_insolation_tenths.resize(region_count, 0);
         }
+        if self.last_tide_envelope_tenths.len() < region_count {
+            self.last_tide_envelope_tenths.resize(region_count, 0);
+        }
         if self.temperature_maxima.len() < region_count {
             let missing = region_count - self.temperature_maxima.len();
+            let len = self.extreme_window;
             self.temperature_maxima
-                .extend((0..missing).map(|_| Self::new_temperature_window()));
+                .extend((0..missing).map(|_| Self::new_temperature_window(len)));
         }
         if self.precipitation_peaks.len() < region_count {
             let missing = region_count - self.precipitation_peaks.len();
+            let len = self.extreme_window;
             self.precipitation_peaks
-                .extend((0..missing).map(|_| Self::new_precipitation_window()));
+                .extend((0..missing).map(|_| Self::new_precipitation_window(len)));
         }
         if self.snowpack_mm.len() < region_count {
-            let missing = region_count - self.snowpack_mm.len();
-            self.snowpack_mm
-                .extend((0..missing).map(|_| Self::new_snowpack_cache()));
+            self.snowpack_mm.resize(region_count, 0);
         }
         if self.permafrost_active_cm.len() < region_count {
-            let missing = region_count - self.permafrost_active_cm.len();
-            self.permafrost_active_cm
-                .extend((0..missing).map(|_| Self::new_permafrost_cache()));
+            self.permafrost_active_cm.resize(region_count, 0);
+        }
+        if self.monsoon_active.len() < region_count {
+            self.monsoon_active.resize(region_count, false);
+        }
+        if self.volcanic_episode.len() < region_count {
+            self.volcanic_episode.resize(region_count, 0);
+        }
+    }
+
+    /// Climate slots for region `index`, or `None` when any per-region vector is too short.
+    pub fn region_mut(&mut self, index: usize) -> Option<RegionClimate<'_>> {
+        Some(RegionClimate {
+            temperature_baseline_tenths: self.temperature_baseline_tenths.get_mut(index)?,
+            last_albedo_milli: self.last_albedo_milli.get_mut(index)?,
+            last_insolation_tenths: self.last_insolation_tenths.get_mut(index)?,
+            last_tide_envelope_tenths: self.last_tide_envelope_tenths.get_mut(index)?,
+            temperature_maxima: self.temperature_maxima.get_mut(index)?,
+            precipitation_peaks: self.precipitation_peaks.get_mut(index)?,
+            snowpack_mm: self.snowpack_mm.get_mut(index)?,
+            permafrost_active_cm: self.permafrost_active_cm.get_mut(index)?,
+            monsoon_active: self.monsoon_active.get_mut(index)?,
+            volcanic_episode: self.volcanic_episode.get_mut(index)?,
+        })
+    }
+
+    /// Every region's climate slots in index order; stops at the shortest vector.
+    pub fn iter_regions_mut(&mut self) -> impl Iterator<Item = RegionClimate<'_>> {
+        RegionClimateIterMut {
+            temperature_baseline_tenths: self.temperature_baseline_tenths.iter_mut(),
+            last_albedo_milli: self.last_albedo_milli.iter_mut(),
+            last_insolation_tenths: self.last_insolation_tenths.iter_mut(),
+            last_tide_envelope_tenths: self.last_tide_envelope_tenths.iter_mut(),
+            temperature_maxima: self.temperature_maxima.iter_mut(),
+            precipitation_peaks: self.precipitation_peaks.iter_mut(),
+            snowpack_mm: self.snowpack_mm.iter_mut(),
+            permafrost_active_cm: self.permafrost_active_cm.iter_mut(),
+            monsoon_active: self.monsoon_active.iter_mut(),
+            volcanic_episode: self.volcanic_episode.iter_mut(),
         }
     }
 
@@ -168,26 +1038,309 @@ impl ClimateState {
         self.sea_level_equivalent_mm = self.sea_level_equivalent_mm.saturating_add(delta_mm);
     }
 
-    fn new_temperature_window() -> VecDeque<i16> {
-        VecDeque::from(vec![0; EXTREME_WINDOW])
+    /// Zero the accumulated sea level, e.g. to rebaseline after restoring a snapshot.
+    pub fn reset_sea_level(&mut self) {
+        self.sea_level_equivalent_mm = 0;
     }
 
-    fn new_precipitation_window() -> VecDeque<u16> {
-        VecDeque::from(vec![0; EXTREME_WINDOW])
+    fn new_temperature_window(len: usize) -> VecDeque<i16> {
+        VecDeque::from(vec![0; len])
     }
 
-    fn new_snowpack_cache() -> i32 {
-        0
-    }
-
-    fn new_permafrost_cache() -> i32 {
-        0
+    fn new_precipitation_window(len: usize) -> VecDeque<u16> {
+        VecDeque::from(vec![0; len])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ClimateState, Region, EXTREME_WINDOW};
+    use super::{
+        latitude_equal_area, latitude_from_row, lowest_neighbor, pressure_altitude_km,
+        pressure_cooling_c, pressure_hpa, ClimateState, Hazards, Projection, Region, RegionField,
+        Tuning, World, WrapMode, DEFAULT_EXTREME_WINDOW,
+    };
+
+    #[test]
+    fn pressure_falls_monotonically_with_elevation_from_sea_level() {
+        assert!((pressure_hpa(0) - 1_013.25).abs() < 0.01);
+        assert_eq!(pressure_hpa(-800), pressure_hpa(0));
+        assert!((Region::sample(0, 0, 0).pressure_hpa() - 1_013.0).abs() < 1.0);
+
+        let mut previous = pressure_hpa(0);
+        for elevation_m in (100..=4_000).step_by(100) {
+            let pressure = pressure_hpa(elevation_m);
+            assert!(pressure < previous, "{} m: {} hPa", elevation_m, pressure);
+            previous = pressure;
+        }
+        assert!((pressure_hpa(3_000) - 701.1).abs() < 1.0);
+        // 6.5 °C per kilometre, as the lapse rate the atmosphere used before.
+        assert!((pressure_cooling_c(pressure_hpa(2_000)) - 13.0).abs() < 1e-6);
+        assert!((pressure_altitude_km(pressure_hpa(2_000)) - 2.0).abs() < 1e-9);
+        assert_eq!(pressure_altitude_km(pressure_hpa(-800)), 0.0);
+    }
+
+    #[test]
+    fn tuning_rejects_non_finite_rates() {
+        assert!(Tuning::default().validate().is_ok());
+        for tuning in [
+            Tuning {
+                ocean_inertia: f64::NAN,
+                ..Tuning::default()
+            },
+            Tuning {
+                ocean_inertia: f64::INFINITY,
+                ..Tuning::default()
+            },
+            Tuning {
+                evapotranspiration: f64::NAN,
+                ..Tuning::default()
+            },
+            Tuning {
+                cold_air_pooling: f64::INFINITY,
+                ..Tuning::default()
+            },
+            Tuning {
+                volcanic_episode_rate_multiplier: f64::NAN,
+                ..Tuning::default()
+            },
+        ] {
+            assert!(tuning.validate().is_err(), "{:?}", tuning);
+        }
+    }
+
+    #[test]
+    fn content_equality_tracks_simulation_state() {
+        let mut seed = crate::testing::test_seed("content", 3, 2);
+        seed.noise.seed = 4;
+        let mut world = crate::io::seed::build_world(&seed, None);
+        let original = world.clone();
+        assert_eq!(world, original);
+        assert_eq!(world.content_hash(), original.content_hash());
+        // Pinned so a change in hashing or world generation shows up across runs.
+        assert_eq!(world.content_hash(), 0xd766_080d_afa1_bc6f);
+
+        let mut unprovenanced = original.clone();
+        unprovenanced.seed_hash = None;
+        assert_eq!(unprovenanced, original);
+
+        let world_seed = world.seed;
+        crate::tick_once(&mut world, world_seed, 1).expect("tick succeeds");
+        assert_ne!(world, original);
+        assert_ne!(world.content_hash(), original.content_hash());
+
+        let mut windowed = original.clone();
+        windowed.climate.snowpack_mm[0] += 1;
+        assert_ne!(windowed, original);
+    }
+
+    #[test]
+    fn field_values_follow_region_order() {
+        let mut world = World::from_fields(
+            5,
+            3,
+            2,
+            &[10, 20, 30, 40, 50, 60],
+            &[0; 6],
+            latitude_from_row,
+        )
+        .expect("fields match grid");
+        for (index, region) in world.regions.iter_mut().enumerate() {
+            region.temperature_tenths_c = 25 - index as i16 * 15;
+        }
+        world.regions[2].ice_mass_kilotons = u32::MAX;
+
+        let temps = world.field_values(RegionField::Temperature);
+        let expected: Vec<i32> = world
+            .regions
+            .iter()
+            .map(|region| i32::from(region.temperature_tenths_c))
+            .collect();
+        assert_eq!(temps, expected);
+        assert_eq!(temps, vec![25, 10, -5, -20, -35, -50]);
+        assert_eq!(
+            world.field_values(RegionField::Elevation),
+            vec![10, 20, 30, 40, 50, 60]
+        );
+        assert_eq!(world.field_values(RegionField::IceMass)[2], i32::MAX);
+        for field in RegionField::ALL {
+            assert_eq!(world.field_values(field).len(), 6);
+        }
+    }
+
+    #[test]
+    fn sample_regions_are_valid_and_setters_touch_one_field() {
+        let base = Region::sample(3, 1, 2);
+        assert_eq!((base.id, base.x, base.y), (3, 1, 2));
+        let world = World::new(5, 1, 1, vec![Region::sample(0, 0, 0)]);
+        assert!(world.validate_invariants().is_ok());
+
+        let changed: Vec<(&str, Region)> = vec![
+            ("elevation_m", base.clone().with_elevation(2_400)),
+            ("latitude_deg", base.clone().with_latitude(-35.0)),
+            ("biome", base.clone().with_biome(4)),
+            ("water", base.clone().with_water(9_000)),
+            ("soil", base.clone().with_soil(100)),
+            ("temperature_tenths_c", base.clone().with_temperature(-120)),
+            ("precipitation_mm", base.clone().with_precipitation(800)),
+            ("albedo_milli", base.clone().with_albedo(650)),
+            (
+                "freshwater_flux_tenths_mm",
+                base.clone().with_freshwater_flux(25),
+            ),
+            ("ice_mass_kilotons", base.clone().with_ice_mass(1_000)),
+            (
+                "hazards",
+                base.clone().with_hazards(Hazards {
+                    drought: 10,
+                    flood: 20,
+                }),
+            ),
+        ];
+        let base_fields = serde_json::to_value(&base).unwrap();
+        for (field, region) in changed {
+            let fields = serde_json::to_value(&region).unwrap();
+            for (key, value) in fields.as_object().unwrap() {
+                assert_eq!(
+                    value != &base_fields[key],
+                    key == field,
+                    "setter for {} changed {}",
+                    field,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_invariants_rejects_out_of_range_latitude() {
+        let mut world = World::from_fields(3, 2, 1, &[0, 0], &[0, 0], latitude_from_row)
+            .expect("fields match grid");
+        assert!(world.validate_invariants().is_ok());
+
+        world.regions[1].latitude_deg = 200.0;
+        let err = world
+            .validate_invariants()
+            .expect_err("latitude 200 is invalid");
+        assert!(err.to_string().contains("region 1"), "{}", err);
+        world.regions[1].latitude_deg = f64::NAN;
+        assert!(world.validate_invariants().is_err());
+
+        assert!(World::from_fields(3, 2, 1, &[0, 0], &[0, 0], |_, _| 200.0).is_err());
+    }
+
+    #[test]
+    fn from_fields_builds_known_grid() {
+        let world =
+            World::from_fields(7, 2, 2, &[10, 20, 30, 40], &[1, 2, 3, 4], latitude_from_row)
+                .expect("fields match grid");
+
+        assert_eq!(world.regions.len(), 4);
+        for (index, region) in world.regions.iter().enumerate() {
+            assert_eq!(region.index(), index);
+            assert_eq!(region.x, index as u32 % 2);
+            assert_eq!(region.y, index as u32 / 2);
+            assert_eq!(region.elevation_m, 10 * (index as i32 + 1));
+            assert_eq!(region.biome, index as u8 + 1);
+        }
+        assert_eq!(world.regions[0].latitude_deg, 45.0);
+        assert_eq!(world.regions[3].latitude_deg, -45.0);
+        assert_eq!(world.climate.temperature_baseline_tenths.len(), 4);
+        assert_eq!(world.climate.snowpack_mm.len(), 4);
+        assert_eq!(world.climate.temperature_maxima.len(), 4);
+
+        assert!(World::from_fields(7, 2, 2, &[0; 3], &[0; 4], latitude_from_row).is_err());
+    }
+
+    #[test]
+    fn zonal_mean_averages_temperature_per_band() {
+        let samples = [
+            (5.0, 250),
+            (-12.0, 270),
+            (40.0, 80),
+            (-50.0, 40),
+            (75.0, -200),
+        ];
+        let regions = samples
+            .iter()
+            .enumerate()
+            .map(|(index, &(latitude_deg, temperature_tenths_c))| Region {
+                id: index as u32,
+                x: index as u32,
+                y: 0,
+                elevation_m: 0,
+                latitude_deg,
+                biome: 0,
+                water: 0,
+                soil: 0,
+                temperature_tenths_c,
+                precipitation_mm: 0,
+                albedo_milli: 0,
+                freshwater_flux_tenths_mm: 0,
+                ice_mass_kilotons: 0,
+                hazards: crate::world::Hazards::default(),
+            })
+            .collect();
+        let world = World::new(1, samples.len() as u32, 1, regions);
+
+        let tropics: Vec<usize> = world
+            .regions_in_belt(0.0, 30.0)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(tropics, vec![0, 1]);
+
+        let means = world.zonal_mean(&[(0.0, 30.0), (30.0, 60.0), (80.0, 90.0)], |region| {
+            f64::from(region.temperature_tenths_c) / 10.0
+        });
+        assert_eq!(means, vec![26.0, 6.0, 0.0]);
+    }
+
+    #[test]
+    fn lowest_neighbor_breaks_ties_in_offset_order() {
+        // 3x3 grid around the centre (1, 1): W=index 3, E=5, N=1, S=7.
+        let grid = |elevation: [i32; 9]| {
+            World::from_fields(5, 3, 3, &elevation, &[0; 9], |_, _| 0.0).expect("grid builds")
+        };
+        let west_north_tie = grid([900, 100, 900, 100, 500, 300, 900, 300, 900]);
+        let east_south_tie = grid([900, 400, 900, 400, 500, 200, 900, 200, 900]);
+        let all_equal = grid([0, 50, 0, 50, 50, 50, 0, 50, 0]);
+        for _ in 0..3 {
+            assert_eq!(lowest_neighbor(&west_north_tie, 1, 1), Some(3));
+            assert_eq!(lowest_neighbor(&east_south_tie, 1, 1), Some(5));
+            assert_eq!(lowest_neighbor(&all_equal, 1, 1), Some(3));
+        }
+        // Corners only see in-grid neighbours: (0, 0) has E=1 and S=3.
+        assert_eq!(
+            all_equal.neighbor_indices(0, 0).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(lowest_neighbor(&all_equal, 0, 0), Some(1));
+    }
+
+    #[test]
+    fn corner_neighbours_follow_the_wrap_mode() {
+        let mut world =
+            World::from_fields(5, 3, 3, &[0; 9], &[0; 9], |_, _| 0.0).expect("grid builds");
+        assert_eq!(world.neighbors4(0).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(world.neighbors4(8).collect::<Vec<_>>(), vec![7, 5]);
+        assert_eq!(world.neighbors4(9).count(), 0);
+
+        world.wrap = WrapMode::Cylinder;
+        // West of (0, 0) wraps to (2, 0); the top row has no northern neighbour.
+        assert_eq!(world.neighbors4(0).collect::<Vec<_>>(), vec![2, 1, 3]);
+        assert_eq!(world.neighbors4(8).collect::<Vec<_>>(), vec![7, 6, 5]);
+        assert_eq!(
+            world.neighbor_indices(0, 0).collect::<Vec<_>>(),
+            vec![2, 1, 3]
+        );
+
+        let narrow =
+            World::from_fields(5, 2, 1, &[0; 2], &[0; 2], |_, _| 0.0).expect("grid builds");
+        let narrow = World {
+            wrap: WrapMode::Cylinder,
+            ..narrow
+        };
+        assert_eq!(narrow.neighbors4(0).collect::<Vec<_>>(), vec![1]);
+    }
 
     #[test]
     fn sea_level_accumulator_saturates_and_tracks_delta() {
@@ -244,12 +1397,12 @@ mod tests {
         let mut climate = ClimateState::from_regions(&regions);
 
         for window in &climate.temperature_maxima {
-            assert_eq!(window.len(), EXTREME_WINDOW);
+            assert_eq!(window.len(), DEFAULT_EXTREME_WINDOW);
             assert!(window.iter().all(|value| *value == 0));
         }
 
         for window in &climate.precipitation_peaks {
-            assert_eq!(window.len(), EXTREME_WINDOW);
+            assert_eq!(window.len(), DEFAULT_EXTREME_WINDOW);
             assert!(window.iter().all(|value| *value == 0));
         }
 
@@ -292,4 +1445,154 @@ mod tests {
             .all(|v| *v == 0));
         assert_eq!(*climate.snowpack_mm.last().unwrap(), 0);
     }
+
+    #[test]
+    fn extreme_window_resizes_existing_windows() {
+        let regions = vec![Region::sample(0, 0, 0), Region::sample(1, 1, 0)];
+        let mut climate = ClimateState::from_regions(&regions);
+        climate.precipitation_peaks[0] = (1..=6).collect();
+        assert!(climate.set_extreme_window(1).is_err());
+        assert_eq!(climate.extreme_window, DEFAULT_EXTREME_WINDOW);
+
+        climate.set_extreme_window(3).expect("3 samples is valid");
+        assert_eq!(climate.precipitation_peaks[0], [4, 5, 6]);
+        climate.set_extreme_window(5).expect("5 samples is valid");
+        assert_eq!(climate.precipitation_peaks[0], [4, 4, 4, 5, 6]);
+        assert!(climate.temperature_maxima.iter().all(|w| w.len() == 5));
+
+        climate.ensure_region_capacity(3);
+        assert_eq!(climate.precipitation_peaks[2].len(), 5);
+    }
+
+    #[test]
+    fn diff_against_reports_only_changed_fields() {
+        let world =
+            World::from_fields(3, 2, 2, &[10, 20, 30, 40], &[1, 2, 3, 4], latitude_from_row)
+                .expect("fields match grid");
+        assert!(world.diff_against(&world.clone()).is_empty());
+
+        let mut warmer = world.clone();
+        warmer.regions[2].temperature_tenths_c = 125;
+        let mut diff = world.diff_against(&warmer);
+        assert_eq!(diff.temperature.len(), 1);
+        assert_eq!(
+            (diff.temperature[0].region, diff.temperature[0].value),
+            (2, 125)
+        );
+        diff.temperature.clear();
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn frame_diff_edits_change_exactly_the_named_regions() {
+        let world =
+            World::from_fields(3, 2, 2, &[10, 20, 30, 40], &[1, 2, 3, 4], latitude_from_row)
+                .expect("fields match grid");
+        let edit: crate::io::frame::FrameDiff =
+            serde_json::from_str(r#"{"biome": {"r:1": 4}, "water": {"r:3": 250}}"#)
+                .expect("edit parses");
+        let diff = edit.to_diff().expect("edit converts");
+        assert_eq!((diff.biome.len(), diff.water.len()), (1, 1));
+
+        let mut edited = world.clone();
+        edited.apply_frame_diff(&edit).expect("edit applies");
+        assert_eq!(edited.tick, world.tick);
+        let changes = world.diff_against(&edited);
+        let biome: Vec<(u32, i32)> = changes.biome.iter().map(|c| (c.region, c.biome)).collect();
+        let water: Vec<(u32, i32)> = changes.water.iter().map(|d| (d.region, d.delta)).collect();
+        assert_eq!(biome, vec![(1, 4)]);
+        assert_eq!(water, vec![(3, 250)]);
+        let mut rest = changes;
+        rest.biome.clear();
+        rest.water.clear();
+        assert!(rest.is_empty());
+
+        let mut untouched = world.clone();
+        for bad in [r#"{"water": {"r:4": 1}}"#, r#"{"biome": {"region4": 1}}"#] {
+            let edit: crate::io::frame::FrameDiff = serde_json::from_str(bad).unwrap();
+            assert!(untouched.apply_frame_diff(&edit).is_err(), "{}", bad);
+        }
+        assert!(world.diff_against(&untouched).is_empty());
+    }
+
+    #[test]
+    fn equal_area_rows_crowd_the_equator_and_reach_the_poles() {
+        let height = 12;
+        let equal_area: Vec<f64> = (0..height)
+            .map(|y| Projection::EqualArea.latitude(y, height))
+            .collect();
+        let linear: Vec<f64> = (0..height)
+            .map(|y| Projection::Equirectangular.latitude(y, height))
+            .collect();
+        assert_eq!(linear[0], latitude_from_row(0, height));
+
+        let spacing = |lats: &[f64], y: usize| lats[y] - lats[y + 1];
+        let middle = height as usize / 2 - 1;
+        assert!(spacing(&equal_area, middle) < spacing(&linear, middle));
+        assert!(spacing(&equal_area, 0) > spacing(&linear, 0));
+        // The polar band is the widest in latitude, but its centre still nears the pole as
+        // the grid gains rows.
+        assert!(equal_area[0] > 60.0 && equal_area[0] < linear[0]);
+        assert!(Projection::EqualArea.latitude(0, 180) > 80.0);
+        assert!(Projection::EqualArea.latitude(179, 180) < -80.0);
+        assert!((equal_area[0] + equal_area[height as usize - 1]).abs() < 1e-9);
+
+        // Each band spans the same share of the sphere: sin(lat) steps evenly.
+        let sines: Vec<f64> = (0..=height)
+            .map(|edge| 1.0 - 2.0 * f64::from(edge) / f64::from(height))
+            .collect();
+        for (y, &lat) in equal_area.iter().enumerate() {
+            assert!((lat.to_radians().sin() - (sines[y] + sines[y + 1]) / 2.0).abs() < 1e-9);
+        }
+        assert_eq!(latitude_equal_area(0, 1), 0.0);
+    }
+
+    #[test]
+    fn paired_climate_iterator_walks_regions_in_lockstep() {
+        let mut world = World::new(7, 3, 1, (0..3).map(|x| Region::sample(x, x, 0)).collect());
+        world.climate.snowpack_mm = vec![10, 20, 30];
+        world.climate.temperature_baseline_tenths = vec![-1, -2, -3];
+        // A snapshot from before the region grew: short vectors are filled on the way in.
+        world.climate.permafrost_active_cm.truncate(1);
+
+        let mut visited = 0;
+        for (index, (region, climate)) in world.iter_regions_mut_with_climate().enumerate() {
+            assert_eq!(region.index(), index);
+            assert_eq!(*climate.snowpack_mm, 10 * (index as i32 + 1));
+            assert_eq!(*climate.temperature_baseline_tenths, -(index as i16 + 1));
+            *climate.permafrost_active_cm = region.id as i32 * 100;
+            region.water = 1_000 + region.id as u16;
+            visited += 1;
+        }
+
+        assert_eq!(visited, 3);
+        assert_eq!(world.climate.permafrost_active_cm, vec![0, 100, 200]);
+        let waters: Vec<u16> = world.regions.iter().map(|region| region.water).collect();
+        assert_eq!(waters, vec![1_000, 1_001, 1_002]);
+        let slot = world.climate.region_mut(2).expect("region 2 climate");
+        assert_eq!(*slot.snowpack_mm, 30);
+        assert!(world.climate.region_mut(3).is_none());
+    }
+
+    #[test]
+    fn snowpack_and_permafrost_track_the_region_count() {
+        let regions: Vec<Region> = (0..4).map(|x| Region::sample(x, x, 0)).collect();
+        let mut climate = ClimateState::from_regions(&regions);
+        assert_eq!(climate.snowpack_mm, vec![0; 4]);
+        assert_eq!(climate.permafrost_active_cm, vec![0; 4]);
+
+        climate.snowpack_mm[3] = 450;
+        climate.permafrost_active_cm[3] = 120;
+        climate.ensure_region_capacity(6);
+        assert_eq!(climate.snowpack_mm, vec![0, 0, 0, 450, 0, 0]);
+        assert_eq!(climate.permafrost_active_cm, vec![0, 0, 0, 120, 0, 0]);
+
+        // Plain world JSON drops them; the next capacity check refills with zeros.
+        let json = serde_json::to_string(&climate).expect("climate serializes");
+        let mut restored: ClimateState = serde_json::from_str(&json).expect("climate parses");
+        assert!(restored.snowpack_mm.is_empty());
+        restored.ensure_region_capacity(6);
+        assert_eq!(restored.snowpack_mm, vec![0; 6]);
+        assert_eq!(restored.permafrost_active_cm, vec![0; 6]);
+    }
 }
@@ -96,7 +96,7 @@ fn astronomy_diff_is_repeatable_and_integral() {
     let chron_second = run_second.chronicle;
 
     let diff_json_first = serde_json::to_value(&diff_first).expect("serialize diff");
-    let diff_json_second = serde_json::to_value(&diff_second).expect("serialize diff");
+    let diff_json_second = serde_json::to_value(diff_second).expect("serialize diff");
 
     assert_eq!(
         diff_json_first, diff_json_second,
@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sim_core::testing::{first_divergence, run_golden};
+
+const GOLDEN_TICKS: u64 = 16;
+
+fn testdata_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../testdata")
+        .join(relative)
+}
+
+fn assert_matches_golden(seed_file: &str, golden_file: &str) {
+    let seed_path = testdata_path(&format!("seeds/{}", seed_file));
+    let golden_path = testdata_path(&format!("golden/{}", golden_file));
+    let seed_json = fs::read_to_string(&seed_path)
+        .unwrap_or_else(|err| panic!("failed to read {:?}: {}", seed_path, err));
+    let reference = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|err| panic!("failed to read {:?}: {}", golden_path, err));
+    let expected: Vec<&str> = reference.lines().collect();
+
+    let actual = run_golden(&seed_json, GOLDEN_TICKS).expect("golden run succeeds");
+
+    if let Some(divergence) = first_divergence(&expected, &actual) {
+        panic!(
+            "golden frames diverged from {:?}: {}\nregenerate intentionally with \
+             `cargo run -p simstep -- --seed-file ./testdata/seeds/{} --ticks {} --out \
+             ./testdata/golden/{}`",
+            golden_path, divergence, seed_file, GOLDEN_TICKS, golden_file
+        );
+    }
+}
+
+#[test]
+fn golden_harness_matches_reference_frames() {
+    assert_matches_golden("seed_golden_harness.json", "seed_golden_harness.ndjson");
+}
+
+/// The harness grid has no mid-latitude rows; this one carries two storm tracks.
+#[test]
+fn storm_golden_matches_reference_frames() {
+    assert_matches_golden("seed_golden_storms.json", "seed_golden_storms.ndjson");
+}
+
+#[test]
+fn golden_harness_is_repeatable() {
+    let seed_path = testdata_path("seeds/seed_golden_harness.json");
+    let seed_json = fs::read_to_string(&seed_path)
+        .unwrap_or_else(|err| panic!("failed to read {:?}: {}", seed_path, err));
+
+    let first = run_golden(&seed_json, GOLDEN_TICKS).expect("first run succeeds");
+    let second = run_golden(&seed_json, GOLDEN_TICKS).expect("second run succeeds");
+    assert_eq!(first_divergence(&first, &second), None);
+}
+
+#[test]
+fn rng_kind_selection_keeps_default_golden_and_is_deterministic() {
+    let seed_path = testdata_path("seeds/seed_golden_harness.json");
+    let golden_path = testdata_path("golden/seed_golden_harness.ndjson");
+    let seed_json = fs::read_to_string(&seed_path)
+        .unwrap_or_else(|err| panic!("failed to read {:?}: {}", seed_path, err));
+    let reference = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|err| panic!("failed to read {:?}: {}", golden_path, err));
+    let expected: Vec<&str> = reference.lines().collect();
+
+    let with_rng = |kind: &str| {
+        let mut seed: serde_json::Value = serde_json::from_str(&seed_json).expect("seed parses");
+        seed["rng"] = serde_json::Value::from(kind);
+        seed.to_string()
+    };
+
+    let explicit_default = run_golden(&with_rng("splitmix64"), GOLDEN_TICKS).expect("run succeeds");
+    assert_eq!(first_divergence(&expected, &explicit_default), None);
+
+    let pcg_seed = with_rng("pcg_rxs_m_xs_64");
+    let pcg = run_golden(&pcg_seed, GOLDEN_TICKS).expect("pcg run succeeds");
+    let divergence = first_divergence(&expected, &pcg).expect("pcg output differs from golden");
+    assert_eq!(divergence.line, 0);
+    let repeat = run_golden(&pcg_seed, GOLDEN_TICKS).expect("pcg rerun succeeds");
+    assert_eq!(first_divergence(&pcg, &repeat), None);
+}
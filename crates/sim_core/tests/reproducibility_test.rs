@@ -0,0 +1,70 @@
+//! Pinned tenths-level outputs of the floating-point hot paths. The values come from
+//! `sim_core::detmath`, which uses only correctly rounded IEEE 754 operations, so they must
+//! match on every target; a failure here on a new platform means a libm call slipped back
+//! into a kernel.
+
+use sim_core::kernels::astronomy::{self, STAGE};
+use sim_core::rng::Stream;
+use sim_core::world::{pressure_hpa, Region, World};
+
+const LATITUDES: [f64; 7] = [0.0, 15.0, 30.0, 45.0, 60.0, 75.0, 90.0];
+const ELEVATIONS_M: [i32; 7] = [0, 500, 1_000, 1_500, 2_000, 2_500, 3_000];
+
+fn meridian_world(seed: u64) -> World {
+    let regions = LATITUDES
+        .iter()
+        .zip(ELEVATIONS_M)
+        .enumerate()
+        .map(|(y, (&latitude, elevation))| {
+            Region::sample(y as u32, 0, y as u32)
+                .with_latitude(latitude)
+                .with_elevation(elevation)
+        })
+        .collect();
+    World::new(seed, 1, LATITUDES.len() as u32, regions)
+}
+
+#[test]
+fn astronomy_tenths_match_the_pinned_table() {
+    // Insolation and tide envelope per latitude for ticks 1..=3 of seed 42.
+    let expected: [([i32; 7], [i32; 7]); 3] = [
+        (
+            [16_200, 14_774, 13_289, 11_727, 10_055, 8_197, 5_691],
+            [26, 24, 22, 19, 17, 14, 10],
+        ),
+        (
+            [13_883, 12_661, 11_389, 10_050, 8_618, 7_026, 4_878],
+            [26, 24, 22, 19, 17, 14, 10],
+        ),
+        (
+            [11_495, 10_483, 9_430, 8_322, 7_136, 5_818, 4_040],
+            [38, 34, 31, 28, 24, 20, 14],
+        ),
+    ];
+
+    let mut world = meridian_world(42);
+    for (tick, (insolation, tide)) in (0u64..).zip(expected) {
+        world.tick = tick;
+        let mut rng = Stream::from(world.seed, STAGE, tick + 1);
+        let diff = astronomy::update(&world, &mut rng).expect("astronomy").diff;
+        let actual_insolation: Vec<i32> = diff.insolation.iter().map(|v| v.value).collect();
+        let actual_tide: Vec<i32> = diff.tide_envelope.iter().map(|v| v.value).collect();
+        assert_eq!(
+            actual_insolation,
+            insolation,
+            "insolation at tick {}",
+            tick + 1
+        );
+        assert_eq!(actual_tide, tide, "tide envelope at tick {}", tick + 1);
+    }
+}
+
+#[test]
+fn surface_pressure_tenths_match_the_pinned_table() {
+    let expected = [10_133, 9_546, 8_987, 8_456, 7_950, 7_468, 7_011];
+    let actual: Vec<i32> = ELEVATIONS_M
+        .iter()
+        .map(|&elevation| (pressure_hpa(elevation) * 10.0).round() as i32)
+        .collect();
+    assert_eq!(actual, expected);
+}
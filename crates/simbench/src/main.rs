@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use serde_json::{json, Value};
+use sim_core::io::seed::{build_world, Humidity, Noise, NoiseMode, Seed, SoilFertility};
+use sim_core::rng::RngKind;
+use sim_core::tick_once_timed;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "simbench",
+    about = "Measure tick throughput for a procedural world without I/O"
+)]
+struct Args {
+    /// Procedural world width in regions.
+    #[arg(long, default_value_t = 64)]
+    width: u32,
+
+    /// Procedural world height in regions.
+    #[arg(long, default_value_t = 32)]
+    height: u32,
+
+    /// Number of ticks to execute.
+    #[arg(long, default_value_t = 100)]
+    ticks: u64,
+
+    /// World seed for deterministic generation.
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+// TODO(agents): rationale - mirror simd's procedural defaults so benches track the daemon.
+fn bench_seed(args: &Args) -> Seed {
+    Seed {
+        name: format!("bench-{}x{}", args.width, args.height),
+        width: args.width,
+        height: args.height,
+        noise: Noise {
+            octaves: 3,
+            freq: 0.02,
+            amp: 1.0,
+            seed: args.seed,
+            mode: NoiseMode::default(),
+        },
+        humidity: Humidity {
+            equator: 0.3,
+            poles: -0.2,
+        },
+        soil_fertility: SoilFertility::default(),
+        rng: RngKind::default(),
+        initial_biomes: None,
+        projection: Default::default(),
+        extreme_window: None,
+        lunar_period_ticks: None,
+        tuning: Default::default(),
+        wrap: Default::default(),
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+/// Run the benchmark and summarise it as a JSON object; world build time is excluded.
+fn run_bench(args: &Args) -> Result<Value> {
+    ensure!(args.ticks > 0, "--ticks must be positive");
+    let mut world = build_world(&bench_seed(args), Some(args.seed));
+    ensure!(!world.regions.is_empty(), "world must contain regions");
+    let regions = world.regions.len();
+
+    let mut kernel_time: BTreeMap<&'static str, Duration> = BTreeMap::new();
+    let started = Instant::now();
+    for _ in 0..args.ticks {
+        let next_tick = world.tick + 1;
+        let seed = world.seed;
+        let (_diff, _chronicle, _highlights, timings) =
+            tick_once_timed(&mut world, seed, next_tick)?;
+        for timing in timings {
+            *kernel_time.entry(timing.label).or_default() += timing.elapsed;
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+    let kernels: BTreeMap<&str, f64> = kernel_time
+        .into_iter()
+        .map(|(label, total)| (label, millis(total)))
+        .collect();
+    let ticks_per_sec = args.ticks as f64 / elapsed;
+    Ok(json!({
+        "width": world.width,
+        "height": world.height,
+        "regions": regions,
+        "ticks": args.ticks,
+        "elapsed_ms": elapsed * 1_000.0,
+        "ticks_per_sec": ticks_per_sec,
+        "regions_per_sec": ticks_per_sec * regions as f64,
+        "kernel_ms": kernels,
+    }))
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    println!("{}", serde_json::to_string(&run_bench(&args)?)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_bench, Args};
+    use clap::Parser;
+
+    #[test]
+    fn tiny_world_reports_expected_keys() {
+        let args =
+            Args::try_parse_from(["simbench", "--width", "3", "--height", "2", "--ticks", "2"])
+                .expect("args parse");
+        let report = run_bench(&args).expect("bench runs");
+        for key in [
+            "width",
+            "height",
+            "regions",
+            "ticks",
+            "elapsed_ms",
+            "ticks_per_sec",
+            "regions_per_sec",
+            "kernel_ms",
+        ] {
+            assert!(report.get(key).is_some(), "missing {}", key);
+        }
+        assert_eq!(report["regions"], 6);
+        let kernels = report["kernel_ms"].as_object().expect("kernel map");
+        assert!(kernels.contains_key("kernel:atmosphere"));
+        assert!(kernels.contains_key("kernel:ecology"));
+        assert!(kernels
+            .values()
+            .all(|value| value.as_f64().unwrap_or(-1.0) >= 0.0));
+    }
+}
@@ -0,0 +1,63 @@
+//! Minimal `simd` client: prints the global mean temperature for every streamed tick.
+//!
+//! Run the daemon, then `cargo run -p simd --example stream_client -- ws://127.0.0.1:8787/stream`.
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use sim_core::io::stream::{ControlKind, FrameDecoder, StreamMessage};
+use sim_core::world::World;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const DEFAULT_URL: &str = "ws://127.0.0.1:8787/stream";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let url = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_URL.to_string());
+    let (mut socket, _) = connect_async(url.as_str())
+        .await
+        .with_context(|| format!("failed to connect to {}", url))?;
+
+    let mut decoder = FrameDecoder::new();
+    // Frames carry sparse diffs, so keep the latest temperature seen for each region.
+    let mut temps: Vec<Option<i32>> = Vec::new();
+    while let Some(message) = socket.next().await {
+        let Message::Text(text) = message.context("websocket receive failed")? else {
+            continue;
+        };
+        for message in decoder.push(&text)? {
+            match message {
+                StreamMessage::Control {
+                    kind: ControlKind::Hello,
+                    payload,
+                } => {
+                    let width = payload["world"]["width"].as_u64().unwrap_or(0);
+                    let height = payload["world"]["height"].as_u64().unwrap_or(0);
+                    temps = vec![None; (width * height) as usize];
+                    println!("connected: {}x{} world", width, height);
+                }
+                StreamMessage::Control { kind, .. } => println!("control: {}", kind.as_str()),
+                StreamMessage::Frame(frame) => {
+                    for (key, value) in &frame.diff.temp {
+                        if let Some(slot) =
+                            World::region_index_from_key(key).and_then(|index| temps.get_mut(index))
+                        {
+                            *slot = Some(*value);
+                        }
+                    }
+                    let known: Vec<i32> = temps.iter().flatten().copied().collect();
+                    if known.is_empty() {
+                        println!("t={} mean_temp=n/a", frame.t);
+                    } else {
+                        let mean_tenths =
+                            known.iter().map(|&t| f64::from(t)).sum::<f64>() / known.len() as f64;
+                        println!("t={} mean_temp={:.1}°C", frame.t, mean_tenths / 10.0);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
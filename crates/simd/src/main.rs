@@ -1,29 +1,43 @@
-use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::State;
+use axum::extract::{Path as UrlPath, Query, State};
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::routing::get;
+use axum::Json;
 use axum::Router;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
 use sim_core::cause::Entry;
-use sim_core::io::frame::make_frame;
-use sim_core::io::seed::{build_world, Humidity, Noise, Seed};
-use sim_core::tick_once;
+use sim_core::io::frame::{make_frame_with_options, FrameOptions};
+use sim_core::io::seed::{
+    build_world, format_seed_hash, Humidity, Noise, NoiseMode, Seed, SoilFertility,
+};
+use sim_core::rng::RngKind;
+use sim_core::world::World;
+use sim_core::{tick_once_with_params, SimParams};
 use tokio::net::TcpListener;
 use tokio::sync::broadcast;
 use tokio::sync::Mutex;
 use tokio::time::sleep;
-use tracing::{error, info};
+use tracing::{error, info, warn, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
 
 #[derive(Parser, Debug)]
 #[command(name = "simd", about = "Ages of a Borrowed Voice streaming daemon")]
 struct Args {
-    /// JSON seed document describing the initial world configuration.
+    /// JSON seed document describing the initial world configuration; `-` reads stdin.
     #[arg(long = "seed-file", value_name = "PATH")]
     seed_file: Option<PathBuf>,
 
@@ -54,17 +68,287 @@ struct Args {
     /// Port to listen on for WebSocket clients.
     #[arg(long, default_value_t = 8787)]
     port: u16,
+
+    /// Include per-region prevailing wind vectors in streamed frames.
+    #[arg(long = "emit-wind")]
+    emit_wind: bool,
+
+    /// Include surface pressure for regions whose elevation changed in streamed frames.
+    #[arg(long = "emit-pressure")]
+    emit_pressure: bool,
+
+    /// Include a per-tick histogram of cause codes in streamed frames.
+    #[arg(long = "emit-cause-summary")]
+    emit_cause_summary: bool,
+
+    /// Drop frame diff entries that leave a region's stored value unchanged.
+    #[arg(long = "compact-diffs")]
+    compact_diffs: bool,
+
+    /// Drop low-priority diagnostic maps from frames larger than this many bytes.
+    #[arg(long = "max-frame-bytes", value_name = "BYTES")]
+    max_frame_bytes: Option<usize>,
+
+    /// Ticks of per-region state kept for `GET /region/:id`.
+    #[arg(
+        long = "history-window",
+        value_name = "TICKS",
+        default_value_t = 64,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    history_window: u64,
+
+    /// Also write every frame's NDJSON line to this file, flushed each tick.
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Keep only the highest-priority highlights per frame.
+    #[arg(long = "max-highlights", value_name = "COUNT", default_value_t = usize::MAX, hide_default_value = true)]
+    max_highlights: usize,
+
+    /// Validate the seed, world, and bind address, print a summary, and exit.
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+
+    /// Operational log output: human-readable text or one JSON object per line.
+    #[arg(long = "log-format", value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Log errors only.
+    #[arg(short = 'q', long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log debug records; repeat (`-vv`) for trace.
+    #[arg(short = 'v', long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Build the daemon's log subscriber, writing records that pass `filter` to `writer`.
+fn log_subscriber<W>(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(writer);
+    match format {
+        LogFormat::Text => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+    }
+}
+
+/// Level selected by `-q`/`-v`; `info` when neither is given.
+fn log_level(args: &Args) -> LevelFilter {
+    if args.quiet {
+        return LevelFilter::ERROR;
+    }
+    match args.verbose {
+        0 => LevelFilter::INFO,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// `rust_log` directives when set and non-blank, otherwise the `-q`/`-v` level.
+fn log_filter(args: &Args, rust_log: Option<&str>) -> EnvFilter {
+    match rust_log
+        .map(str::trim)
+        .filter(|directives| !directives.is_empty())
+    {
+        Some(directives) => EnvFilter::new(directives),
+        None => EnvFilter::new(log_level(args).to_string()),
+    }
+}
+
+fn log_startup(addr: SocketAddr) {
+    info!(%addr, "starting simd");
+}
+
+/// Advance `world` one tick and serialize the resulting frame; with `compact_diffs`, the
+/// diff is compacted against the pre-tick world first.
+fn next_frame_line(
+    world: &mut World,
+    params: &SimParams,
+    frame_options: FrameOptions,
+    max_frame_bytes: Option<usize>,
+    compact_diffs: bool,
+) -> Result<(String, Vec<Entry>, u64)> {
+    let next_tick = world.tick + 1;
+    let seed = world.seed;
+    let before = compact_diffs.then(|| world.clone());
+    let (mut diff, chronicle, highlights) = tick_once_with_params(world, seed, next_tick, params)?;
+    if let Some(before) = &before {
+        diff.compact(before);
+    }
+    let causes = diff.causes.clone();
+    let mut frame = make_frame_with_options(
+        next_tick,
+        diff,
+        highlights,
+        chronicle,
+        false,
+        world.width,
+        world.height,
+        frame_options,
+    );
+    if let Some(max_bytes) = max_frame_bytes {
+        if !frame.truncate_to(max_bytes) {
+            warn!(
+                t = next_tick,
+                bytes = frame.estimated_bytes(),
+                max_bytes,
+                "frame exceeds cap after truncation"
+            );
+        }
+    }
+    Ok((frame.to_ndjson()?, causes, next_tick))
+}
+
+/// `--record` sink: every streamed frame line, flushed as soon as it is written so the
+/// file survives a crash mid-run.
+struct FrameRecorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl FrameRecorder {
+    fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("failed to create record file {:?}", path))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(file),
+        })
+    }
+
+    fn append(&mut self, line: &str) -> Result<()> {
+        self.writer
+            .write_all(line.as_bytes())
+            .and_then(|()| self.writer.flush())
+            .with_context(|| format!("failed to append frame to {:?}", self.path))
+    }
 }
 
 #[derive(Clone)]
 struct AppState {
     tx: broadcast::Sender<String>,
+    hello: Arc<String>,
+    history: Arc<Mutex<RegionHistory>>,
+}
+
+/// One region's state at the end of tick `t`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct RegionSample {
+    t: u64,
+    temp: i16,
+    precip: u16,
+    biome: u8,
+}
+
+/// Ring buffer of the last `capacity` ticks of per-region state. The buffer grows as ticks
+/// arrive, so a large `--history-window` costs nothing until the run reaches it.
+#[derive(Debug)]
+struct RegionHistory {
+    capacity: usize,
+    region_count: usize,
+    /// Oldest first; each entry holds every region's sample for one tick.
+    ticks: VecDeque<Vec<RegionSample>>,
+}
+
+impl RegionHistory {
+    fn new(capacity: usize, region_count: usize) -> Self {
+        Self {
+            capacity,
+            region_count,
+            ticks: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, world: &World) {
+        if self.ticks.len() == self.capacity {
+            self.ticks.pop_front();
+        }
+        self.ticks.push_back(
+            world
+                .regions
+                .iter()
+                .map(|region| RegionSample {
+                    t: world.tick,
+                    temp: region.temperature_tenths_c,
+                    precip: region.precipitation_mm,
+                    biome: region.biome,
+                })
+                .collect(),
+        );
+    }
+
+    /// Up to `window` most recent samples for region `id`, oldest first; `None` when the
+    /// id is out of range.
+    fn region_window(&self, id: usize, window: usize) -> Option<Vec<RegionSample>> {
+        if id >= self.region_count {
+            return None;
+        }
+        let skip = self.ticks.len().saturating_sub(window);
+        Some(
+            self.ticks
+                .iter()
+                .skip(skip)
+                .filter_map(|samples| samples.get(id).cloned())
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    window: Option<usize>,
+}
+
+async fn region_handler(
+    UrlPath(id): UrlPath<usize>,
+    Query(query): Query<HistoryQuery>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let history = state.history.lock().await;
+    let window = query.window.unwrap_or(history.capacity);
+    match history.region_window(id, window) {
+        Some(samples) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"region": id, "samples": samples})),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no region {}", id)})),
+        ),
+    }
+}
+
+/// Provenance line sent once to each client before any frames.
+fn hello_line(world: &World) -> Result<String> {
+    let hello = serde_json::json!({
+        "type": "hello",
+        "seed_hash": world.seed_hash.map(format_seed_hash),
+        "world": {"width": world.width, "height": world.height},
+    });
+    let mut line = serde_json::to_string(&hello)?;
+    line.push('\n');
+    Ok(line)
 }
 
 fn load_seed(args: &Args) -> Result<Seed> {
     if let Some(path) = &args.seed_file {
-        return Seed::load_from_path(path)
-            .with_context(|| format!("failed to load seed from {:?}", path));
+        return Seed::load(path).with_context(|| format!("failed to load seed from {:?}", path));
     }
 
     let width = args
@@ -88,57 +372,129 @@ fn load_seed(args: &Args) -> Result<Seed> {
             freq: 0.02,
             amp: 1.0,
             seed,
+            mode: NoiseMode::default(),
         },
         humidity: Humidity {
             equator: 0.3,
             poles: -0.2,
         },
+        soil_fertility: SoilFertility::default(),
+        rng: RngKind::default(),
+        initial_biomes: None,
+        projection: Default::default(),
+        extreme_window: None,
+        lunar_period_ticks: None,
+        tuning: Default::default(),
+        wrap: Default::default(),
     })
 }
 
+/// Requested listen address; `--bind` takes IPv4 or IPv6 literals, bracketed or not.
+fn bind_addr(args: &Args) -> Result<SocketAddr> {
+    let ip: IpAddr = args
+        .bind
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .with_context(|| format!("invalid bind address {}:{}", args.bind, args.port))?;
+    Ok(SocketAddr::new(ip, args.port))
+}
+
+/// Bind `addr` and report the address actually bound, which differs for `--port 0`.
+async fn bind_listener(addr: SocketAddr) -> Result<(TcpListener, SocketAddr)> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to bind {}", addr))?;
+    let local_addr = listener
+        .local_addr()
+        .with_context(|| format!("failed to read bound address for {}", addr))?;
+    Ok((listener, local_addr))
+}
+
+/// Run every startup step short of binding the socket and describe the result.
+fn dry_run(args: &Args) -> Result<String> {
+    let seed = load_seed(args)?;
+    let world = build_world(&seed, args.seed.or(args.world_seed));
+    let addr = bind_addr(args)?;
+    Ok(format!(
+        "dry run ok: seed {:?}, {} regions ({}x{}), bind {}",
+        seed.name,
+        world.regions.len(),
+        world.width,
+        world.height,
+        addr
+    ))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .with_target(false)
-        .init();
-
     let args = Args::parse();
+    let rust_log = std::env::var(EnvFilter::DEFAULT_ENV).ok();
+    log_subscriber(
+        args.log_format,
+        log_filter(&args, rust_log.as_deref()),
+        std::io::stdout,
+    )
+    .init();
+    if args.dry_run {
+        println!("{}", dry_run(&args)?);
+        return Ok(());
+    }
 
     let seed = load_seed(&args)?;
+    let addr = bind_addr(&args)?;
     let frame_period = Duration::from_secs_f64(1.0 / f64::from(args.fps));
     let world_seed_override = args.seed.or(args.world_seed);
+    let frame_options = FrameOptions {
+        include_wind: args.emit_wind,
+        include_pressure: args.emit_pressure,
+        include_cause_summary: args.emit_cause_summary,
+        max_highlights: args.max_highlights,
+    };
+    let max_frame_bytes = args.max_frame_bytes;
+    let compact_diffs = args.compact_diffs;
+    let params = SimParams {
+        record_wind: args.emit_wind,
+        ..SimParams::default()
+    };
     let world = build_world(&seed, world_seed_override);
 
     let (tx, _rx) = broadcast::channel::<String>(128);
-    let state = AppState { tx: tx.clone() };
+    let history = Arc::new(Mutex::new(RegionHistory::new(
+        usize::try_from(args.history_window).unwrap_or(usize::MAX),
+        world.regions.len(),
+    )));
+    let state = AppState {
+        tx: tx.clone(),
+        hello: Arc::new(hello_line(&world)?),
+        history: Arc::clone(&history),
+    };
     let world_handle = Arc::new(Mutex::new(world));
+    let mut recorder = args
+        .record
+        .as_deref()
+        .map(FrameRecorder::create)
+        .transpose()?;
 
     // Spawn ticking task.
     let tick_tx = tx.clone();
     let tick_handle = Arc::clone(&world_handle);
+    let tick_history = Arc::clone(&history);
     tokio::spawn(async move {
         loop {
-            let tick_result: Result<(String, Vec<Entry>, u64), anyhow::Error> = {
+            let tick_result = {
                 let mut world = tick_handle.lock().await;
-                let next_tick = world.tick + 1;
-                let seed = world.seed;
-
-                match tick_once(&mut world, seed, next_tick) {
-                    Ok((diff, chronicle, highlights)) => {
-                        let causes = diff.causes.clone();
-                        let width = world.width;
-                        let height = world.height;
-                        let frame = make_frame(
-                            next_tick, diff, highlights, chronicle, false, width, height,
-                        );
-                        match frame.to_ndjson() {
-                            Ok(line) => Ok((line, causes, next_tick)),
-                            Err(err) => Err(err.into()),
-                        }
-                    }
-                    Err(err) => Err(err),
+                let result = next_frame_line(
+                    &mut world,
+                    &params,
+                    frame_options,
+                    max_frame_bytes,
+                    compact_diffs,
+                );
+                if result.is_ok() {
+                    tick_history.lock().await.record(&world);
                 }
+                result
             };
 
             let (line, causes, t) = match tick_result {
@@ -149,6 +505,12 @@ async fn main() -> Result<()> {
                 }
             };
 
+            if let Some(active) = recorder.as_mut() {
+                if let Err(err) = active.append(&line) {
+                    error!(?err, "frame recording failed; continuing without --record");
+                    recorder = None;
+                }
+            }
             if tick_tx.send(line).is_err() {
                 tracing::trace!("no subscribers for frame t={}", t);
             }
@@ -162,16 +524,11 @@ async fn main() -> Result<()> {
 
     let app = Router::new()
         .route("/stream", get(ws_handler))
+        .route("/region/:id", get(region_handler))
         .with_state(state);
 
-    let addr: SocketAddr = format!("{}:{}", args.bind, args.port)
-        .parse()
-        .with_context(|| format!("invalid bind address {}:{}", args.bind, args.port))?;
-
-    info!(%addr, "starting simd");
-    let listener = TcpListener::bind(addr)
-        .await
-        .with_context(|| format!("failed to bind {}", addr))?;
+    let (listener, local_addr) = bind_listener(addr).await?;
+    log_startup(local_addr);
     axum::serve(listener, app.into_make_service())
         .await
         .context("server error")?;
@@ -179,10 +536,16 @@ async fn main() -> Result<()> {
 }
 
 async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-    ws.on_upgrade(|socket| async move { handle_socket(socket, state.tx.subscribe()).await })
+    ws.on_upgrade(|socket| async move {
+        handle_socket(socket, state.hello.as_str(), state.tx.subscribe()).await
+    })
 }
 
-async fn handle_socket(mut socket: WebSocket, mut rx: broadcast::Receiver<String>) {
+async fn handle_socket(mut socket: WebSocket, hello: &str, mut rx: broadcast::Receiver<String>) {
+    if socket.send(Message::Text(hello.to_string())).await.is_err() {
+        error!("websocket client disconnected");
+        return;
+    }
     while let Ok(line) = rx.recv().await {
         if socket.send(Message::Text(line.clone())).await.is_err() {
             error!("websocket client disconnected");
@@ -193,8 +556,47 @@ async fn handle_socket(mut socket: WebSocket, mut rx: broadcast::Receiver<String
 
 #[cfg(test)]
 mod tests {
-    use super::Args;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::{
+        bind_addr, bind_listener, build_world, dry_run, load_seed, log_filter, log_startup,
+        log_subscriber, next_frame_line, region_handler, AppState, Args, FrameOptions,
+        FrameRecorder, HistoryQuery, LogFormat, RegionHistory, SimParams,
+    };
+    use axum::extract::{Path as UrlPath, Query, State};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
     use clap::{error::ErrorKind, Parser};
+    use sim_core::io::frame::Frame;
+    use tracing_subscriber::filter::LevelFilter;
+    use tracing_subscriber::fmt::MakeWriter;
+    use tracing_subscriber::EnvFilter;
+
+    #[derive(Clone, Default)]
+    struct CapturedLog(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0
+                .lock()
+                .expect("log buffer lock")
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'writer> MakeWriter<'writer> for CapturedLog {
+        type Writer = CapturedLog;
+
+        fn make_writer(&'writer self) -> Self::Writer {
+            self.clone()
+        }
+    }
 
     #[test]
     fn rejects_conflicting_seed_aliases() {
@@ -223,4 +625,242 @@ mod tests {
             .unwrap_err();
         assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
+
+    #[test]
+    fn dry_run_validates_procedural_config() {
+        let args = Args::try_parse_from([
+            "simd",
+            "--seed",
+            "7",
+            "--width",
+            "4",
+            "--height",
+            "2",
+            "--dry-run",
+        ])
+        .expect("args parse");
+        let summary = dry_run(&args).expect("dry run succeeds");
+        assert!(summary.contains("8 regions (4x2)"), "summary: {}", summary);
+        assert!(summary.contains("127.0.0.1:8787"), "summary: {}", summary);
+    }
+
+    #[test]
+    fn dry_run_rejects_unparseable_bind() {
+        let args = Args::try_parse_from([
+            "simd",
+            "--seed",
+            "7",
+            "--width",
+            "4",
+            "--height",
+            "2",
+            "--bind",
+            "not an address",
+            "--dry-run",
+        ])
+        .expect("args parse");
+        let err = dry_run(&args).expect_err("bad bind fails");
+        assert!(err.to_string().contains("invalid bind address"));
+    }
+
+    #[tokio::test]
+    async fn region_endpoint_serves_recent_history() {
+        let args = Args::try_parse_from([
+            "simd",
+            "--seed",
+            "7",
+            "--width",
+            "3",
+            "--height",
+            "2",
+            "--history-window",
+            "4",
+        ])
+        .expect("args parse");
+        let mut world = build_world(&load_seed(&args).expect("seed"), args.seed);
+        let mut history = RegionHistory::new(args.history_window as usize, world.regions.len());
+        for _ in 0..6 {
+            next_frame_line(
+                &mut world,
+                &SimParams::default(),
+                FrameOptions::default(),
+                None,
+                false,
+            )
+            .expect("tick");
+            history.record(&world);
+        }
+        let state = AppState {
+            tx: tokio::sync::broadcast::channel(1).0,
+            hello: Default::default(),
+            history: std::sync::Arc::new(tokio::sync::Mutex::new(history)),
+        };
+
+        let fetch = |id: usize, window: Option<usize>| {
+            let state = state.clone();
+            async move {
+                let response =
+                    region_handler(UrlPath(id), Query(HistoryQuery { window }), State(state))
+                        .await
+                        .into_response();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .expect("body reads");
+                let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+                (status, json)
+            }
+        };
+
+        let (status, json) = fetch(4, Some(3)).await;
+        assert_eq!(status, StatusCode::OK);
+        let ticks: Vec<u64> = json["samples"]
+            .as_array()
+            .expect("samples array")
+            .iter()
+            .map(|sample| sample["t"].as_u64().expect("tick"))
+            .collect();
+        assert_eq!(ticks, vec![4, 5, 6]);
+        assert_eq!(
+            json["samples"][2]["temp"],
+            i64::from(world.regions[4].temperature_tenths_c)
+        );
+
+        let (_, full) = fetch(0, None).await;
+        assert_eq!(full["samples"].as_array().map(Vec::len), Some(4));
+        let (status, _) = fetch(6, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn huge_history_window_allocates_only_what_it_records() {
+        let args = Args::try_parse_from([
+            "simd",
+            "--seed",
+            "7",
+            "--width",
+            "3",
+            "--height",
+            "2",
+            "--history-window",
+            &u64::MAX.to_string(),
+        ])
+        .expect("args parse");
+        let world = build_world(&load_seed(&args).expect("seed"), args.seed);
+        let mut history = RegionHistory::new(usize::MAX, world.regions.len());
+        assert_eq!(history.ticks.capacity(), 0);
+        history.record(&world);
+        assert_eq!(history.ticks.len(), 1);
+        assert!(history.ticks.capacity() < 1_024);
+    }
+
+    #[test]
+    fn recorded_run_writes_one_frame_per_tick() {
+        let path = std::env::temp_dir().join(format!("simd-record-{}.ndjson", std::process::id()));
+        let args = Args::try_parse_from([
+            "simd",
+            "--seed",
+            "7",
+            "--width",
+            "3",
+            "--height",
+            "2",
+            "--record",
+            path.to_str().expect("utf8 temp path"),
+        ])
+        .expect("args parse");
+        let mut world = build_world(&load_seed(&args).expect("seed"), args.seed);
+        let mut recorder =
+            FrameRecorder::create(args.record.as_deref().expect("record path")).expect("create");
+        for _ in 0..4 {
+            let (line, _, _) = next_frame_line(
+                &mut world,
+                &SimParams::default(),
+                FrameOptions::default(),
+                None,
+                false,
+            )
+            .expect("tick");
+            recorder.append(&line).expect("append");
+        }
+
+        let text = std::fs::read_to_string(&path).expect("record file reads");
+        let ticks: Vec<u64> = text
+            .lines()
+            .map(|line| Frame::from_ndjson(line).expect("line parses as a frame").t)
+            .collect();
+        assert_eq!(ticks, vec![1, 2, 3, 4]);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn bind_accepts_ipv4_and_ipv6_wildcards() {
+        let addr = |bind: &str| {
+            let args =
+                Args::try_parse_from(["simd", "--bind", bind, "--port", "0"]).expect("args parse");
+            bind_addr(&args).expect("bind address parses").to_string()
+        };
+        assert_eq!(addr("0.0.0.0"), "0.0.0.0:0");
+        assert_eq!(addr("::"), "[::]:0");
+        assert_eq!(addr("[::1]"), "[::1]:0");
+    }
+
+    #[tokio::test]
+    async fn port_zero_logs_the_bound_port() {
+        let args = Args::try_parse_from(["simd", "--port", "0"]).expect("args parse");
+        let (_listener, local_addr) = bind_listener(bind_addr(&args).expect("bind address"))
+            .await
+            .expect("ephemeral bind succeeds");
+        assert_ne!(local_addr.port(), 0);
+
+        let captured = CapturedLog::default();
+        let subscriber = log_subscriber(LogFormat::Json, EnvFilter::new("info"), captured.clone());
+        tracing::subscriber::with_default(subscriber, || log_startup(local_addr));
+        let bytes = captured.0.lock().expect("log buffer lock").clone();
+        let output = String::from_utf8(bytes).expect("utf8 log output");
+        let record: serde_json::Value =
+            serde_json::from_str(output.lines().next().expect("startup line")).expect("json");
+        assert_eq!(record["fields"]["addr"], local_addr.to_string());
+        assert!(!local_addr.to_string().ends_with(":0"));
+    }
+
+    #[test]
+    fn verbosity_flags_select_filter_level() {
+        let level = |argv: &[&str]| {
+            let args = Args::try_parse_from(argv).expect("args parse");
+            log_filter(&args, None).max_level_hint()
+        };
+        assert_eq!(level(&["simd"]), Some(LevelFilter::INFO));
+        assert_eq!(level(&["simd", "-q"]), Some(LevelFilter::ERROR));
+        assert_eq!(level(&["simd", "-v"]), Some(LevelFilter::DEBUG));
+        assert!(level(&["simd", "-vv"]) >= Some(LevelFilter::DEBUG));
+        assert!(level(&["simd"]) < Some(LevelFilter::DEBUG));
+
+        let args = Args::try_parse_from(["simd", "-vv"]).expect("args parse");
+        assert_eq!(
+            log_filter(&args, Some("warn")).max_level_hint(),
+            Some(LevelFilter::WARN)
+        );
+        let err = Args::try_parse_from(["simd", "-q", "-v"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn json_log_format_emits_structured_startup_line() {
+        let args = Args::try_parse_from(["simd", "--log-format", "json"]).expect("args parse");
+        assert_eq!(args.log_format, LogFormat::Json);
+
+        let captured = CapturedLog::default();
+        let subscriber = log_subscriber(args.log_format, EnvFilter::new("info"), captured.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            log_startup("127.0.0.1:8787".parse().expect("socket address"))
+        });
+
+        let bytes = captured.0.lock().expect("log buffer lock").clone();
+        let output = String::from_utf8(bytes).expect("utf8 log output");
+        let line = output.lines().next().expect("startup line");
+        let record: serde_json::Value = serde_json::from_str(line).expect("json log line");
+        assert_eq!(record["fields"]["addr"], "127.0.0.1:8787");
+        assert_eq!(record["fields"]["message"], "starting simd");
+    }
 }
@@ -0,0 +1,124 @@
+//! Counting global allocator behind the `alloc-profiling` feature.
+//!
+//! Counts are kept per thread so `--profile-allocations` (and tests running in
+//! parallel) only see allocations made by the thread that ticks the world.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    // `try_with` because the allocator can run while thread-locals are torn down.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations (including reallocations) made so far on the current thread.
+pub fn allocation_count() -> u64 {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Allocation counts for each measured tick of a run.
+#[derive(Clone, Debug, Default)]
+pub struct AllocationProfile {
+    per_tick: Vec<u64>,
+}
+
+impl AllocationProfile {
+    /// Run `tick` and record how many allocations it made.
+    pub fn measure<T>(&mut self, tick: impl FnOnce() -> T) -> T {
+        let before = allocation_count();
+        let result = tick();
+        self.per_tick.push(allocation_count() - before);
+        result
+    }
+
+    pub fn per_tick(&self) -> &[u64] {
+        &self.per_tick
+    }
+
+    /// One `t=<tick> allocations=<n>` line per tick followed by a min/mean/max summary.
+    pub fn report(&self) -> String {
+        let per_tick = self.per_tick();
+        let mut lines: Vec<String> = per_tick
+            .iter()
+            .enumerate()
+            .map(|(index, count)| format!("t={} allocations={}", index + 1, count))
+            .collect();
+        if let (Some(min), Some(max)) = (per_tick.iter().min(), per_tick.iter().max()) {
+            let mean = per_tick.iter().sum::<u64>() as f64 / per_tick.len() as f64;
+            lines.push(format!(
+                "allocations per tick: min={} mean={:.1} max={}",
+                min, mean, max
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllocationProfile;
+    use sim_core::io::seed::build_world;
+    use sim_core::testing::test_seed;
+    use sim_core::tick_once;
+
+    fn profile_ticks(ticks: u64) -> AllocationProfile {
+        let seed = test_seed("alloc", 4, 4);
+        let mut world = build_world(&seed, None);
+        let mut profile = AllocationProfile::default();
+        for tick in 1..=ticks {
+            let world_seed = world.seed;
+            profile
+                .measure(|| tick_once(&mut world, world_seed, tick))
+                .expect("tick succeeds");
+        }
+        profile
+    }
+
+    #[test]
+    fn allocation_counts_are_reported_and_stable() {
+        let first = profile_ticks(12);
+        let second = profile_ticks(12);
+        assert_eq!(first.per_tick().len(), 12);
+        assert!(first.per_tick().iter().all(|count| *count > 0));
+        // Identical worlds allocate identically; seasons only move counts within a band.
+        assert_eq!(first.per_tick(), second.per_tick());
+        let min = first.per_tick().iter().min().copied().unwrap_or(0);
+        let max = first.per_tick().iter().max().copied().unwrap_or(0);
+        assert!(
+            max <= min * 2,
+            "per-tick allocations {:?}",
+            first.per_tick()
+        );
+        assert!(first.report().contains("allocations per tick: min="));
+    }
+}
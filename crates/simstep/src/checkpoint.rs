@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sim_core::io::snapshot::save_snapshot;
+use sim_core::world::World;
+
+/// Periodic `world-<tick>.snapshot` files written during a run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoints {
+    pub dir: PathBuf,
+    /// Write a snapshot after every tick divisible by this.
+    pub every: u64,
+    /// Most recent snapshots to retain; `None` keeps all of them.
+    pub keep: Option<usize>,
+}
+
+impl Checkpoints {
+    /// Snapshot `world` if its tick is due, then prune the oldest files beyond `keep`.
+    pub fn after_tick(&self, world: &World) -> Result<()> {
+        if self.every == 0 || world.tick % self.every != 0 {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create checkpoint dir {:?}", self.dir))?;
+        save_snapshot(world, &checkpoint_path(&self.dir, world.tick))?;
+
+        if let Some(keep) = self.keep {
+            let existing = list_checkpoints(&self.dir)?;
+            let stale = existing.len().saturating_sub(keep);
+            for (_, path) in existing.into_iter().take(stale) {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove old checkpoint {:?}", path))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn checkpoint_path(dir: &Path, tick: u64) -> PathBuf {
+    dir.join(format!("world-{}.snapshot", tick))
+}
+
+/// Checkpoint files in `dir` as `(tick, path)`, oldest first; other files are ignored.
+pub fn list_checkpoints(dir: &Path) -> Result<Vec<(u64, PathBuf)>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read checkpoints {:?}", dir))?;
+    let mut checkpoints = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let tick = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("world-"))
+            .and_then(|name| name.strip_suffix(".snapshot"))
+            .and_then(|tick| tick.parse::<u64>().ok());
+        if let Some(tick) = tick {
+            checkpoints.push((tick, path));
+        }
+    }
+    checkpoints.sort();
+    Ok(checkpoints)
+}
+
+/// The highest-tick checkpoint in `dir`, if any.
+pub fn newest_checkpoint(dir: &Path) -> Result<Option<(u64, PathBuf)>> {
+    Ok(list_checkpoints(dir)?.pop())
+}
@@ -1,14 +1,33 @@
 use std::fs::File;
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use clap::Parser;
 use serde_json::json;
-use sim_core::io::frame::make_frame;
-use sim_core::io::seed::{build_world, Seed};
-use sim_core::tick_once;
+use sim_core::biome;
+use sim_core::diag::{biome_histogram, GlobalMeans, SteadyStateDetector};
+use sim_core::io::frame::{make_frame, Frame};
+use sim_core::io::geojson::world_to_geojson;
+use sim_core::io::index::FrameIndexEntry;
+use sim_core::io::seed::{build_world, format_seed_hash, Seed};
+use sim_core::io::snapshot::load_snapshot;
+use sim_core::kernels::forcing::ForcingSeries;
+use sim_core::kernels::registry;
+use sim_core::reduce::apply_frame;
+use sim_core::testing::{first_divergence, Divergence};
 use sim_core::world::World;
+use sim_core::{tick_once_with_params, SimParams};
+
+#[cfg(feature = "alloc-profiling")]
+mod alloc_profile;
+mod checkpoint;
+mod summary;
+mod sweep;
+
+use checkpoint::{newest_checkpoint, Checkpoints};
+use summary::RunSummary;
+use sweep::SeedSweep;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -16,9 +35,22 @@ use sim_core::world::World;
     about = "Batch runner for deterministic NDJSON frames"
 )]
 struct Args {
-    /// Path to the seed JSON document.
-    #[arg(long = "seed-file", value_name = "PATH")]
-    seed_file: PathBuf,
+    /// Path to the seed JSON document, or `-` to read it from stdin.
+    #[arg(
+        long = "seed-file",
+        value_name = "PATH",
+        required_unless_present_any = ["compare", "resume", "seed_dir", "list_kernels"]
+    )]
+    seed_file: Option<PathBuf>,
+
+    /// Run every `*.json` seed in this directory; `--out` then names a directory that
+    /// receives `<seed file stem>.ndjson` per seed.
+    #[arg(
+        long = "seed-dir",
+        value_name = "DIR",
+        conflicts_with_all = ["seed_file", "seed_sweep", "ensemble", "resume", "checkpoint_every"]
+    )]
+    seed_dir: Option<PathBuf>,
 
     /// Override the world seed used when building the initial world state.
     #[arg(long, value_name = "NUMBER", conflicts_with = "world_seed")]
@@ -29,25 +61,155 @@ struct Args {
     world_seed: Option<u64>,
 
     /// Number of ticks to execute.
-    #[arg(long)]
-    ticks: u64,
+    #[arg(long, required_unless_present_any = ["compare", "replay", "list_kernels"])]
+    ticks: Option<u64>,
 
-    /// Output NDJSON file path.
-    #[arg(long)]
-    out: PathBuf,
+    /// Output NDJSON file path (the world JSON under `--replay`), or `-` to stream frames to
+    /// stdout.
+    #[arg(long, required_unless_present_any = ["compare", "list_kernels"])]
+    out: Option<PathBuf>,
 
-    /// Optional path to emit per-tick global metrics as NDJSON.
+    /// Optional path to emit per-tick global metrics as NDJSON, including each tick's
+    /// region count per biome under `biomes`.
     #[arg(long = "emit-metrics", value_name = "PATH")]
     emit_metrics: Option<PathBuf>,
-}
 
-struct GlobalMeans {
-    temp_c: f64,
-    albedo: f64,
-    humidity_pct: f64,
-    precip_native: f64,
+    /// Optional sidecar NDJSON mapping each tick to its frame's byte offset in `--out`.
+    #[arg(long = "index-out", value_name = "PATH")]
+    index_out: Option<PathBuf>,
+
+    /// Optional path to write the final region table as a GeoJSON FeatureCollection.
+    #[arg(long = "geojson-out", value_name = "PATH")]
+    geojson_out: Option<PathBuf>,
+
+    /// Optional `tick,offset_tenths` CSV of global temperature forcing added to every
+    /// region's baseline before the atmosphere runs.
+    #[arg(long = "forcing-csv", value_name = "PATH")]
+    forcing_csv: Option<PathBuf>,
+
+    /// Run once per value of a numeric seed field, e.g. `humidity.equator=0.1:0.5:0.1`.
+    /// Each run writes `<out stem>_<field>_<value>.<ext>` (and likewise for metrics).
+    #[arg(long = "seed-sweep", value_name = "FIELD=START:END:STEP")]
+    seed_sweep: Option<SeedSweep>,
+
+    /// Run M ensemble members over the same world, varying only the per-member kernels.
+    /// Member `k` writes `<out stem>_member_<k>.<ext>` (and likewise for other outputs).
+    #[arg(
+        long,
+        value_name = "M",
+        conflicts_with_all = ["seed_sweep", "checkpoint_every", "resume"],
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    ensemble: Option<u64>,
+
+    /// Write a full-world snapshot to `--checkpoint-dir` after every N-th tick.
+    #[arg(
+        long = "checkpoint-every",
+        value_name = "N",
+        requires = "checkpoint_dir",
+        conflicts_with = "seed_sweep",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    checkpoint_every: Option<u64>,
+
+    /// Directory holding `world-<tick>.snapshot` checkpoints.
+    #[arg(long = "checkpoint-dir", value_name = "DIR")]
+    checkpoint_dir: Option<PathBuf>,
+
+    /// Keep only the K most recent checkpoints, deleting older ones as new ones land.
+    #[arg(
+        long = "keep-checkpoints",
+        value_name = "K",
+        requires = "checkpoint_every",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    keep_checkpoints: Option<usize>,
+
+    /// Continue from the newest checkpoint in `--checkpoint-dir` until the world reaches
+    /// `--ticks`; `--out` receives only the resumed frames.
+    #[arg(
+        long,
+        requires = "checkpoint_dir",
+        conflicts_with_all = ["seed_sweep", "seed", "world_seed"]
+    )]
+    resume: bool,
+
+    /// Stop early once the season-averaged global means hold within `--tolerance` for
+    /// several consecutive ticks; `--ticks` still caps the run.
+    #[arg(long = "run-to-steady")]
+    run_to_steady: bool,
+
+    /// Largest per-tick relative change in any season-averaged global mean that still
+    /// counts as steady.
+    #[arg(
+        long,
+        value_name = "T",
+        default_value_t = 0.005,
+        requires = "run_to_steady"
+    )]
+    tolerance: f64,
+
+    /// Fail on the first value a stage pushes out of range instead of clamping it.
+    #[arg(long)]
+    strict: bool,
+
+    /// Emit insolation and tide envelope only for regions whose value changed.
+    #[arg(long)]
+    sparse_astronomy: bool,
+
+    /// Print per-tick allocation counts to stderr when the run finishes.
+    #[cfg(feature = "alloc-profiling")]
+    #[arg(long = "profile-allocations")]
+    profile_allocations: bool,
+
+    /// Compare two NDJSON runs frame by frame instead of simulating; exits non-zero and
+    /// reports the first differing tick and field on divergence.
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["seed_file", "ticks", "out", "seed_sweep"]
+    )]
+    compare: Option<Vec<PathBuf>>,
+
+    /// Rebuild the world from `--seed-file` and this recorded NDJSON run by re-applying
+    /// its frame diffs up to `--to-tick`, without running the kernels; `--out` receives
+    /// the world as JSON. State the frames do not record keeps its seeded values, so the
+    /// output is for inspection and cannot seed `--resume`.
+    #[arg(
+        long,
+        value_name = "FRAMES",
+        requires_all = ["seed_file", "to_tick"],
+        conflicts_with_all = ["seed_sweep", "ensemble", "seed_dir", "resume", "checkpoint_every"]
+    )]
+    replay: Option<PathBuf>,
+
+    /// Tick to stop replaying at.
+    #[arg(long = "to-tick", value_name = "T", requires = "replay")]
+    to_tick: Option<u64>,
+
+    /// Print a JSON run summary when the run ends: tick count, global temperature range
+    /// and mean, volcanic events, monsoon onsets, peak sea level, and cause totals.
+    /// Goes to stdout, or to stderr when `--out -` streams frames there.
+    #[arg(long)]
+    summary: bool,
+
+    /// Drop frame diff entries that leave a region's stored value unchanged.
+    #[arg(long = "compact-diffs")]
+    compact_diffs: bool,
+
+    /// Print each stage label in tick order with whether it varies per ensemble member, then
+    /// exit.
+    #[arg(long = "list-kernels")]
+    list_kernels: bool,
 }
 
+/// `--out` path that streams frames to stdout instead of a file.
+const STDOUT_PATH: &str = "-";
+
+/// Consecutive quiet ticks `--run-to-steady` waits for before stopping.
+const STEADY_STATE_PATIENCE: usize = 10;
+
 fn compute_global_means(
     world: &World,
     humidity_cache: &[i32],
@@ -90,15 +252,432 @@ fn compute_global_means(
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let seed = Seed::load_from_path(&args.seed_file)
-        .with_context(|| format!("failed to read seed {:?}", args.seed_file))?;
-    let mut world = build_world(&seed, args.seed.or(args.world_seed));
+    if args.list_kernels {
+        for kernel in registry() {
+            println!(
+                "{}\t{}\t{}",
+                kernel.order,
+                kernel.stage,
+                if kernel.per_member {
+                    "per-member"
+                } else {
+                    "shared"
+                }
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(paths) = &args.compare {
+        if let Some(divergence) = compare_runs(&paths[0], &paths[1])? {
+            bail!("runs diverge: {}", divergence);
+        }
+        println!("runs match");
+        return Ok(());
+    }
+
+    if let Some(frames) = &args.replay {
+        let seed_file = args
+            .seed_file
+            .as_deref()
+            .context("--replay requires --seed-file")?;
+        let seed = Seed::load(seed_file)
+            .with_context(|| format!("failed to read seed {:?}", seed_file))?;
+        let to_tick = args.to_tick.context("--replay requires --to-tick")?;
+        let world = replay(&seed, args.seed.or(args.world_seed), frames, to_tick)?;
+        let out = args.out.as_deref().context("--out is required")?;
+        let file = File::create(out).with_context(|| format!("failed to create {:?}", out))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &world)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        return Ok(());
+    }
+
+    let ticks = args.ticks.context("--ticks is required")?;
+    let outputs = RunOutputs {
+        frames: args.out.clone().context("--out is required")?,
+        metrics: args.emit_metrics.clone(),
+        index: args.index_out.clone(),
+        geojson: args.geojson_out.clone(),
+        checkpoints: args
+            .checkpoint_every
+            .zip(args.checkpoint_dir.clone())
+            .map(|(every, dir)| Checkpoints {
+                dir,
+                every,
+                keep: args.keep_checkpoints,
+            }),
+        params: SimParams {
+            strict: args.strict,
+            sparse_astronomy: args.sparse_astronomy,
+            ..SimParams::default()
+        },
+        steady_tolerance: args.run_to_steady.then_some(args.tolerance),
+        summary: args.summary,
+        compact_diffs: args.compact_diffs,
+        #[cfg(feature = "alloc-profiling")]
+        allocation_report: args.profile_allocations,
+    };
+    ensure!(
+        outputs.frames != Path::new(STDOUT_PATH)
+            || (args.seed_dir.is_none() && args.seed_sweep.is_none() && args.ensemble.is_none()),
+        "--out - streams a single run; it cannot be combined with --seed-dir, --seed-sweep, or \
+         --ensemble"
+    );
+    let forcing = args
+        .forcing_csv
+        .as_deref()
+        .map(ForcingSeries::load_from_path)
+        .transpose()?;
+
+    if args.resume {
+        let dir = args
+            .checkpoint_dir
+            .as_deref()
+            .context("--resume requires --checkpoint-dir")?;
+        return resume(dir, forcing.as_ref(), ticks, &outputs);
+    }
+
+    if let Some(dir) = &args.seed_dir {
+        let world_seed = args.seed.or(args.world_seed);
+        return run_seed_dir(dir, world_seed, forcing.as_ref(), ticks, &outputs);
+    }
+
+    let seed_file = args
+        .seed_file
+        .as_deref()
+        .context("--seed-file is required")?;
+    let seed =
+        Seed::load(seed_file).with_context(|| format!("failed to read seed {:?}", seed_file))?;
+    let world_seed = args.seed.or(args.world_seed);
+
+    match (&args.seed_sweep, args.ensemble) {
+        (Some(sweep), _) => run_sweep(sweep, &seed, world_seed, forcing.as_ref(), ticks, &outputs),
+        (None, Some(members)) => run_ensemble(
+            members,
+            &seed,
+            world_seed,
+            forcing.as_ref(),
+            ticks,
+            &outputs,
+        ),
+        (None, None) => run(&seed, world_seed, forcing.as_ref(), ticks, &outputs),
+    }
+}
+
+/// Files written by a single run; only `frames` is mandatory.
+#[derive(Clone, Debug)]
+struct RunOutputs {
+    frames: PathBuf,
+    metrics: Option<PathBuf>,
+    index: Option<PathBuf>,
+    geojson: Option<PathBuf>,
+    checkpoints: Option<Checkpoints>,
+    /// Simulation switches applied to every tick.
+    params: SimParams,
+    /// Stop before the tick cap once global means hold within this tolerance.
+    steady_tolerance: Option<f64>,
+    /// Print a [`RunSummary`] once the run ends.
+    summary: bool,
+    /// `Diff::compact` each tick's diff against the pre-tick world before framing it.
+    compact_diffs: bool,
+    /// Print the run's per-tick allocation counts to stderr.
+    #[cfg(feature = "alloc-profiling")]
+    allocation_report: bool,
+}
+
+impl RunOutputs {
+    #[cfg(test)]
+    fn frames_only(frames: PathBuf) -> Self {
+        Self {
+            frames,
+            metrics: None,
+            index: None,
+            geojson: None,
+            checkpoints: None,
+            params: SimParams::default(),
+            steady_tolerance: None,
+            summary: false,
+            compact_diffs: false,
+            #[cfg(feature = "alloc-profiling")]
+            allocation_report: false,
+        }
+    }
+
+    /// Per-value paths for one seed sweep run.
+    fn for_sweep(&self, sweep: &SeedSweep, label: &str) -> Self {
+        let swept = |path: &Path| sweep.output_path(path, label);
+        Self {
+            frames: swept(&self.frames),
+            metrics: self.metrics.as_deref().map(swept),
+            index: self.index.as_deref().map(swept),
+            geojson: self.geojson.as_deref().map(swept),
+            checkpoints: self.checkpoints.clone(),
+            params: self.params,
+            steady_tolerance: self.steady_tolerance,
+            summary: self.summary,
+            compact_diffs: self.compact_diffs,
+            #[cfg(feature = "alloc-profiling")]
+            allocation_report: self.allocation_report,
+        }
+    }
+
+    /// Paths for one `--seed-dir` run: frames land in the `frames` directory as
+    /// `<name>.ndjson`, other outputs gain a `_<name>` suffix.
+    fn for_seed(&self, name: &str) -> Self {
+        let suffixed = |path: &Path| suffixed_path(path, name);
+        Self {
+            frames: self.frames.join(format!("{}.ndjson", name)),
+            metrics: self.metrics.as_deref().map(suffixed),
+            index: self.index.as_deref().map(suffixed),
+            geojson: self.geojson.as_deref().map(suffixed),
+            checkpoints: None,
+            params: self.params,
+            steady_tolerance: self.steady_tolerance,
+            summary: self.summary,
+            compact_diffs: self.compact_diffs,
+            #[cfg(feature = "alloc-profiling")]
+            allocation_report: self.allocation_report,
+        }
+    }
+
+    /// Per-member paths for one ensemble run, e.g. `run.ndjson` → `run_member_2.ndjson`.
+    fn for_member(&self, member: u64) -> Self {
+        let suffix = format!("member_{}", member);
+        let suffixed = |path: &Path| suffixed_path(path, &suffix);
+        Self {
+            frames: suffixed(&self.frames),
+            metrics: self.metrics.as_deref().map(suffixed),
+            index: self.index.as_deref().map(suffixed),
+            geojson: self.geojson.as_deref().map(suffixed),
+            checkpoints: None,
+            params: self.params,
+            steady_tolerance: self.steady_tolerance,
+            summary: self.summary,
+            compact_diffs: self.compact_diffs,
+            #[cfg(feature = "alloc-profiling")]
+            allocation_report: self.allocation_report,
+        }
+    }
+}
+
+/// `path` with `_<suffix>` appended to its file stem, keeping the extension.
+fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut name = format!("{}_{}", stem, suffix);
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+/// Parse both runs as frames and report the first divergence between them, if any.
+///
+/// Frames are re-serialized before comparing, so key order and whitespace never count
+/// as differences; malformed lines are reported with their file and line number.
+fn compare_runs(old: &Path, new: &Path) -> Result<Option<Divergence>> {
+    let old_lines = canonical_frames(old)?;
+    let new_lines = canonical_frames(new)?;
+    Ok(first_divergence(&old_lines, &new_lines))
+}
+
+fn canonical_frames(path: &Path) -> Result<Vec<String>> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            let frame = Frame::from_ndjson(line)
+                .with_context(|| format!("{:?} line {}: invalid frame", path, number + 1))?;
+            Ok(frame.to_ndjson()?.trim_end().to_string())
+        })
+        .collect()
+}
+
+fn run_sweep(
+    sweep: &SeedSweep,
+    base: &Seed,
+    world_seed: Option<u64>,
+    forcing: Option<&ForcingSeries>,
+    ticks: u64,
+    outputs: &RunOutputs,
+) -> Result<()> {
+    for (label, value) in &sweep.values {
+        let seed = sweep.apply(base, *value)?;
+        let outputs = outputs.for_sweep(sweep, label);
+        run(&seed, world_seed, forcing, ticks, &outputs)
+            .with_context(|| format!("seed sweep run {}={} failed", sweep.field, label))?;
+    }
+    Ok(())
+}
+
+/// Run each seed in `dir` independently, in file name order.
+///
+/// A seed that fails to load or run is reported on stderr and skipped; the batch still
+/// fails afterwards so callers see a non-zero exit.
+fn run_seed_dir(
+    dir: &Path,
+    world_seed: Option<u64>,
+    forcing: Option<&ForcingSeries>,
+    ticks: u64,
+    outputs: &RunOutputs,
+) -> Result<()> {
+    let mut seeds = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("failed to read {:?}", dir))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            seeds.push(path);
+        }
+    }
+    seeds.sort();
+    ensure!(!seeds.is_empty(), "no *.json seeds found in {:?}", dir);
+    std::fs::create_dir_all(&outputs.frames)
+        .with_context(|| format!("failed to create output dir {:?}", outputs.frames))?;
+
+    let mut failed = 0usize;
+    for path in &seeds {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let result = Seed::load_from_path(path)
+            .and_then(|seed| run(&seed, world_seed, forcing, ticks, &outputs.for_seed(&name)));
+        if let Err(err) = result {
+            eprintln!("seed {:?} failed: {:#}", path, err);
+            failed += 1;
+        }
+    }
+    ensure!(
+        failed == 0,
+        "{} of {} seeds in {:?} failed",
+        failed,
+        seeds.len(),
+        dir
+    );
+    Ok(())
+}
+
+/// Run members `0..members` of one world; member 0 matches a plain run.
+fn run_ensemble(
+    members: u64,
+    seed: &Seed,
+    world_seed: Option<u64>,
+    forcing: Option<&ForcingSeries>,
+    ticks: u64,
+    outputs: &RunOutputs,
+) -> Result<()> {
+    for member in 0..members {
+        let mut world = build_world(seed, world_seed);
+        world.forcing = forcing.cloned();
+        world.ensemble_member = member;
+        run_world(world, ticks, &outputs.for_member(member))
+            .with_context(|| format!("ensemble member {} failed", member))?;
+    }
+    Ok(())
+}
+
+fn run(
+    seed: &Seed,
+    world_seed: Option<u64>,
+    forcing: Option<&ForcingSeries>,
+    ticks: u64,
+    outputs: &RunOutputs,
+) -> Result<()> {
+    let mut world = build_world(seed, world_seed);
+    world.forcing = forcing.cloned();
+    run_world(world, ticks, outputs)
+}
 
-    let frame_file =
-        File::create(&args.out).with_context(|| format!("failed to create {:?}", args.out))?;
-    let mut frame_writer = BufWriter::new(frame_file);
+/// Load the newest checkpoint in `dir` and tick it until it reaches `target_tick`.
+fn resume(
+    dir: &Path,
+    forcing: Option<&ForcingSeries>,
+    target_tick: u64,
+    outputs: &RunOutputs,
+) -> Result<()> {
+    let (_, path) =
+        newest_checkpoint(dir)?.with_context(|| format!("no checkpoints found in {:?}", dir))?;
+    let mut world = load_snapshot(&path)?;
+    ensure!(
+        world.tick < target_tick,
+        "checkpoint {:?} is already at tick {} (--ticks {})",
+        path,
+        world.tick,
+        target_tick
+    );
+    world.forcing = forcing.cloned();
+    let remaining = target_tick - world.tick;
+    run_world(world, remaining, outputs)
+}
+
+/// Rebuild the world at `to_tick` from `seed` by re-applying the diffs recorded in
+/// `frames`, which must start at tick 1.
+///
+/// Climate bookkeeping and hazard meters are not in frames, so they keep their seeded
+/// values; every region field a frame carries matches the live run.
+fn replay(seed: &Seed, world_seed: Option<u64>, frames: &Path, to_tick: u64) -> Result<World> {
+    let mut world = build_world(seed, world_seed);
+    let file = File::open(frames).with_context(|| format!("failed to open {:?}", frames))?;
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        if world.tick == to_tick {
+            break;
+        }
+        let line = line.with_context(|| format!("failed to read {:?}", frames))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let frame = Frame::from_ndjson(&line)
+            .with_context(|| format!("{:?} line {}: invalid frame", frames, number + 1))?;
+        apply_frame(&mut world, &frame)
+            .with_context(|| format!("{:?} line {}", frames, number + 1))?;
+    }
+    ensure!(
+        world.tick == to_tick,
+        "{:?} ends at tick {}, before --to-tick {}",
+        frames,
+        world.tick,
+        to_tick
+    );
+    Ok(world)
+}
+
+/// Advance `world` by `ticks`, writing every requested output.
+///
+/// Frames go to stdout when `outputs.frames` is [`STDOUT_PATH`]; everything else the run
+/// reports goes to its own file or to stderr, so the stream stays pure NDJSON.
+fn run_world(world: World, ticks: u64, outputs: &RunOutputs) -> Result<()> {
+    let out = outputs.frames.as_path();
+    let summary = if out == Path::new(STDOUT_PATH) {
+        let frame_writer = BufWriter::new(std::io::stdout().lock());
+        run_world_into(world, ticks, outputs, frame_writer)?
+    } else {
+        let frame_file =
+            File::create(out).with_context(|| format!("failed to create {:?}", out))?;
+        run_world_into(world, ticks, outputs, BufWriter::new(frame_file))?
+    };
+    match summary {
+        Some(summary) if out == Path::new(STDOUT_PATH) => eprintln!("{}", summary.to_json()),
+        Some(summary) => println!("{}", summary.to_json()),
+        None => {}
+    }
+    Ok(())
+}
 
-    let mut metrics_writer = if let Some(path) = &args.emit_metrics {
+/// [`run_world`] with frames written to `frame_writer`; returns the run's summary when
+/// `outputs.summary` asks for one.
+fn run_world_into<W: Write>(
+    mut world: World,
+    ticks: u64,
+    outputs: &RunOutputs,
+    mut frame_writer: W,
+) -> Result<Option<RunSummary>> {
+    let mut metrics_writer = if let Some(path) = &outputs.metrics {
         let file = File::create(path)
             .with_context(|| format!("failed to create metrics file at {:?}", path))?;
         Some(BufWriter::new(file))
@@ -106,25 +685,69 @@ fn main() -> Result<()> {
         None
     };
 
+    let mut index_writer = if let Some(path) = &outputs.index {
+        let file = File::create(path)
+            .with_context(|| format!("failed to create frame index at {:?}", path))?;
+        Some(BufWriter::new(file))
+    } else {
+        None
+    };
+    let mut frame_offset = 0u64;
+    let params = &outputs.params;
+
     let mut humidity_cache = vec![0i32; world.regions.len()];
     let mut region_order: Vec<usize> = (0..world.regions.len()).collect();
     region_order.sort_by_key(|&idx| world.regions[idx].id);
 
-    for _ in 0..args.ticks {
+    let mut steady = outputs
+        .steady_tolerance
+        .map(|tolerance| SteadyStateDetector::new(tolerance, STEADY_STATE_PATIENCE));
+    let mut summary = outputs.summary.then(RunSummary::default);
+
+    #[cfg(feature = "alloc-profiling")]
+    let mut allocation_profile = alloc_profile::AllocationProfile::default();
+
+    for _ in 0..ticks {
         let next_tick = world.tick + 1;
         let seed = world.seed;
-        let (diff, chronicle, highlights) = tick_once(&mut world, seed, next_tick)?;
+        let before = outputs.compact_diffs.then(|| world.clone());
+        #[cfg(feature = "alloc-profiling")]
+        let (mut diff, chronicle, highlights) = allocation_profile
+            .measure(|| tick_once_with_params(&mut world, seed, next_tick, params))?;
+        #[cfg(not(feature = "alloc-profiling"))]
+        let (mut diff, chronicle, highlights) =
+            tick_once_with_params(&mut world, seed, next_tick, params)?;
+        if let Some(before) = &before {
+            diff.compact(before);
+        }
 
-        if let Some(writer) = metrics_writer.as_mut() {
+        let means = if metrics_writer.is_some() || steady.is_some() || summary.is_some() {
             for value in &diff.humidity {
                 let index = value.region as usize;
                 if let Some(slot) = humidity_cache.get_mut(index) {
                     *slot = value.value;
                 }
             }
+            Some(compute_global_means(&world, &humidity_cache, &region_order))
+        } else {
+            None
+        };
+
+        if let (Some(summary), Some(means)) = (summary.as_mut(), means) {
+            summary.record(
+                &means,
+                &diff.causes,
+                world.climate.sea_level_equivalent_mm(),
+            );
+        }
 
-            let means = compute_global_means(&world, &humidity_cache, &region_order);
+        if let (Some(writer), Some(means)) = (metrics_writer.as_mut(), means) {
             let diag_energy = diff.diagnostics.get("energy_balance").copied().unwrap_or(0);
+            let biomes: serde_json::Map<String, serde_json::Value> = biome::LABELS
+                .iter()
+                .zip(biome_histogram(&world))
+                .map(|(label, count)| (label.to_string(), json!(count)))
+                .collect();
             let metrics_line = json!({
                 "t": next_tick,
                 "global": {
@@ -133,7 +756,8 @@ fn main() -> Result<()> {
                     "humidity_pct": means.humidity_pct,
                     "precip_native": means.precip_native,
                     "diag_energy_tenths": diag_energy as f64,
-                }
+                },
+                "biomes": biomes,
             });
             let serialized = serde_json::to_string(&metrics_line)?;
             writer.write_all(serialized.as_bytes())?;
@@ -142,25 +766,73 @@ fn main() -> Result<()> {
 
         let width = world.width;
         let height = world.height;
-        let frame = make_frame(next_tick, diff, highlights, chronicle, false, width, height);
+        let mut frame = make_frame(next_tick, diff, highlights, chronicle, false, width, height);
+        // A resumed run continues the original, whose first frame already carried the hash.
+        if next_tick == 1 {
+            frame.world.seed_hash = world.seed_hash.map(format_seed_hash);
+        }
         let line = frame.to_ndjson()?;
+        if let Some(writer) = index_writer.as_mut() {
+            let entry = FrameIndexEntry {
+                t: next_tick,
+                offset: frame_offset,
+            };
+            writer.write_all(entry.to_ndjson()?.as_bytes())?;
+        }
         frame_writer.write_all(line.as_bytes())?;
+        frame_offset += line.len() as u64;
+
+        if let Some(checkpoints) = &outputs.checkpoints {
+            checkpoints.after_tick(&world)?;
+        }
+
+        if let (Some(detector), Some(means)) = (steady.as_mut(), means) {
+            detector.push(means);
+            if detector.converged() {
+                eprintln!("steady state reached at tick {}", next_tick);
+                break;
+            }
+        }
     }
 
     frame_writer.flush()?;
     if let Some(writer) = metrics_writer.as_mut() {
         writer.flush()?;
     }
+    if let Some(writer) = index_writer.as_mut() {
+        writer.flush()?;
+    }
+    if let Some(path) = &outputs.geojson {
+        let file = File::create(path)
+            .with_context(|| format!("failed to create geojson file at {:?}", path))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &world_to_geojson(&world))?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+    #[cfg(feature = "alloc-profiling")]
+    if outputs.allocation_report {
+        eprintln!("{}", allocation_profile.report());
+    }
 
-    Ok(())
+    Ok(summary)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{tick_once, Args};
+    use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+    use super::checkpoint::{list_checkpoints, Checkpoints};
+    use super::{
+        compare_runs, replay, resume, run, run_ensemble, run_seed_dir, run_sweep, run_world_into,
+        Args, RunOutputs, SeedSweep,
+    };
     use clap::{error::ErrorKind, Parser};
     use sim_core::io::frame::make_frame;
-    use sim_core::io::seed::{build_world, Seed};
+    use sim_core::io::index::FrameIndex;
+    use sim_core::io::seed::build_world;
+    use sim_core::testing::test_seed;
+    use sim_core::tick_once;
 
     #[test]
     fn requires_seed_file() {
@@ -190,14 +862,7 @@ mod tests {
 
     #[test]
     fn paired_runs_are_deterministic_over_200_ticks() {
-        let seed_json = r#"{
-            "name": "determinism",
-            "width": 4,
-            "height": 2,
-            "elevation_noise": {"octaves": 1, "freq": 0.1, "amp": 1.0, "seed": 3},
-            "humidity_bias": {"equator": 0.2, "poles": -0.2}
-        }"#;
-        let seed: Seed = serde_json::from_str(seed_json).expect("seed parses");
+        let seed = test_seed("determinism", 4, 2);
 
         let run_once = || {
             let mut world = build_world(&seed, Some(1_234_567));
@@ -225,4 +890,401 @@ mod tests {
         let second = run_once();
         assert_eq!(first, second);
     }
+
+    #[test]
+    fn humidity_sweep_writes_one_distinct_run_per_value() {
+        let seed = test_seed("sweep", 4, 2);
+        let sweep: SeedSweep = "humidity.equator=0.1:0.3:0.1".parse().expect("spec parses");
+        let labels: Vec<&str> = sweep
+            .values
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["0.1", "0.2", "0.3"]);
+
+        let dir = std::env::temp_dir().join(format!("simstep-sweep-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let out = dir.join("run.ndjson");
+        run_sweep(&sweep, &seed, None, None, 4, &RunOutputs::frames_only(out)).expect("sweep runs");
+
+        let outputs: Vec<String> = labels
+            .iter()
+            .map(|label| {
+                let path = dir.join(format!("run_humidity.equator_{}.ndjson", label));
+                std::fs::read_to_string(path).expect("sweep output exists")
+            })
+            .collect();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(outputs.iter().all(|frames| frames.lines().count() == 4));
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(outputs[1], outputs[2]);
+        assert_ne!(outputs[0], outputs[2]);
+    }
+
+    #[test]
+    fn ensemble_writes_one_run_per_member_with_member_zero_as_baseline() {
+        let seed = test_seed("ensemble", 4, 2);
+        let dir = std::env::temp_dir().join(format!("simstep-ensemble-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let baseline = dir.join("baseline.ndjson");
+        run(
+            &seed,
+            None,
+            None,
+            4,
+            &RunOutputs::frames_only(baseline.clone()),
+        )
+        .expect("baseline runs");
+        run_ensemble(
+            2,
+            &seed,
+            None,
+            None,
+            4,
+            &RunOutputs::frames_only(dir.join("run.ndjson")),
+        )
+        .expect("ensemble runs");
+
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).expect("output exists");
+        let baseline = std::fs::read_to_string(&baseline).expect("baseline exists");
+        let members = [read("run_member_0.ndjson"), read("run_member_1.ndjson")];
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(members[0], baseline);
+        assert_eq!(members[1].lines().count(), 4);
+        assert_ne!(members[0], members[1]);
+    }
+
+    #[test]
+    fn run_to_steady_stops_before_the_tick_cap() {
+        let seed = test_seed("steady", 4, 3);
+        let args = Args::try_parse_from([
+            "simstep",
+            "--seed-file",
+            "seed.json",
+            "--ticks",
+            "500",
+            "--out",
+            "run.ndjson",
+            "--run-to-steady",
+        ])
+        .expect("args parse");
+        let dir = std::env::temp_dir().join(format!("simstep-steady-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let out = dir.join("run.ndjson");
+        let outputs = RunOutputs {
+            steady_tolerance: args.run_to_steady.then_some(args.tolerance),
+            ..RunOutputs::frames_only(out.clone())
+        };
+        run(&seed, None, None, 500, &outputs).expect("run succeeds");
+
+        let frames = std::fs::read_to_string(&out).expect("frames exist");
+        std::fs::remove_dir_all(&dir).ok();
+        let ticks = frames.lines().count();
+        assert!(ticks > 20 && ticks < 500, "stopped after {} ticks", ticks);
+    }
+
+    #[test]
+    fn seed_dir_runs_every_seed_and_reports_failures() {
+        let dir = std::env::temp_dir().join(format!("simstep-seed-dir-{}", std::process::id()));
+        let seeds = dir.join("seeds");
+        std::fs::create_dir_all(&seeds).expect("temp dir");
+        for (name, noise_seed) in [("alpha", 4), ("beta", 5)] {
+            let mut seed = test_seed(name, 3, 2);
+            seed.noise.seed = noise_seed;
+            let json = serde_json::to_string(&seed).expect("seed serializes");
+            std::fs::write(seeds.join(format!("{}.json", name)), json).expect("write seed");
+        }
+        std::fs::write(seeds.join("notes.txt"), "not a seed").expect("write note");
+
+        let out = dir.join("runs");
+        run_seed_dir(&seeds, None, None, 5, &RunOutputs::frames_only(out.clone()))
+            .expect("seed dir runs");
+        let frames = |name: &str| {
+            std::fs::read_to_string(out.join(format!("{}.ndjson", name))).expect("output exists")
+        };
+        let (alpha, beta) = (frames("alpha"), frames("beta"));
+        assert_eq!(alpha.lines().count(), 5);
+        assert_eq!(beta.lines().count(), 5);
+        assert_ne!(alpha, beta);
+
+        std::fs::write(seeds.join("broken.json"), "{").expect("write broken seed");
+        std::fs::remove_dir_all(&out).ok();
+        let err =
+            run_seed_dir(&seeds, None, None, 3, &RunOutputs::frames_only(out.clone())).unwrap_err();
+        assert!(err.to_string().contains("1 of 3 seeds"), "{}", err);
+        assert_eq!(frames("beta").lines().count(), 3);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn summary_totals_match_the_causes_of_a_fixed_run() {
+        use std::collections::BTreeMap;
+
+        let seed = test_seed("summary", 4, 3);
+        let mut expected: BTreeMap<String, u64> = BTreeMap::new();
+        let mut live = build_world(&seed, None);
+        for tick in 1..=12 {
+            let world_seed = live.seed;
+            let (diff, _, _) = tick_once(&mut live, world_seed, tick).expect("tick succeeds");
+            for cause in &diff.causes {
+                *expected.entry(cause.code.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        let outputs = RunOutputs {
+            summary: true,
+            ..RunOutputs::frames_only("unused.ndjson".into())
+        };
+        let mut frames = Vec::new();
+        let summary = run_world_into(build_world(&seed, None), 12, &outputs, &mut frames)
+            .expect("run succeeds")
+            .expect("summary requested");
+        assert_eq!(frames.iter().filter(|&&byte| byte == b'\n').count(), 12);
+
+        let report = summary.to_json();
+        assert_eq!(report["ticks"], 12);
+        let counts: BTreeMap<String, u64> =
+            serde_json::from_value(report["cause_counts"].clone()).expect("cause counts");
+        assert_eq!(counts, expected);
+        let count = |code: &str| expected.get(code).copied().unwrap_or(0);
+        assert_eq!(report["volcanic_events"], count("volcanic_aerosol_pulse"));
+        assert_eq!(report["monsoon_onsets"], count("monsoon_onset"));
+        let top = expected.values().copied().max().expect("causes recorded");
+        assert_eq!(report["top_cause"]["count"], top);
+        let temp = &report["global_temp_c"];
+        assert!(temp["min"].as_f64() <= temp["mean"].as_f64());
+        assert!(temp["mean"].as_f64() <= temp["max"].as_f64());
+        assert!(report["peak_sea_level_mm"].is_i64(), "{}", report);
+
+        let quiet = run_world_into(
+            build_world(&seed, None),
+            2,
+            &RunOutputs::frames_only("unused.ndjson".into()),
+            Vec::new(),
+        )
+        .expect("run succeeds");
+        assert!(quiet.is_none());
+    }
+
+    #[test]
+    fn metrics_lines_carry_per_tick_biome_counts() {
+        let seed = test_seed("biome_stats", 4, 3);
+        let dir = std::env::temp_dir().join(format!("simstep-biomes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let metrics = dir.join("metrics.ndjson");
+        let outputs = RunOutputs {
+            metrics: Some(metrics.clone()),
+            ..RunOutputs::frames_only(dir.join("run.ndjson"))
+        };
+        run_world_into(build_world(&seed, None), 4, &outputs, Vec::new()).expect("run succeeds");
+
+        let lines = std::fs::read_to_string(&metrics).expect("metrics written");
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(lines.lines().count(), 4);
+        for line in lines.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).expect("metrics json");
+            let biomes = value["biomes"].as_object().expect("biomes object");
+            assert_eq!(biomes.len(), sim_core::biome::COUNT);
+            let total: u64 = biomes.values().filter_map(|count| count.as_u64()).sum();
+            assert_eq!(total, 12);
+        }
+    }
+
+    #[test]
+    fn seed_sweep_rejects_unknown_fields() {
+        assert!("humidity.tropics=0.1:0.2:0.1".parse::<SeedSweep>().is_err());
+        assert!("noise.octaves=1:3:1".parse::<SeedSweep>().is_ok());
+    }
+
+    #[test]
+    fn checkpoints_keep_newest_and_resume_matches_uninterrupted_run() {
+        let seed = test_seed("checkpoint", 4, 3);
+        let dir = std::env::temp_dir().join(format!("simstep-checkpoint-{}", std::process::id()));
+        let checkpoint_dir = dir.join("checkpoints");
+        std::fs::create_dir_all(&dir).expect("temp dir");
+
+        let outputs = RunOutputs {
+            checkpoints: Some(Checkpoints {
+                dir: checkpoint_dir.clone(),
+                every: 5,
+                keep: Some(2),
+            }),
+            ..RunOutputs::frames_only(dir.join("first.ndjson"))
+        };
+        run(&seed, None, None, 20, &outputs).expect("checkpointed run");
+        let ticks: Vec<u64> = list_checkpoints(&checkpoint_dir)
+            .expect("checkpoints listed")
+            .into_iter()
+            .map(|(tick, _)| tick)
+            .collect();
+        assert_eq!(ticks, vec![15, 20]);
+        assert_eq!(
+            std::fs::read_dir(&checkpoint_dir)
+                .expect("dir reads")
+                .count(),
+            2
+        );
+
+        let full = dir.join("full.ndjson");
+        run(
+            &seed,
+            None,
+            None,
+            25,
+            &RunOutputs::frames_only(full.clone()),
+        )
+        .expect("full run");
+        let resumed = dir.join("resumed.ndjson");
+        resume(
+            &checkpoint_dir,
+            None,
+            25,
+            &RunOutputs::frames_only(resumed.clone()),
+        )
+        .expect("resume succeeds");
+
+        let full = std::fs::read_to_string(&full).expect("full frames");
+        let resumed = std::fs::read_to_string(&resumed).expect("resumed frames");
+        std::fs::remove_dir_all(&dir).ok();
+        let tail: Vec<&str> = full.lines().skip(20).collect();
+        assert_eq!(resumed.lines().collect::<Vec<_>>(), tail);
+    }
+
+    #[test]
+    fn replaying_recorded_frames_matches_live_ticking() {
+        let seed = test_seed("replay", 4, 3);
+        let dir = std::env::temp_dir().join(format!("simstep-replay-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let frames = dir.join("run.ndjson");
+        run(
+            &seed,
+            None,
+            None,
+            15,
+            &RunOutputs::frames_only(frames.clone()),
+        )
+        .expect("recorded run");
+
+        let mut live = build_world(&seed, None);
+        for tick in 1..=10 {
+            let world_seed = live.seed;
+            tick_once(&mut live, world_seed, tick).expect("live tick");
+        }
+        let mut replayed = replay(&seed, None, &frames, 10).expect("replay succeeds");
+        let too_far = replay(&seed, None, &frames, 16);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(replayed.tick, 10);
+        // Hazard meters never reach frames.
+        for (replayed, live) in replayed.regions.iter_mut().zip(&mut live.regions) {
+            replayed.hazards = Default::default();
+            live.hazards = Default::default();
+        }
+        assert_eq!(
+            serde_json::to_value(&replayed.regions).unwrap(),
+            serde_json::to_value(&live.regions).unwrap()
+        );
+        assert_eq!(
+            replayed.climate.last_insolation_tenths,
+            live.climate.last_insolation_tenths
+        );
+        assert!(too_far.is_err());
+    }
+
+    #[test]
+    fn index_offsets_seek_to_matching_frame_lines() {
+        let seed = test_seed("index", 3, 2);
+        let dir = std::env::temp_dir().join(format!("simstep-index-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let out = dir.join("run.ndjson");
+        let index_path = dir.join("run.index.ndjson");
+        let outputs = RunOutputs {
+            index: Some(index_path.clone()),
+            ..RunOutputs::frames_only(out.clone())
+        };
+        run(&seed, None, None, 6, &outputs).expect("run succeeds");
+
+        let index = FrameIndex::load_from_path(&index_path).expect("index loads");
+        assert_eq!(index.len(), 6);
+        let mut reader = BufReader::new(std::fs::File::open(&out).expect("frames exist"));
+        for t in (1..=6).rev() {
+            let offset = index.offset_for_tick(t).expect("tick indexed");
+            reader.seek(SeekFrom::Start(offset)).expect("seek");
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read frame");
+            let frame: serde_json::Value = serde_json::from_str(&line).expect("frame json");
+            assert_eq!(frame["t"], t);
+        }
+        assert_eq!(index.offset_for_tick(7), None);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn compare_reports_first_differing_region_field() {
+        let seed = test_seed("compare", 3, 2);
+        let dir = std::env::temp_dir().join(format!("simstep-compare-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let old = dir.join("old.ndjson");
+        let same = dir.join("same.ndjson");
+        let changed = dir.join("changed.ndjson");
+        run(&seed, None, None, 3, &RunOutputs::frames_only(old.clone())).expect("run succeeds");
+        run(&seed, None, None, 3, &RunOutputs::frames_only(same.clone())).expect("run succeeds");
+        assert_eq!(compare_runs(&old, &same).expect("compare runs"), None);
+
+        let text = std::fs::read_to_string(&old).expect("frames exist");
+        let mut lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("frame json"))
+            .collect();
+        let previous = lines[1]["diff"]["temp"]["r:4"].as_i64().expect("r:4 temp");
+        lines[1]["diff"]["temp"]["r:4"] = serde_json::json!(previous + 3);
+        let edited: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        std::fs::write(&changed, edited.join("\n")).expect("write edited run");
+
+        let divergence = compare_runs(&old, &changed)
+            .expect("compare runs")
+            .expect("runs diverge");
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(divergence.tick, Some(2));
+        assert_eq!(divergence.field, "diff.temp.r:4");
+        assert_eq!(divergence.expected, previous.to_string());
+        assert_eq!(divergence.actual, (previous + 3).to_string());
+    }
+
+    #[test]
+    fn compare_needs_no_seed_file() {
+        let args = Args::try_parse_from(["simstep", "--compare", "a.ndjson", "b.ndjson"])
+            .expect("compare args parse");
+        assert_eq!(args.compare.map(|paths| paths.len()), Some(2));
+        let err = Args::try_parse_from(["simstep", "--compare", "a.ndjson"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WrongNumberOfValues);
+    }
+
+    #[test]
+    fn geojson_out_writes_one_feature_per_region() {
+        let seed = test_seed("geojson", 4, 3);
+        let dir = std::env::temp_dir().join(format!("simstep-geojson-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let geojson_path = dir.join("world.geojson");
+        let outputs = RunOutputs {
+            geojson: Some(geojson_path.clone()),
+            ..RunOutputs::frames_only(dir.join("run.ndjson"))
+        };
+        run(&seed, None, None, 2, &outputs).expect("run succeeds");
+
+        let text = std::fs::read_to_string(&geojson_path).expect("geojson written");
+        std::fs::remove_dir_all(&dir).ok();
+        let collection: serde_json::Value = serde_json::from_str(&text).expect("geojson parses");
+        assert_eq!(collection["type"], "FeatureCollection");
+        let features = collection["features"].as_array().expect("features array");
+        assert_eq!(features.len(), 4 * 3);
+        for feature in features {
+            assert_eq!(feature["geometry"]["type"], "Polygon");
+            let properties = feature["properties"].as_object().expect("properties");
+            for key in ["biome", "elevation_m", "temp_c", "water"] {
+                assert!(properties.contains_key(key), "missing {}", key);
+            }
+        }
+    }
 }
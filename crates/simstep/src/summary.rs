@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use sim_core::cause::{Code, Entry};
+use sim_core::diag::GlobalMeans;
+
+/// End-of-run totals printed by `--summary`.
+#[derive(Clone, Debug, Default)]
+pub struct RunSummary {
+    ticks: u64,
+    temp_min_c: f64,
+    temp_max_c: f64,
+    temp_sum_c: f64,
+    peak_sea_level_mm: Option<i32>,
+    cause_counts: BTreeMap<Code, u64>,
+}
+
+impl RunSummary {
+    /// Fold one tick's global means, causes, and sea level into the totals.
+    pub fn record(&mut self, means: &GlobalMeans, causes: &[Entry], sea_level_mm: i32) {
+        if self.ticks == 0 {
+            self.temp_min_c = means.temp_c;
+            self.temp_max_c = means.temp_c;
+        } else {
+            self.temp_min_c = self.temp_min_c.min(means.temp_c);
+            self.temp_max_c = self.temp_max_c.max(means.temp_c);
+        }
+        self.temp_sum_c += means.temp_c;
+        self.ticks += 1;
+        self.peak_sea_level_mm = Some(
+            self.peak_sea_level_mm
+                .map_or(sea_level_mm, |peak| peak.max(sea_level_mm)),
+        );
+        for cause in causes {
+            *self.cause_counts.entry(cause.code).or_insert(0) += 1;
+        }
+    }
+
+    pub fn count(&self, code: Code) -> u64 {
+        self.cause_counts.get(&code).copied().unwrap_or(0)
+    }
+
+    /// Most frequent cause code; ties go to the code declared first.
+    pub fn top_cause(&self) -> Option<(Code, u64)> {
+        self.cause_counts.iter().fold(
+            None,
+            |best: Option<(Code, u64)>, (&code, &count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((code, count)),
+            },
+        )
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mean_c = if self.ticks == 0 {
+            0.0
+        } else {
+            self.temp_sum_c / self.ticks as f64
+        };
+        let cause_counts: BTreeMap<String, u64> = self
+            .cause_counts
+            .iter()
+            .map(|(code, count)| (code.to_string(), *count))
+            .collect();
+        json!({
+            "ticks": self.ticks,
+            "global_temp_c": {
+                "min": self.temp_min_c,
+                "max": self.temp_max_c,
+                "mean": mean_c,
+            },
+            "volcanic_events": self.count(Code::VolcanicAerosolPulse),
+            "monsoon_onsets": self.count(Code::MonsoonOnset),
+            "peak_sea_level_mm": self.peak_sea_level_mm,
+            "top_cause": self.top_cause().map(|(code, count)| json!({
+                "code": code.to_string(),
+                "count": count,
+            })),
+            "cause_counts": cause_counts,
+        })
+    }
+}
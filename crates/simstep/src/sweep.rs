@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use sim_core::io::seed::Seed;
+
+/// Numeric seed fields that `--seed-sweep` can vary.
+const SWEEP_FIELDS: &[&str] = &[
+    "width",
+    "height",
+    "noise.octaves",
+    "noise.freq",
+    "noise.amp",
+    "noise.seed",
+    "humidity.equator",
+    "humidity.poles",
+];
+
+// TODO(agents): rationale - cap sweeps so a typo in the step cannot fill the disk.
+const MAX_SWEEP_STEPS: usize = 1_000;
+
+/// Parsed `field=start:end:step` sweep over a single seed parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedSweep {
+    pub field: String,
+    /// Swept values paired with the label used in output file names.
+    pub values: Vec<(String, f64)>,
+}
+
+impl FromStr for SeedSweep {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let (field, range) = spec.split_once('=').with_context(|| {
+            format!("seed sweep {:?} must look like field=start:end:step", spec)
+        })?;
+        let field = field.trim();
+        ensure!(
+            SWEEP_FIELDS.contains(&field),
+            "unsupported seed sweep field {:?} (expected one of {})",
+            field,
+            SWEEP_FIELDS.join(", ")
+        );
+
+        let parts: Vec<&str> = range.split(':').map(str::trim).collect();
+        ensure!(
+            parts.len() == 3,
+            "seed sweep range {:?} must be start:end:step",
+            range
+        );
+        let parse = |text: &str| {
+            text.parse::<f64>()
+                .with_context(|| format!("invalid seed sweep number {:?}", text))
+        };
+        let start = parse(parts[0])?;
+        let end = parse(parts[1])?;
+        let step = parse(parts[2])?;
+        ensure!(
+            start.is_finite() && end.is_finite() && step.is_finite(),
+            "seed sweep bounds must be finite"
+        );
+        ensure!(step > 0.0, "seed sweep step must be positive");
+        ensure!(end >= start, "seed sweep end must not precede start");
+
+        // Labels use the widest precision written in the spec so accumulated float error
+        // never leaks into file names or the swept values themselves.
+        let decimals = parts
+            .iter()
+            .map(|part| decimal_places(part))
+            .max()
+            .unwrap_or(0);
+        let steps = ((end - start) / step + 1e-9).floor() as usize + 1;
+        ensure!(
+            steps <= MAX_SWEEP_STEPS,
+            "seed sweep produces {} runs (max {})",
+            steps,
+            MAX_SWEEP_STEPS
+        );
+
+        let mut values = Vec::with_capacity(steps);
+        for index in 0..steps {
+            let label = format!("{:.*}", decimals, start + step * index as f64);
+            let value = parse(&label)?;
+            values.push((label, value));
+        }
+
+        Ok(Self {
+            field: field.to_string(),
+            values,
+        })
+    }
+}
+
+impl SeedSweep {
+    /// Clone `base` with the swept field set to `value`.
+    pub fn apply(&self, base: &Seed, value: f64) -> Result<Seed> {
+        let mut seed = base.clone();
+        match self.field.as_str() {
+            "width" => seed.width = integral(&self.field, value)?,
+            "height" => seed.height = integral(&self.field, value)?,
+            "noise.octaves" => seed.noise.octaves = integral(&self.field, value)?,
+            "noise.freq" => seed.noise.freq = value,
+            "noise.amp" => seed.noise.amp = value,
+            "noise.seed" => seed.noise.seed = integral(&self.field, value)?,
+            "humidity.equator" => seed.humidity.equator = value,
+            "humidity.poles" => seed.humidity.poles = value,
+            other => bail!("unsupported seed sweep field {:?}", other),
+        }
+        Ok(seed)
+    }
+
+    /// Output path for one swept value, e.g. `run.ndjson` → `run_humidity.equator_0.3.ndjson`.
+    pub fn output_path(&self, base: &Path, label: &str) -> PathBuf {
+        let stem = base
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut name = format!("{}_{}_{}", stem, self.field, label);
+        if let Some(extension) = base.extension() {
+            name.push('.');
+            name.push_str(&extension.to_string_lossy());
+        }
+        base.with_file_name(name)
+    }
+}
+
+fn decimal_places(text: &str) -> usize {
+    text.split_once('.')
+        .map(|(_, fraction)| fraction.len())
+        .unwrap_or(0)
+}
+
+fn integral<T: TryFrom<u64>>(field: &str, value: f64) -> Result<T> {
+    ensure!(
+        value >= 0.0 && value.fract() == 0.0,
+        "seed sweep field {} requires non-negative whole numbers, got {}",
+        field,
+        value
+    );
+    T::try_from(value as u64)
+        .map_err(|_| anyhow!("seed sweep value {} out of range for {}", value, field))
+}
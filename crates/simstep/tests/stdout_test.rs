@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn seed_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../testdata/seeds/seed_golden_harness.json")
+}
+
+#[test]
+fn out_dash_streams_the_same_bytes_as_a_frame_file() {
+    let dir = std::env::temp_dir().join(format!("simstep-stdout-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let file_out = dir.join("run.ndjson");
+    let metrics = dir.join("metrics.ndjson");
+    let simstep = || {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_simstep"));
+        command
+            .arg("--seed-file")
+            .arg(seed_path())
+            .args(["--ticks", "6"]);
+        command
+    };
+
+    let to_file = simstep()
+        .arg("--out")
+        .arg(&file_out)
+        .output()
+        .expect("simstep runs");
+    assert!(to_file.status.success(), "{:?}", to_file);
+    let to_stdout = simstep()
+        .args(["--out", "-", "--emit-metrics"])
+        .arg(&metrics)
+        .output()
+        .expect("simstep runs");
+    assert!(to_stdout.status.success(), "{:?}", to_stdout);
+
+    let frames = std::fs::read(&file_out).expect("frame file written");
+    let metrics = std::fs::read_to_string(&metrics).expect("metrics written");
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(frames.iter().filter(|&&byte| byte == b'\n').count(), 6);
+    assert_eq!(to_stdout.stdout, frames);
+    assert_eq!(metrics.lines().count(), 6);
+}
+
+#[test]
+fn out_dash_rejects_multi_run_modes() {
+    let output = Command::new(env!("CARGO_BIN_EXE_simstep"))
+        .arg("--seed-file")
+        .arg(seed_path())
+        .args(["--ticks", "2", "--out", "-", "--ensemble", "2"])
+        .output()
+        .expect("simstep runs");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}